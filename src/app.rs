@@ -1,31 +1,61 @@
 use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::command::{complete, Command};
+use crate::config::AppConfig;
 use crate::protocol::UiToEngine;
-use crate::engine::scheduler::TaskUpdate;
-use crate::task::TaskSnapshot;
+use crate::report::ReportFormat;
+use crate::engine::scheduler::{LogLevel, TaskUpdate};
+use crate::path_picker::{PathPicker, PathTarget};
+use crate::preview::ImagePreviews;
+use crate::spec::{
+    BudgetSpec, HeuristicSpec, LlmSpec, ReportSpec, SpecField, SpecIssue, TaskSpec,
+};
+use crate::task::{Hypothesis, TaskSnapshot, TaskStatus};
+use crate::text::TextBuffer;
+use crate::screens::dialog::{Dialog, DialogKind};
+use crate::screens::keymap::Keymaps;
+use crate::screens::mouse::MouseMap;
+use crate::screens::theme::Theme;
 use crate::screens::{FragmentId, ScreenId};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+const DEFAULT_SPEC_PATH: &str = "task.toml";
+/// Command-line entries kept for Up/Down recall.
+const COMMAND_HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DraftField {
     Name,
     DatasetFolder,
     Heuristics,
+    SpecPath,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HeuristicsFocus {
     Titles,
     Images,
 }
 
+/// The draft list that delete/duplicate/move events act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftList {
+    Hypotheses,
+    Heuristics,
+    Images,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AppEvent {
     Quit,
     ToggleCursor,
     Main(MainScreenEvent),
     TaskInput(TaskInputEvent),
+    Hypotheses(HypothesesEvent),
+    Command(Command),
+    Dialog(DialogEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +66,69 @@ pub enum MainScreenEvent {
     FocusInput,
     SelectTaskNext,
     SelectTaskPrev,
+    SelectTask(usize),
+    /// Asks before cancelling the selected task.
     CancelSelectedTask,
+    /// Asks for a report format, then exports the selected task.
+    ExportSelectedTask,
+    /// Quits, asking first while tasks are still running.
+    Quit,
+    FocusLogs,
+    /// Positive values scroll towards older lines.
+    ScrollLogs(isize),
+    ScrollLogsTop,
+    ScrollLogsBottom,
+    StartLogSearch,
+    LogSearch(TextEditEvent),
+    EndLogSearch,
+    ClearLogSearch,
+    OpenHypotheses,
+    CommandLine(CommandLineEvent),
+    CycleTaskFilter,
+    CycleTaskSort,
+    CycleTaskGrouping,
+    StartTaskSearch,
+    TaskSearch(TextEditEvent),
+    EndTaskSearch,
+    ClearTaskSearch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CommandLineEvent {
+    Edit(TextEditEvent),
+    Complete,
+    HistoryPrev,
+    HistoryNext,
+    Submit,
+    /// Clears the line and returns focus to the task list.
+    Leave,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HypothesesEvent {
+    Close,
+    SelectNext,
+    SelectPrev,
+    Select(usize),
+    NextImage,
+    PrevImage,
+    CycleSort,
+    CycleFilter,
+    TogglePin,
+    ToggleReject,
+    StartInject,
+    InjectEdit(TextEditEvent),
+    SubmitInject,
+    CancelInject,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DialogEvent {
+    Open(Dialog),
+    /// Yes on a confirmation, OK on a prompt; closes any other dialog.
+    Confirm,
+    Cancel,
+    Edit(TextEditEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +139,16 @@ pub enum TextEditEvent {
     MoveDown,
     Backspace,
     InsertChar(char),
+    Delete,
+    Home,
+    End,
+    WordLeft,
+    WordRight,
+    DeleteWordBack,
+    DeleteToStart,
+    DeleteToEnd,
+    /// Inserts pasted text; line breaks become spaces.
+    Paste(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,12 +157,33 @@ pub enum TaskInputEvent {
     SwitchField,
     FocusDescription,
     FocusHypotheses,
+    FocusField(DraftField),
+    FocusHeuristicList(HeuristicsFocus),
+    SelectItem(usize),
     AddHeuristic,
     AddImage,
+    DeleteItem,
+    DuplicateItem,
+    MoveItemUp,
+    MoveItemDown,
+    OpenSpec,
+    SaveSpec,
+    OpenPathPicker,
+    PathPicker(PathPickerEvent),
     Edit(TextEditEvent),
     Submit,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PathPickerEvent {
+    SelectNext,
+    SelectPrev,
+    Filter(TextEditEvent),
+    Complete,
+    Confirm,
+    Close,
+}
+
 #[derive(Debug, Default)]
 pub struct EventResult {
     pub quit: bool,
@@ -69,21 +192,30 @@ pub struct EventResult {
 
 #[derive(Debug, Clone)]
 pub struct HypothesisDraft {
-    pub title: String,
+    pub title: TextBuffer,
 }
 
 #[derive(Debug, Clone)]
 pub struct HeuristicDraft {
-    pub title: String,
-    pub images: Vec<String>,
+    pub title: TextBuffer,
+    pub images: Vec<TextBuffer>,
 }
 
 #[derive(Debug, Clone)]
 pub struct TaskDraft {
     pub name: String,
     pub dataset_folder: String,
+    pub labels: Vec<String>,
     pub heuristics: Vec<HeuristicDraft>,
     pub hypotheses: Vec<HypothesisDraft>,
+    pub budget: BudgetSpec,
+    pub llm: LlmSpec,
+    pub report: ReportSpec,
+    pub spec_path: String,
+    pub spec_status: Option<String>,
+    /// Problems found by the last Submit; kept up to date until resolved.
+    /// Issues with fields the screen can't edit only warn (`TaskDraft::can_edit`).
+    pub issues: Vec<SpecIssue>,
     pub field: DraftField,
     pub selected_hypothesis: usize,
     pub selected_heuristic: usize,
@@ -91,126 +223,727 @@ pub struct TaskDraft {
     pub heuristics_focus: HeuristicsFocus,
 }
 
+impl TaskDraft {
+    /// Labels, budget and LLM settings come from a loaded spec or the config
+    /// and have no field on the task input screen.
+    pub fn can_edit(field: SpecField) -> bool {
+        !matches!(field, SpecField::Labels | SpecField::Budget | SpecField::Llm)
+    }
+
+    /// Whether an issue stops Submit; the rest are warnings.
+    pub fn blocks_submit(&self) -> bool {
+        self.issues.iter().any(|issue| Self::can_edit(issue.field))
+    }
+
+    pub fn from_spec(spec: &TaskSpec) -> Self {
+        Self {
+            name: spec.name.clone(),
+            dataset_folder: spec.dataset_folder.clone(),
+            labels: spec.labels.clone(),
+            heuristics: spec
+                .heuristics
+                .iter()
+                .map(|h| HeuristicDraft {
+                    title: TextBuffer::new(h.title.as_str()),
+                    images: h.images.iter().map(|image| TextBuffer::new(image.as_str())).collect(),
+                })
+                .collect(),
+            hypotheses: spec
+                .hypotheses
+                .iter()
+                .map(|title| HypothesisDraft {
+                    title: TextBuffer::new(title.as_str()),
+                })
+                .collect(),
+            budget: spec.budget.clone(),
+            llm: spec.llm.clone(),
+            report: spec.report.clone(),
+            spec_path: DEFAULT_SPEC_PATH.to_string(),
+            spec_status: None,
+            issues: Vec::new(),
+            field: DraftField::Name,
+            selected_hypothesis: 0,
+            selected_heuristic: 0,
+            selected_image: 0,
+            heuristics_focus: HeuristicsFocus::Titles,
+        }
+    }
+
+    pub fn to_spec(&self) -> TaskSpec {
+        TaskSpec {
+            name: self.name.trim().to_string(),
+            dataset_folder: self.dataset_folder.clone(),
+            labels: self.labels.clone(),
+            hypotheses: self
+                .hypotheses
+                .iter()
+                .map(|h| h.title.as_str().trim().to_string())
+                .filter(|title| !title.is_empty())
+                .collect(),
+            budget: self.budget.clone(),
+            llm: self.llm.clone(),
+            report: self.report.clone(),
+            heuristics: self
+                .heuristics
+                .iter()
+                .map(|h| HeuristicSpec {
+                    title: h.title.as_str().to_string(),
+                    images: h.images.iter().map(|image| image.as_str().to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub level: LogLevel,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogView {
+    /// Lines scrolled up from the newest one; 0 follows new output.
+    pub scroll: usize,
+    pub query: String,
+    pub searching: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HypothesisMark {
+    Pinned,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HypothesisSort {
+    #[default]
+    ScoreDesc,
+    ScoreAsc,
+    Iteration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HypothesisFilter {
+    #[default]
+    All,
+    Verified,
+    Discarded,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExplorerState {
+    pub task_id: Option<usize>,
+    pub sort: HypothesisSort,
+    pub filter: HypothesisFilter,
+    pub selected: usize,
+    /// Per-image result of the selected hypothesis shown in the preview.
+    pub image: usize,
+    /// Text of a hypothesis being written for injection, while the prompt is open.
+    pub inject: Option<TextBuffer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TaskFilter {
+    #[default]
+    All,
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TaskSort {
+    /// Oldest first.
+    #[default]
+    Created,
+    Newest,
+    /// Best score, highest first.
+    Score,
+    /// Progress, furthest first.
+    Progress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TaskGrouping {
+    #[default]
+    None,
+    Status,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskSearch {
+    pub query: String,
+    pub searching: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandLine {
+    pub text: TextBuffer,
+    /// Submitted lines, oldest first.
+    pub history: Vec<String>,
+    /// Index into `history` while recalling entries with Up/Down.
+    pub history_pos: Option<usize>,
+    pub message: Option<(LogLevel, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExplorerRow {
+    pub hypothesis: Hypothesis,
+    pub verified: bool,
+    pub mark: Option<HypothesisMark>,
+}
+
+impl HypothesisSort {
+    pub fn next(self) -> Self {
+        match self {
+            HypothesisSort::ScoreDesc => HypothesisSort::ScoreAsc,
+            HypothesisSort::ScoreAsc => HypothesisSort::Iteration,
+            HypothesisSort::Iteration => HypothesisSort::ScoreDesc,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HypothesisSort::ScoreDesc => "score desc",
+            HypothesisSort::ScoreAsc => "score asc",
+            HypothesisSort::Iteration => "iteration",
+        }
+    }
+}
+
+impl HypothesisFilter {
+    pub fn next(self) -> Self {
+        match self {
+            HypothesisFilter::All => HypothesisFilter::Verified,
+            HypothesisFilter::Verified => HypothesisFilter::Discarded,
+            HypothesisFilter::Discarded => HypothesisFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HypothesisFilter::All => "all",
+            HypothesisFilter::Verified => "verified",
+            HypothesisFilter::Discarded => "discarded",
+        }
+    }
+}
+
+impl TaskFilter {
+    pub const ALL: [TaskFilter; 6] = [
+        TaskFilter::All,
+        TaskFilter::Pending,
+        TaskFilter::Running,
+        TaskFilter::Done,
+        TaskFilter::Failed,
+        TaskFilter::Cancelled,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskFilter::All => "all",
+            TaskFilter::Pending => "pending",
+            TaskFilter::Running => "running",
+            TaskFilter::Done => "done",
+            TaskFilter::Failed => "failed",
+            TaskFilter::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase();
+        Self::ALL.into_iter().find(|filter| filter.label() == value)
+    }
+
+    /// Status groups in the order the grouped task list shows them.
+    pub const GROUPS: [TaskFilter; 5] = [
+        TaskFilter::Running,
+        TaskFilter::Pending,
+        TaskFilter::Done,
+        TaskFilter::Failed,
+        TaskFilter::Cancelled,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|filter| *filter == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn of(status: &TaskStatus) -> Self {
+        match status {
+            TaskStatus::Pending => TaskFilter::Pending,
+            TaskStatus::Running => TaskFilter::Running,
+            TaskStatus::Done => TaskFilter::Done,
+            TaskStatus::Failed(_) => TaskFilter::Failed,
+            TaskStatus::Cancelled => TaskFilter::Cancelled,
+        }
+    }
+
+    pub fn matches(self, status: &TaskStatus) -> bool {
+        matches!(
+            (self, status),
+            (TaskFilter::All, _)
+                | (TaskFilter::Pending, TaskStatus::Pending)
+                | (TaskFilter::Running, TaskStatus::Running)
+                | (TaskFilter::Done, TaskStatus::Done)
+                | (TaskFilter::Failed, TaskStatus::Failed(_))
+                | (TaskFilter::Cancelled, TaskStatus::Cancelled)
+        )
+    }
+}
+
+impl TaskSort {
+    pub const ALL: [TaskSort; 4] = [
+        TaskSort::Created,
+        TaskSort::Newest,
+        TaskSort::Score,
+        TaskSort::Progress,
+    ];
+
+    pub fn next(self) -> Self {
+        match self {
+            TaskSort::Created => TaskSort::Newest,
+            TaskSort::Newest => TaskSort::Score,
+            TaskSort::Score => TaskSort::Progress,
+            TaskSort::Progress => TaskSort::Created,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSort::Created => "created",
+            TaskSort::Newest => "newest",
+            TaskSort::Score => "score",
+            TaskSort::Progress => "progress",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase();
+        Self::ALL.into_iter().find(|sort| sort.label() == value)
+    }
+}
+
+impl TaskGrouping {
+    pub const ALL: [TaskGrouping; 2] = [TaskGrouping::None, TaskGrouping::Status];
+
+    pub fn next(self) -> Self {
+        match self {
+            TaskGrouping::None => TaskGrouping::Status,
+            TaskGrouping::Status => TaskGrouping::None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskGrouping::None => "none",
+            TaskGrouping::Status => "status",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase();
+        Self::ALL.into_iter().find(|grouping| grouping.label() == value)
+    }
+}
+
 #[derive(Debug)]
 pub struct AppState {
     tasks_by_id: HashMap<usize, TaskSnapshot>,
     order: Vec<usize>,
     pub selected: usize,
-    pub input: String,
+    /// The focused Name, Dataset Folder or Spec File field while it is edited.
+    pub input: TextBuffer,
     pub screen: ScreenId,
     pub fragment: FragmentId,
     pub draft: TaskDraft,
-    pub cursor_pos: usize,
     pub cursor_visible: bool,
+    pub config: AppConfig,
+    pub log_view: LogView,
+    pub explorer: ExplorerState,
+    pub command_line: CommandLine,
+    pub task_filter: TaskFilter,
+    pub task_sort: TaskSort,
+    pub task_grouping: TaskGrouping,
+    pub task_search: TaskSearch,
+    pub path_picker: Option<PathPicker>,
+    pub previews: ImagePreviews,
+    pub mouse: MouseMap,
+    /// Active key bindings: screen defaults with `[keys]` overrides applied.
+    pub keymaps: Keymaps,
+    /// Styles for every fragment: `[theme]` palette, off under `NO_COLOR`.
+    pub theme: Theme,
+    /// Open modal dialogs; the last one is drawn on top and takes the keys.
+    pub dialogs: Vec<Dialog>,
     event_queue: VecDeque<AppEvent>,
-    logs: Vec<String>,
+    logs: HashMap<usize, VecDeque<LogEntry>>,
+    marks: HashMap<(usize, usize), HypothesisMark>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let draft = TaskDraft {
-            name: String::new(),
-            dataset_folder: String::from("./datasets/mock"),
-            heuristics: vec![
-                HeuristicDraft {
-                    title: "Edge threshold".to_string(),
-                    images: vec!["/data/edge_01.png".to_string()],
-                },
-                HeuristicDraft {
-                    title: "Blob area filter".to_string(),
-                    images: vec![
-                        "/data/blob_03.png".to_string(),
-                        "/data/blob_07.png".to_string(),
-                    ],
-                },
-            ],
-            hypotheses: vec![
-                HypothesisDraft {
-                    title: "Edge + blob fusion".to_string(),
-                },
-                HypothesisDraft {
-                    title: "Contrast boosted contours".to_string(),
-                },
-                HypothesisDraft {
-                    title: "Texture density heuristic".to_string(),
-                },
-            ],
-            field: DraftField::Name,
-            selected_hypothesis: 0,
-            selected_heuristic: 0,
-            selected_image: 0,
-            heuristics_focus: HeuristicsFocus::Titles,
-        };
+        Self::with_config(AppConfig::default())
+    }
+
+    pub fn with_config(config: AppConfig) -> Self {
+        let draft = TaskDraft::from_spec(&config.default_spec());
+        let previews = ImagePreviews::new(config.ui.image_protocol);
+        let keymaps = Keymaps::build(&config.keys).unwrap_or_default();
+        let theme = Theme::from_config(&config.theme).unwrap_or_default();
         Self {
             tasks_by_id: HashMap::new(),
             order: Vec::new(),
             selected: 0,
-            input: String::new(),
+            input: TextBuffer::default(),
             screen: ScreenId::Main,
             fragment: FragmentId::MainTasks,
             draft,
-            cursor_pos: 0,
             cursor_visible: true,
+            config,
+            log_view: LogView::default(),
+            explorer: ExplorerState::default(),
+            command_line: CommandLine::default(),
+            task_filter: TaskFilter::default(),
+            task_sort: TaskSort::default(),
+            task_grouping: TaskGrouping::default(),
+            task_search: TaskSearch::default(),
+            path_picker: None,
+            previews,
+            mouse: MouseMap::default(),
+            keymaps,
+            theme,
+            dialogs: Vec::new(),
             event_queue: VecDeque::new(),
-            logs: Vec::new(),
+            logs: HashMap::new(),
+            marks: HashMap::new(),
         }
     }
 
     pub fn apply_update(&mut self, update: TaskUpdate) {
         match update {
             TaskUpdate::Upsert(snapshot) => {
+                let selected = self.selected_task_id();
+                let hypothesis = self.selected_hypothesis_id();
                 let id = snapshot.id;
+                let was_failed = self
+                    .task(id)
+                    .is_some_and(|task| matches!(task.status, TaskStatus::Failed(_)));
+                if let TaskStatus::Failed(err) = &snapshot.status
+                    && !was_failed
+                {
+                    self.show_task_error(id, format!("Task failed: {err}"));
+                }
                 let is_new = !self.tasks_by_id.contains_key(&id);
                 self.tasks_by_id.insert(id, snapshot);
                 if is_new {
                     self.order.push(id);
                 }
-                if self.selected >= self.order.len() && !self.order.is_empty() {
-                    self.selected = self.order.len() - 1;
+                // Scores and statuses move tasks around; keep the same one selected.
+                self.reselect(selected);
+                // Steering and new results reorder the explorer rows the same way.
+                if self.explorer.task_id == Some(id) {
+                    self.reselect_hypothesis(hypothesis);
+                }
+            }
+            TaskUpdate::Log { id, level, message } => {
+                if level == LogLevel::Error {
+                    self.show_task_error(id, message.clone());
+                }
+                let capacity = self.config.ui.log_capacity;
+                let logs = self.logs.entry(id).or_default();
+                logs.push_back(LogEntry { level, message });
+                while logs.len() > capacity {
+                    logs.pop_front();
                 }
             }
-            TaskUpdate::Log { id, message } => {
-                self.logs.push(format!("Task {id}: {message}"));
-                if self.logs.len() > 5 {
-                    self.logs.remove(0);
+            TaskUpdate::SteeringDropped {
+                id,
+                hypothesis_id,
+                message,
+            } => {
+                if let Some(hypothesis_id) = hypothesis_id {
+                    self.marks.remove(&(id, hypothesis_id));
+                }
+                self.open_dialog(Dialog::message("Steering dropped", message.clone()));
+                self.apply_update(TaskUpdate::Log {
+                    id,
+                    level: LogLevel::Warn,
+                    message,
+                });
+            }
+        }
+    }
+
+    pub fn open_dialog(&mut self, dialog: Dialog) {
+        self.dialogs.push(dialog);
+    }
+
+    /// Engine errors of one task share a dialog while it is the topmost one,
+    /// so a failure and the error logged before it read as one report.
+    fn show_task_error(&mut self, id: usize, message: String) {
+        let title = format!("Task {id} error");
+        match self.dialogs.last_mut() {
+            Some(dialog) if matches!(dialog.kind, DialogKind::Error) && dialog.title == title => {
+                dialog.message.push('\n');
+                dialog.message.push_str(&message);
+            }
+            _ => self.open_dialog(Dialog::error(title, message)),
+        }
+    }
+
+    fn apply_dialog_event(&mut self, event: DialogEvent) {
+        match event {
+            DialogEvent::Open(dialog) => self.open_dialog(dialog),
+            DialogEvent::Confirm => match self.dialogs.pop().map(|dialog| dialog.kind) {
+                Some(DialogKind::Confirm(event)) => self.enqueue_event(*event),
+                Some(DialogKind::Prompt { command, input }) => {
+                    match Command::parse(&format!("{command} {}", input.as_str())) {
+                        Ok(command) => self.enqueue_event(AppEvent::Command(command)),
+                        Err(err) => self.open_dialog(Dialog::error("Invalid input", err)),
+                    }
+                }
+                _ => {}
+            },
+            DialogEvent::Cancel => {
+                self.dialogs.pop();
+            }
+            DialogEvent::Edit(edit) => {
+                if let Some(Dialog {
+                    kind: DialogKind::Prompt { input, .. },
+                    ..
+                }) = self.dialogs.last_mut()
+                {
+                    input.apply(&edit);
                 }
             }
         }
     }
 
     pub fn select_next(&mut self) {
-        if self.order.is_empty() {
+        let visible = self.visible_task_ids().len();
+        if visible == 0 {
             return;
         }
-        self.selected = (self.selected + 1).min(self.order.len() - 1);
+        self.selected = (self.selected + 1).min(visible - 1);
+        self.log_view.scroll = 0;
     }
 
     pub fn select_prev(&mut self) {
-        if self.order.is_empty() {
-            return;
-        }
         if self.selected == 0 {
             return;
         }
         self.selected -= 1;
+        self.log_view.scroll = 0;
+    }
+
+    /// Ids of the tasks passing `task_filter` and the name search, ordered by
+    /// `task_sort` within the `task_grouping` groups.
+    pub fn visible_task_ids(&self) -> Vec<usize> {
+        let query = self.task_search.query.to_lowercase();
+        let mut tasks = self
+            .order
+            .iter()
+            .filter_map(|id| self.tasks_by_id.get(id))
+            .filter(|task| self.task_filter.matches(&task.status))
+            .filter(|task| query.is_empty() || task.name.to_lowercase().contains(&query))
+            .collect::<Vec<_>>();
+        match self.task_sort {
+            TaskSort::Created => {}
+            TaskSort::Newest => tasks.reverse(),
+            TaskSort::Score => tasks.sort_by(|a, b| b.best_score.total_cmp(&a.best_score)),
+            TaskSort::Progress => tasks.sort_by(|a, b| b.progress.total_cmp(&a.progress)),
+        }
+        if self.task_grouping == TaskGrouping::Status {
+            tasks.sort_by_key(|task| {
+                let group = TaskFilter::of(&task.status);
+                TaskFilter::GROUPS.iter().position(|g| *g == group)
+            });
+        }
+        tasks.into_iter().map(|task| task.id).collect()
+    }
+
+    fn selected_task_id(&self) -> Option<usize> {
+        self.visible_task_ids().get(self.selected).copied()
+    }
+
+    /// Selects task `id` again after the list changed, or keeps the row
+    /// index within bounds when it is no longer shown.
+    fn reselect(&mut self, id: Option<usize>) {
+        let visible = self.visible_task_ids();
+        match id.and_then(|id| visible.iter().position(|other| *other == id)) {
+            Some(index) => self.selected = index,
+            None => {
+                self.selected = self.selected.min(visible.len().saturating_sub(1));
+                if id.is_some() {
+                    self.log_view.scroll = 0;
+                }
+            }
+        }
+    }
+
+    fn change_task_list(&mut self, change: impl FnOnce(&mut Self)) {
+        let selected = self.selected_task_id();
+        change(self);
+        self.reselect(selected);
     }
 
     pub fn tasks_in_order(&self) -> Vec<TaskSnapshot> {
-        self.order
+        self.visible_task_ids()
             .iter()
             .filter_map(|id| self.tasks_by_id.get(id).cloned())
             .collect()
     }
 
     pub fn selected_task(&self) -> Option<TaskSnapshot> {
-        self.order
+        self.visible_task_ids()
             .get(self.selected)
             .and_then(|id| self.tasks_by_id.get(id))
             .cloned()
     }
 
-    pub fn recent_logs(&self) -> &[String] {
-        &self.logs
+    pub fn task(&self, id: usize) -> Option<&TaskSnapshot> {
+        self.tasks_by_id.get(&id)
+    }
+
+    pub fn task_logs(&self, id: usize) -> impl Iterator<Item = &LogEntry> {
+        self.logs.get(&id).into_iter().flatten()
+    }
+
+    pub fn last_log(&self, id: usize) -> Option<&LogEntry> {
+        self.logs.get(&id).and_then(|logs| logs.back())
+    }
+
+    /// Log lines of the selected task matching the search query, oldest first.
+    pub fn visible_logs(&self) -> Vec<&LogEntry> {
+        let Some(id) = self.visible_task_ids().get(self.selected).copied() else {
+            return Vec::new();
+        };
+        let query = self.log_view.query.to_lowercase();
+        self.task_logs(id)
+            .filter(|entry| query.is_empty() || entry.message.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    pub fn scroll_logs(&mut self, delta: isize) {
+        let max = self.visible_logs().len().saturating_sub(1);
+        self.log_view.scroll = self.log_view.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    pub fn open_hypotheses(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        self.explorer.task_id = Some(task.id);
+        self.explorer.selected = 0;
+        self.explorer.image = 0;
+        self.screen = ScreenId::Hypotheses;
+        self.fragment = FragmentId::HypothesisList;
+    }
+
+    pub fn close_hypotheses(&mut self) {
+        self.explorer.inject = None;
+        self.screen = ScreenId::Main;
+        self.fragment = FragmentId::MainTasks;
+    }
+
+    pub fn explorer_task(&self) -> Option<&TaskSnapshot> {
+        self.explorer
+            .task_id
+            .and_then(|id| self.tasks_by_id.get(&id))
+    }
+
+    pub fn explorer_rows(&self) -> Vec<ExplorerRow> {
+        let Some(task) = self.explorer_task() else {
+            return Vec::new();
+        };
+        let verified = task.verified.iter().map(|h| (h, true));
+        let discarded = task.discarded.iter().map(|h| (h, false));
+        let mut rows = verified
+            .chain(discarded)
+            .filter(|(_, verified)| match self.explorer.filter {
+                HypothesisFilter::All => true,
+                HypothesisFilter::Verified => *verified,
+                HypothesisFilter::Discarded => !*verified,
+            })
+            .map(|(hypothesis, verified)| ExplorerRow {
+                hypothesis: hypothesis.clone(),
+                verified,
+                mark: self.hypothesis_mark(task.id, hypothesis.id),
+            })
+            .collect::<Vec<_>>();
+        match self.explorer.sort {
+            HypothesisSort::ScoreDesc => {
+                rows.sort_by(|a, b| b.hypothesis.score.total_cmp(&a.hypothesis.score))
+            }
+            HypothesisSort::ScoreAsc => {
+                rows.sort_by(|a, b| a.hypothesis.score.total_cmp(&b.hypothesis.score))
+            }
+            HypothesisSort::Iteration => rows.sort_by_key(|row| row.hypothesis.id),
+        }
+        rows
+    }
+
+    pub fn selected_explorer_row(&self) -> Option<ExplorerRow> {
+        self.explorer_rows().into_iter().nth(self.explorer.selected)
+    }
+
+    fn selected_hypothesis_id(&self) -> Option<usize> {
+        self.selected_explorer_row().map(|row| row.hypothesis.id)
+    }
+
+    /// Selects hypothesis `id` again after the explorer rows changed, or keeps
+    /// the row index within bounds when it is no longer shown.
+    fn reselect_hypothesis(&mut self, id: Option<usize>) {
+        let rows = self.explorer_rows();
+        match id.and_then(|id| rows.iter().position(|row| row.hypothesis.id == id)) {
+            Some(index) => self.explorer.selected = index,
+            None => {
+                self.explorer.selected = self.explorer.selected.min(rows.len().saturating_sub(1));
+                self.explorer.image = 0;
+            }
+        }
+    }
+
+    pub fn hypothesis_mark(&self, task_id: usize, hypothesis_id: usize) -> Option<HypothesisMark> {
+        self.marks.get(&(task_id, hypothesis_id)).copied()
+    }
+
+    /// Sets `mark` on the selected hypothesis, or clears it if already set,
+    /// and returns the matching steering command for the engine.
+    pub fn toggle_mark(&mut self, mark: HypothesisMark) -> Option<UiToEngine> {
+        let (Some(task_id), Some(row)) = (self.explorer.task_id, self.selected_explorer_row())
+        else {
+            return None;
+        };
+        let hypothesis_id = row.hypothesis.id;
+        let key = (task_id, hypothesis_id);
+        if row.mark == Some(mark) {
+            self.marks.remove(&key);
+            return Some(UiToEngine::ReleaseHypothesis {
+                task_id,
+                hypothesis_id,
+            });
+        }
+        self.marks.insert(key, mark);
+        Some(match mark {
+            HypothesisMark::Pinned => UiToEngine::PinHypothesis {
+                task_id,
+                hypothesis_id,
+            },
+            HypothesisMark::Rejected => UiToEngine::BanHypothesis {
+                task_id,
+                hypothesis_id,
+            },
+        })
     }
 
     pub fn set_fragment(&mut self, fragment: FragmentId) {
@@ -222,59 +955,300 @@ impl AppState {
         self.fragment = FragmentId::TaskDescription;
         self.draft.field = DraftField::Name;
         self.draft.heuristics_focus = HeuristicsFocus::Titles;
-        self.input = self.draft.name.clone();
-        self.cursor_pos = self.input.len();
+        self.input = TextBuffer::new(self.draft.name.as_str());
     }
 
     pub fn close_task_input(&mut self) {
+        self.path_picker = None;
         self.screen = ScreenId::Main;
         self.fragment = FragmentId::MainTasks;
         self.input.clear();
-        self.cursor_pos = 0;
     }
 
     pub fn commit_draft_field(&mut self) {
+        let text = self.input.as_str().to_string();
         match self.draft.field {
-            DraftField::Name => self.draft.name = self.input.clone(),
-            DraftField::DatasetFolder => self.draft.dataset_folder = self.input.clone(),
+            DraftField::Name => self.draft.name = text,
+            DraftField::DatasetFolder => self.draft.dataset_folder = text,
             DraftField::Heuristics => {}
+            DraftField::SpecPath => self.draft.spec_path = text,
         }
     }
 
     pub fn load_draft_field(&mut self) {
-        self.input = match self.draft.field {
-            DraftField::Name => self.draft.name.clone(),
-            DraftField::DatasetFolder => self.draft.dataset_folder.clone(),
-            DraftField::Heuristics => String::new(),
-        };
-        self.cursor_pos = self.input.len();
+        self.input = TextBuffer::new(match self.draft.field {
+            DraftField::Name => self.draft.name.as_str(),
+            DraftField::DatasetFolder => self.draft.dataset_folder.as_str(),
+            DraftField::Heuristics => "",
+            DraftField::SpecPath => self.draft.spec_path.as_str(),
+        });
     }
 
     pub fn reset_draft(&mut self) {
         self.draft.name.clear();
-        self.draft.dataset_folder = "./datasets/mock".to_string();
+        self.draft.dataset_folder = self.config.defaults.dataset_folder.clone();
+        self.draft.spec_status = None;
+        self.draft.issues.clear();
         self.draft.field = DraftField::Name;
         self.draft.selected_hypothesis = 0;
         self.draft.selected_heuristic = 0;
         self.draft.selected_image = 0;
         self.draft.heuristics_focus = HeuristicsFocus::Titles;
         self.input.clear();
-        self.cursor_pos = 0;
+    }
+
+    /// Replaces the draft with the spec at `draft.spec_path`, keeping the path.
+    pub fn open_spec(&mut self) {
+        self.commit_draft_field();
+        let path = self.draft.spec_path.clone();
+        match TaskSpec::load_over(&path, &self.config.default_spec()) {
+            Ok(spec) => {
+                let field = self.draft.field;
+                self.draft = TaskDraft::from_spec(&spec);
+                self.draft.spec_path = path.clone();
+                self.draft.field = field;
+                self.draft.spec_status = Some(format!("Loaded {path}"));
+            }
+            Err(err) => self.draft.spec_status = Some(err),
+        }
+        self.load_draft_field();
+    }
+
+    pub fn save_spec(&mut self) {
+        self.commit_draft_field();
+        let path = self.draft.spec_path.clone();
+        self.draft.spec_status = Some(match self.draft.to_spec().save(&path) {
+            Ok(()) => format!("Saved {path}"),
+            Err(err) => err,
+        });
     }
 
     pub fn add_heuristic(&mut self, title: String) {
         self.draft.heuristics.push(HeuristicDraft {
-            title,
-            images: vec!["/data/new_image.png".to_string()],
+            title: TextBuffer::new(title),
+            images: Vec::new(),
         });
         self.draft.selected_heuristic = self.draft.heuristics.len().saturating_sub(1);
         self.draft.selected_image = 0;
-        self.cursor_pos = self
-            .draft
+    }
+
+    pub fn add_hypothesis(&mut self) {
+        let index = if self.draft.hypotheses.is_empty() {
+            0
+        } else {
+            self.draft.selected_hypothesis + 1
+        };
+        self.draft.hypotheses.insert(
+            index,
+            HypothesisDraft {
+                title: TextBuffer::default(),
+            },
+        );
+        self.draft.selected_hypothesis = index;
+    }
+
+    /// An empty folder is valid: the task then runs on the mock dataset.
+    pub fn dataset_folder_valid(&self, folder: &str) -> bool {
+        folder.trim().is_empty() || Path::new(folder).is_dir()
+    }
+
+    pub fn image_path_valid(path: &str) -> bool {
+        Path::new(path).is_file()
+    }
+
+    /// The dataset folder as currently shown, including uncommitted edits.
+    pub fn draft_dataset_folder(&self) -> &str {
+        if self.draft.field == DraftField::DatasetFolder {
+            self.input.as_str()
+        } else {
+            &self.draft.dataset_folder
+        }
+    }
+
+    /// Validates the draft as it would be submitted, including uncommitted edits.
+    pub fn validate_draft(&self) -> Vec<SpecIssue> {
+        let mut spec = self.draft.to_spec();
+        match self.draft.field {
+            DraftField::Name => spec.name = self.input.as_str().trim().to_string(),
+            DraftField::DatasetFolder => spec.dataset_folder = self.input.as_str().to_string(),
+            DraftField::Heuristics | DraftField::SpecPath => {}
+        }
+        spec.validate()
+    }
+
+    /// Paths in the draft that do not exist, dataset folder first.
+    pub fn missing_paths(&self) -> Vec<String> {
+        let dataset = self.draft_dataset_folder();
+        let mut missing = Vec::new();
+        if !self.dataset_folder_valid(dataset) {
+            missing.push(dataset.to_string());
+        }
+        missing.extend(
+            self.draft
+                .heuristics
+                .iter()
+                .flat_map(|h| &h.images)
+                .map(TextBuffer::as_str)
+                .filter(|image| !Self::image_path_valid(image))
+                .map(str::to_string),
+        );
+        missing
+    }
+
+    pub fn selected_draft_image(&self) -> Option<&str> {
+        self.draft
             .heuristics
             .get(self.draft.selected_heuristic)
-            .map(|h| h.title.len())
-            .unwrap_or(0);
+            .and_then(|h| h.images.get(self.draft.selected_image))
+            .map(TextBuffer::as_str)
+    }
+
+    pub fn open_path_picker(&mut self) {
+        let (target, current) = match self.focused_list() {
+            Some(DraftList::Images) => (
+                PathTarget::Image,
+                self.selected_draft_image().unwrap_or_default().to_string(),
+            ),
+            _ if self.fragment == FragmentId::TaskDescription
+                && self.draft.field == DraftField::DatasetFolder =>
+            {
+                (PathTarget::DatasetFolder, self.input.as_str().to_string())
+            }
+            _ => return,
+        };
+        self.path_picker = Some(PathPicker::open(target, &current));
+    }
+
+    fn apply_path_picker_event(&mut self, event: PathPickerEvent) {
+        let Some(picker) = self.path_picker.as_mut() else {
+            return;
+        };
+        let chosen = match event {
+            PathPickerEvent::SelectNext => {
+                picker.select_next();
+                None
+            }
+            PathPickerEvent::SelectPrev => {
+                picker.select_prev();
+                None
+            }
+            PathPickerEvent::Filter(TextEditEvent::InsertChar(ch)) => {
+                picker.push_filter(ch);
+                None
+            }
+            PathPickerEvent::Filter(TextEditEvent::Backspace) => {
+                picker.pop_filter();
+                None
+            }
+            PathPickerEvent::Filter(edit) => {
+                edit_prompt(&mut picker.filter, &edit);
+                picker.selected = 0;
+                None
+            }
+            PathPickerEvent::Complete => {
+                picker.complete();
+                None
+            }
+            PathPickerEvent::Confirm => picker.confirm(),
+            PathPickerEvent::Close => {
+                self.path_picker = None;
+                None
+            }
+        };
+        let Some(path) = chosen else {
+            return;
+        };
+        let target = self.path_picker.take().map(|picker| picker.target);
+        match target {
+            Some(PathTarget::DatasetFolder) => {
+                self.input = TextBuffer::new(path);
+                self.commit_draft_field();
+            }
+            Some(PathTarget::Image) => {
+                if let Some(heuristic) = self.draft.heuristics.get_mut(self.draft.selected_heuristic) {
+                    if heuristic.images.is_empty() {
+                        heuristic.images.push(TextBuffer::default());
+                        self.draft.selected_image = 0;
+                    }
+                    if let Some(image) = heuristic.images.get_mut(self.draft.selected_image) {
+                        *image = TextBuffer::new(path);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn focused_list(&self) -> Option<DraftList> {
+        match (self.fragment, self.draft.field, self.draft.heuristics_focus) {
+            (FragmentId::TaskHypotheses, _, _) => Some(DraftList::Hypotheses),
+            (FragmentId::TaskDescription, DraftField::Heuristics, HeuristicsFocus::Titles) => {
+                Some(DraftList::Heuristics)
+            }
+            (FragmentId::TaskDescription, DraftField::Heuristics, HeuristicsFocus::Images) => {
+                Some(DraftList::Images)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn delete_item(&mut self) {
+        let list = self.focused_list();
+        let draft = &mut self.draft;
+        match list {
+            Some(DraftList::Hypotheses) => {
+                remove_selected(&mut draft.hypotheses, &mut draft.selected_hypothesis)
+            }
+            Some(DraftList::Heuristics) => {
+                remove_selected(&mut draft.heuristics, &mut draft.selected_heuristic);
+                draft.selected_image = 0;
+            }
+            Some(DraftList::Images) => {
+                if let Some(heuristic) = draft.heuristics.get_mut(draft.selected_heuristic) {
+                    remove_selected(&mut heuristic.images, &mut draft.selected_image);
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn duplicate_item(&mut self) {
+        let list = self.focused_list();
+        let draft = &mut self.draft;
+        match list {
+            Some(DraftList::Hypotheses) => {
+                duplicate_selected(&mut draft.hypotheses, &mut draft.selected_hypothesis)
+            }
+            Some(DraftList::Heuristics) => {
+                duplicate_selected(&mut draft.heuristics, &mut draft.selected_heuristic);
+                draft.selected_image = 0;
+            }
+            Some(DraftList::Images) => {
+                if let Some(heuristic) = draft.heuristics.get_mut(draft.selected_heuristic) {
+                    duplicate_selected(&mut heuristic.images, &mut draft.selected_image);
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn move_item(&mut self, delta: isize) {
+        let list = self.focused_list();
+        let draft = &mut self.draft;
+        match list {
+            Some(DraftList::Hypotheses) => {
+                move_selected(&mut draft.hypotheses, &mut draft.selected_hypothesis, delta)
+            }
+            Some(DraftList::Heuristics) => {
+                move_selected(&mut draft.heuristics, &mut draft.selected_heuristic, delta)
+            }
+            Some(DraftList::Images) => {
+                if let Some(heuristic) = draft.heuristics.get_mut(draft.selected_heuristic) {
+                    move_selected(&mut heuristic.images, &mut draft.selected_image, delta);
+                }
+            }
+            None => {}
+        }
     }
 
     pub fn toggle_cursor(&mut self) {
@@ -296,35 +1270,312 @@ impl AppState {
             AppEvent::ToggleCursor => self.toggle_cursor(),
             AppEvent::Main(main) => self.apply_main_event(main, &mut result),
             AppEvent::TaskInput(task_input) => self.apply_task_input_event(task_input, &mut result),
+            AppEvent::Hypotheses(event) => self.apply_hypotheses_event(event, &mut result),
+            AppEvent::Command(command) => self.apply_command(command, &mut result),
+            AppEvent::Dialog(event) => self.apply_dialog_event(event),
         }
         result
     }
 
+    fn apply_command(&mut self, command: Command, result: &mut EventResult) {
+        let outcome = match command {
+            Command::Cancel(id) => self.command_task(id).map(|_| {
+                result.cmd = Some(UiToEngine::CancelTask { id });
+                format!("Cancelling task {id}.")
+            }),
+            Command::Retry(id) => self.command_task(id).and_then(|task| match task.status {
+                TaskStatus::Pending | TaskStatus::Running => {
+                    Err(format!("Task {id} is still running."))
+                }
+                _ => {
+                    result.cmd = Some(UiToEngine::RetryTask { id });
+                    Ok(format!("Retrying task {id} as a new task."))
+                }
+            }),
+            Command::Export { id, format } => {
+                self.command_task(id).and_then(|task| match task.status {
+                    TaskStatus::Done | TaskStatus::Failed(_) => {
+                        result.cmd = Some(UiToEngine::ExportReport { id, format });
+                        Ok(format!("Exporting task {id} as {}.", format.name()))
+                    }
+                    _ => Err(format!("Task {id} has no results to export.")),
+                })
+            }
+            Command::Filter(filter) => {
+                self.change_task_list(|app| app.task_filter = filter);
+                Ok(format!("Showing {} tasks.", filter.label()))
+            }
+            Command::Sort(sort) => {
+                self.change_task_list(|app| app.task_sort = sort);
+                Ok(format!("Sorting tasks by {}.", sort.label()))
+            }
+            Command::Group(grouping) => {
+                self.change_task_list(|app| app.task_grouping = grouping);
+                Ok(match grouping {
+                    TaskGrouping::None => "Tasks are not grouped.".to_string(),
+                    TaskGrouping::Status => "Grouping tasks by status.".to_string(),
+                })
+            }
+        };
+        self.command_line.message = Some(match outcome {
+            Ok(message) => (LogLevel::Info, message),
+            Err(message) => (LogLevel::Error, message),
+        });
+    }
+
+    fn command_task(&self, id: usize) -> Result<&TaskSnapshot, String> {
+        self.task(id).ok_or_else(|| format!("No task {id}."))
+    }
+
+    fn apply_command_line_event(&mut self, event: CommandLineEvent) {
+        let line = &mut self.command_line;
+        match event {
+            CommandLineEvent::Edit(edit) => {
+                match edit {
+                    // The prompt already shows the leading ':'.
+                    TextEditEvent::InsertChar(':') if line.text.is_empty() => {}
+                    edit => line.text.apply(&edit),
+                }
+                line.history_pos = None;
+            }
+            CommandLineEvent::Complete => {
+                let completion = complete(line.text.as_str(), &self.order);
+                line.text = TextBuffer::new(completion.line);
+                line.message = (!completion.candidates.is_empty())
+                    .then(|| (LogLevel::Info, completion.candidates.join("  ")));
+            }
+            CommandLineEvent::HistoryPrev => {
+                if !line.history.is_empty() {
+                    let pos = line
+                        .history_pos
+                        .map_or(line.history.len() - 1, |pos| pos.saturating_sub(1));
+                    line.history_pos = Some(pos);
+                    line.text = TextBuffer::new(line.history[pos].as_str());
+                }
+            }
+            CommandLineEvent::HistoryNext => {
+                if let Some(pos) = line.history_pos {
+                    if pos + 1 < line.history.len() {
+                        line.history_pos = Some(pos + 1);
+                        line.text = TextBuffer::new(line.history[pos + 1].as_str());
+                    } else {
+                        line.history_pos = None;
+                        line.text.clear();
+                    }
+                }
+            }
+            CommandLineEvent::Submit => {
+                let text = String::from(std::mem::take(&mut line.text)).trim().to_string();
+                line.history_pos = None;
+                if text.is_empty() {
+                    return;
+                }
+                if line.history.last() != Some(&text) {
+                    line.history.push(text.clone());
+                    if line.history.len() > COMMAND_HISTORY_LIMIT {
+                        line.history.remove(0);
+                    }
+                }
+                match Command::parse(&text) {
+                    Ok(command) => self.enqueue_event(AppEvent::Command(command)),
+                    Err(err) => line.message = Some((LogLevel::Error, err)),
+                }
+            }
+            CommandLineEvent::Leave => {
+                line.text.clear();
+                line.history_pos = None;
+                self.set_fragment(FragmentId::MainTasks);
+            }
+        }
+    }
+
+    fn apply_hypotheses_event(&mut self, event: HypothesesEvent, result: &mut EventResult) {
+        match event {
+            HypothesesEvent::Close => self.close_hypotheses(),
+            HypothesesEvent::SelectNext => {
+                let max = self.explorer_rows().len().saturating_sub(1);
+                self.explorer.selected = (self.explorer.selected + 1).min(max);
+                self.explorer.image = 0;
+            }
+            HypothesesEvent::SelectPrev => {
+                self.explorer.selected = self.explorer.selected.saturating_sub(1);
+                self.explorer.image = 0;
+            }
+            HypothesesEvent::Select(index) => {
+                if index < self.explorer_rows().len() {
+                    self.explorer.selected = index;
+                    self.explorer.image = 0;
+                }
+            }
+            HypothesesEvent::NextImage => {
+                let max = self
+                    .selected_explorer_row()
+                    .map(|row| row.hypothesis.results.len().saturating_sub(1))
+                    .unwrap_or(0);
+                self.explorer.image = (self.explorer.image + 1).min(max);
+            }
+            HypothesesEvent::PrevImage => {
+                self.explorer.image = self.explorer.image.saturating_sub(1);
+            }
+            HypothesesEvent::CycleSort => {
+                self.explorer.sort = self.explorer.sort.next();
+                self.explorer.selected = 0;
+                self.explorer.image = 0;
+            }
+            HypothesesEvent::CycleFilter => {
+                self.explorer.filter = self.explorer.filter.next();
+                self.explorer.selected = 0;
+                self.explorer.image = 0;
+            }
+            HypothesesEvent::TogglePin => result.cmd = self.toggle_mark(HypothesisMark::Pinned),
+            HypothesesEvent::ToggleReject => {
+                result.cmd = self.toggle_mark(HypothesisMark::Rejected)
+            }
+            HypothesesEvent::StartInject => {
+                if self.explorer.task_id.is_some() {
+                    self.explorer.inject = Some(TextBuffer::default());
+                }
+            }
+            HypothesesEvent::InjectEdit(edit) => {
+                if let Some(text) = self.explorer.inject.as_mut() {
+                    text.apply(&edit);
+                }
+            }
+            HypothesesEvent::SubmitInject => {
+                let text = String::from(self.explorer.inject.take().unwrap_or_default());
+                if let Some(task_id) = self.explorer.task_id
+                    && !text.trim().is_empty()
+                {
+                    result.cmd = Some(UiToEngine::InjectHypothesis {
+                        task_id,
+                        text: text.trim().to_string(),
+                    });
+                }
+            }
+            HypothesesEvent::CancelInject => self.explorer.inject = None,
+        }
+    }
+
     fn apply_main_event(&mut self, event: MainScreenEvent, result: &mut EventResult) {
         match event {
             MainScreenEvent::OpenTaskInput => self.open_task_input(),
             MainScreenEvent::FocusTasks => self.set_fragment(FragmentId::MainTasks),
             MainScreenEvent::FocusDetail => self.set_fragment(FragmentId::MainDetail),
             MainScreenEvent::FocusInput => self.set_fragment(FragmentId::MainInput),
-            MainScreenEvent::SelectTaskNext => {
-                if self.fragment == FragmentId::MainTasks {
-                    self.select_next();
+            MainScreenEvent::SelectTaskNext => match self.fragment {
+                FragmentId::MainTasks => self.select_next(),
+                FragmentId::MainLogs => self.scroll_logs(-1),
+                _ => {}
+            },
+            MainScreenEvent::SelectTaskPrev => match self.fragment {
+                FragmentId::MainTasks => self.select_prev(),
+                FragmentId::MainLogs => self.scroll_logs(1),
+                _ => {}
+            },
+            MainScreenEvent::SelectTask(index) => {
+                self.set_fragment(FragmentId::MainTasks);
+                if index < self.visible_task_ids().len() && index != self.selected {
+                    self.selected = index;
+                    self.log_view.scroll = 0;
                 }
             }
-            MainScreenEvent::SelectTaskPrev => {
-                if self.fragment == FragmentId::MainTasks {
-                    self.select_prev();
+            MainScreenEvent::CancelSelectedTask => {
+                if let Some(task) = self.selected_task() {
+                    let (id, title) = (task.id, "Cancel task");
+                    self.open_dialog(match task.status {
+                        TaskStatus::Pending | TaskStatus::Running => Dialog::confirm(
+                            title,
+                            format!("Cancel task {id} \"{}\"?", task.name),
+                            AppEvent::Command(Command::Cancel(id)),
+                        ),
+                        _ => Dialog::message(title, format!("Task {id} is not running.")),
+                    });
                 }
             }
-            MainScreenEvent::CancelSelectedTask => {
+            MainScreenEvent::ExportSelectedTask => {
                 if let Some(task) = self.selected_task() {
-                    result.cmd = Some(UiToEngine::CancelTask { id: task.id });
+                    let (id, title) = (task.id, "Export report");
+                    self.open_dialog(match task.status {
+                        TaskStatus::Done | TaskStatus::Failed(_) => Dialog::prompt(
+                            title,
+                            format!(
+                                "Export task {id} \"{}\" as ({}):",
+                                task.name,
+                                ReportFormat::ALL.map(ReportFormat::name).join(", ")
+                            ),
+                            format!("export {id}"),
+                            self.config.reports.formats.first().map_or("markdown", |f| f.name()),
+                        ),
+                        _ => Dialog::message(title, format!("Task {id} has no results to export.")),
+                    });
+                }
+            }
+            MainScreenEvent::Quit => {
+                let running = self
+                    .tasks_by_id
+                    .values()
+                    .filter(|task| matches!(task.status, TaskStatus::Pending | TaskStatus::Running))
+                    .count();
+                if running == 0 {
+                    result.quit = true;
+                } else {
+                    let tasks = if running == 1 { "task is" } else { "tasks are" };
+                    self.open_dialog(Dialog::confirm(
+                        "Quit",
+                        format!("{running} {tasks} still running. Quit and stop them?"),
+                        AppEvent::Quit,
+                    ));
                 }
             }
+            MainScreenEvent::FocusLogs => self.set_fragment(FragmentId::MainLogs),
+            MainScreenEvent::ScrollLogs(delta) => self.scroll_logs(delta),
+            MainScreenEvent::ScrollLogsTop => self.scroll_logs(isize::MAX),
+            MainScreenEvent::ScrollLogsBottom => self.log_view.scroll = 0,
+            MainScreenEvent::StartLogSearch => {
+                self.set_fragment(FragmentId::MainLogs);
+                self.log_view.searching = true;
+            }
+            MainScreenEvent::LogSearch(edit) => {
+                edit_prompt(&mut self.log_view.query, &edit);
+                self.log_view.scroll = 0;
+            }
+            MainScreenEvent::EndLogSearch => self.log_view.searching = false,
+            MainScreenEvent::ClearLogSearch => {
+                self.log_view = LogView::default();
+            }
+            MainScreenEvent::OpenHypotheses => self.open_hypotheses(),
+            MainScreenEvent::CommandLine(event) => self.apply_command_line_event(event),
+            MainScreenEvent::CycleTaskFilter => {
+                self.change_task_list(|app| app.task_filter = app.task_filter.next())
+            }
+            MainScreenEvent::CycleTaskSort => {
+                self.change_task_list(|app| app.task_sort = app.task_sort.next())
+            }
+            MainScreenEvent::CycleTaskGrouping => {
+                self.change_task_list(|app| app.task_grouping = app.task_grouping.next())
+            }
+            MainScreenEvent::StartTaskSearch => {
+                self.set_fragment(FragmentId::MainTasks);
+                self.task_search.searching = true;
+            }
+            MainScreenEvent::TaskSearch(edit) => {
+                self.change_task_list(|app| edit_prompt(&mut app.task_search.query, &edit))
+            }
+            MainScreenEvent::EndTaskSearch => self.task_search.searching = false,
+            MainScreenEvent::ClearTaskSearch => {
+                self.change_task_list(|app| app.task_search = TaskSearch::default())
+            }
         }
     }
 
     fn apply_task_input_event(&mut self, event: TaskInputEvent, result: &mut EventResult) {
+        self.apply_task_input_change(event, result);
+        if self.screen == ScreenId::TaskInput && !self.draft.issues.is_empty() {
+            self.draft.issues = self.validate_draft();
+        }
+    }
+
+    fn apply_task_input_change(&mut self, event: TaskInputEvent, result: &mut EventResult) {
         match event {
             TaskInputEvent::Close => self.close_task_input(),
             TaskInputEvent::SwitchField => {
@@ -333,15 +1584,71 @@ impl AppState {
                     self.draft.field = match self.draft.field {
                         DraftField::Name => DraftField::DatasetFolder,
                         DraftField::DatasetFolder => DraftField::Heuristics,
-                        DraftField::Heuristics => DraftField::Name,
+                        DraftField::Heuristics => DraftField::SpecPath,
+                        DraftField::SpecPath => DraftField::Name,
                     };
                     self.load_draft_field();
                 }
             }
-            TaskInputEvent::FocusDescription => self.set_fragment(FragmentId::TaskDescription),
-            TaskInputEvent::FocusHypotheses => self.set_fragment(FragmentId::TaskHypotheses),
+            TaskInputEvent::FocusDescription => {
+                if self.fragment == FragmentId::TaskHypotheses {
+                    self.set_fragment(FragmentId::TaskDescription);
+                    self.load_draft_field();
+                }
+            }
+            TaskInputEvent::FocusHypotheses => {
+                if self.fragment == FragmentId::TaskDescription {
+                    self.commit_draft_field();
+                    self.set_fragment(FragmentId::TaskHypotheses);
+                }
+            }
+            TaskInputEvent::FocusField(field) => {
+                if self.fragment == FragmentId::TaskDescription {
+                    self.commit_draft_field();
+                }
+                self.set_fragment(FragmentId::TaskDescription);
+                self.draft.field = field;
+                self.load_draft_field();
+            }
+            TaskInputEvent::FocusHeuristicList(focus) => {
+                if self.fragment != FragmentId::TaskDescription
+                    || self.draft.field != DraftField::Heuristics
+                {
+                    self.apply_task_input_change(
+                        TaskInputEvent::FocusField(DraftField::Heuristics),
+                        result,
+                    );
+                }
+                self.draft.heuristics_focus = focus;
+            }
+            TaskInputEvent::SelectItem(index) => {
+                let list = self.focused_list();
+                let draft = &mut self.draft;
+                let (selected, len) = match list {
+                    Some(DraftList::Hypotheses) => {
+                        (&mut draft.selected_hypothesis, draft.hypotheses.len())
+                    }
+                    Some(DraftList::Heuristics) => {
+                        draft.selected_image = 0;
+                        (&mut draft.selected_heuristic, draft.heuristics.len())
+                    }
+                    Some(DraftList::Images) => (
+                        &mut draft.selected_image,
+                        draft
+                            .heuristics
+                            .get(draft.selected_heuristic)
+                            .map_or(0, |h| h.images.len()),
+                    ),
+                    None => return,
+                };
+                if index < len {
+                    *selected = index;
+                }
+            }
             TaskInputEvent::AddHeuristic => {
-                if self.fragment == FragmentId::TaskDescription
+                if self.fragment == FragmentId::TaskHypotheses {
+                    self.add_hypothesis();
+                } else if self.fragment == FragmentId::TaskDescription
                     && self.draft.field == DraftField::Heuristics
                 {
                     if self.draft.heuristics_focus == HeuristicsFocus::Images {
@@ -353,6 +1660,14 @@ impl AppState {
                 }
             }
             TaskInputEvent::AddImage => self.add_image(),
+            TaskInputEvent::DeleteItem => self.delete_item(),
+            TaskInputEvent::DuplicateItem => self.duplicate_item(),
+            TaskInputEvent::MoveItemUp => self.move_item(-1),
+            TaskInputEvent::MoveItemDown => self.move_item(1),
+            TaskInputEvent::OpenSpec => self.open_spec(),
+            TaskInputEvent::SaveSpec => self.save_spec(),
+            TaskInputEvent::OpenPathPicker => self.open_path_picker(),
+            TaskInputEvent::PathPicker(event) => self.apply_path_picker_event(event),
             TaskInputEvent::Edit(edit) => self.apply_text_edit_event(edit),
             TaskInputEvent::Submit => {
                 if self.fragment == FragmentId::TaskDescription
@@ -363,10 +1678,16 @@ impl AppState {
                     return;
                 }
                 self.commit_draft_field();
-                let name = self.draft.name.trim().to_string();
-                if !name.is_empty() {
-                    result.cmd = Some(UiToEngine::AddTask { name });
+                let issues = self.validate_draft();
+                // Warnings only hold back the Submit that first shows them.
+                let unseen = issues != self.draft.issues;
+                self.draft.issues = issues;
+                if self.draft.blocks_submit() || unseen {
+                    return;
                 }
+                result.cmd = Some(UiToEngine::AddTaskFromSpec {
+                    spec: self.draft.to_spec(),
+                });
                 self.reset_draft();
                 self.close_task_input();
             }
@@ -374,87 +1695,94 @@ impl AppState {
     }
 
     fn apply_text_edit_event(&mut self, event: TextEditEvent) {
+        let heuristics = self.fragment == FragmentId::TaskDescription
+            && self.draft.field == DraftField::Heuristics;
         match event {
-            TextEditEvent::CursorLeft => self.move_cursor_left(),
-            TextEditEvent::CursorRight => self.move_cursor_right(),
             TextEditEvent::MoveUp => self.move_selection_up(),
             TextEditEvent::MoveDown => self.move_selection_down(),
-            TextEditEvent::Backspace => self.handle_backspace(),
-            TextEditEvent::InsertChar(ch) => self.handle_insert_char(ch),
-        }
-    }
-
-    fn move_cursor_left(&mut self) {
-        if self.fragment == FragmentId::TaskDescription && self.draft.field == DraftField::Heuristics {
-            if self.draft.heuristics_focus == HeuristicsFocus::Images {
-                if self.cursor_pos == 0 {
-                    self.draft.heuristics_focus = HeuristicsFocus::Titles;
-                    self.cursor_pos = self
-                        .draft
-                        .heuristics
-                        .get(self.draft.selected_heuristic)
-                        .map(|h| h.title.len())
-                        .unwrap_or(0);
-                } else if let Some(image) = self
-                    .draft
-                    .heuristics
-                    .get(self.draft.selected_heuristic)
-                    .and_then(|h| h.images.get(self.draft.selected_image))
-                {
-                    self.cursor_pos = self.cursor_pos.saturating_sub(1).min(image.len());
-                }
-            } else if let Some(title) = self
-                .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .map(|h| h.title.as_str())
+            // Left from the start of an image path goes back to the titles.
+            TextEditEvent::CursorLeft
+                if heuristics
+                    && self.draft.heuristics_focus == HeuristicsFocus::Images
+                    && self.focused_text().is_none_or(|text| text.cursor() == 0) =>
             {
-                self.cursor_pos = self.cursor_pos.saturating_sub(1).min(title.len());
+                self.draft.heuristics_focus = HeuristicsFocus::Titles;
+            }
+            // Right from the end of a title goes on to its images.
+            TextEditEvent::CursorRight
+                if heuristics
+                    && self.draft.heuristics_focus == HeuristicsFocus::Titles
+                    && self
+                        .focused_text()
+                        .is_some_and(|text| text.cursor() >= text.as_str().len()) =>
+            {
+                self.draft.heuristics_focus = HeuristicsFocus::Images;
+            }
+            edit => {
+                let create = matches!(edit, TextEditEvent::InsertChar(_) | TextEditEvent::Paste(_));
+                if let Some(text) = self.focused_text_mut(create) {
+                    text.apply(&edit);
+                }
             }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field != DraftField::Heuristics
-        {
-            self.cursor_pos = self.cursor_pos.saturating_sub(1).min(self.input.len());
         }
     }
 
-    fn move_cursor_right(&mut self) {
-        if self.fragment == FragmentId::TaskDescription && self.draft.field == DraftField::Heuristics {
-            if self.draft.heuristics_focus == HeuristicsFocus::Titles {
-                if let Some(title) = self
-                    .draft
-                    .heuristics
-                    .get(self.draft.selected_heuristic)
-                    .map(|h| h.title.as_str())
-                {
-                    if self.cursor_pos >= title.len() {
-                        self.draft.heuristics_focus = HeuristicsFocus::Images;
-                        self.cursor_pos = self
-                            .draft
-                            .heuristics
-                            .get(self.draft.selected_heuristic)
-                            .and_then(|h| h.images.get(self.draft.selected_image))
-                            .map(|s| s.len())
-                            .unwrap_or(0);
-                    } else {
-                        self.cursor_pos = (self.cursor_pos + 1).min(title.len());
-                    }
-                }
-            } else if let Some(image) = self
+    /// The focused task input field. `create` adds an item first when the
+    /// focused images or seed hypotheses list is empty.
+    fn focused_text_mut(&mut self, create: bool) -> Option<&mut TextBuffer> {
+        if self.fragment == FragmentId::TaskHypotheses {
+            if create && self.draft.hypotheses.is_empty() {
+                self.add_hypothesis();
+            }
+            return self
                 .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .and_then(|h| h.images.get(self.draft.selected_image))
-            {
-                self.cursor_pos = (self.cursor_pos + 1).min(image.len());
+                .hypotheses
+                .get_mut(self.draft.selected_hypothesis)
+                .map(|hypothesis| &mut hypothesis.title);
+        }
+        if self.fragment != FragmentId::TaskDescription {
+            return None;
+        }
+        if self.draft.field != DraftField::Heuristics {
+            return Some(&mut self.input);
+        }
+        let heuristic = self.draft.heuristics.get_mut(self.draft.selected_heuristic)?;
+        if self.draft.heuristics_focus == HeuristicsFocus::Titles {
+            return Some(&mut heuristic.title);
+        }
+        if create && heuristic.images.is_empty() {
+            heuristic.images.push(TextBuffer::default());
+            self.draft.selected_image = 0;
+        }
+        heuristic.images.get_mut(self.draft.selected_image)
+    }
+
+    fn focused_text(&self) -> Option<&TextBuffer> {
+        let draft = &self.draft;
+        match (self.fragment, self.focused_list()) {
+            (FragmentId::TaskDescription, None) => Some(&self.input),
+            (_, Some(DraftList::Hypotheses)) => {
+                draft.hypotheses.get(draft.selected_hypothesis).map(|h| &h.title)
             }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field != DraftField::Heuristics
-        {
-            self.cursor_pos = (self.cursor_pos + 1).min(self.input.len());
+            (_, Some(DraftList::Heuristics)) => {
+                draft.heuristics.get(draft.selected_heuristic).map(|h| &h.title)
+            }
+            (_, Some(DraftList::Images)) => draft
+                .heuristics
+                .get(draft.selected_heuristic)
+                .and_then(|h| h.images.get(draft.selected_image)),
+            _ => None,
         }
     }
 
+    /// Terminal column of the cursor within the focused task input field,
+    /// counting wide characters as two and combining marks as none.
+    pub fn cursor_column(&self) -> usize {
+        self.focused_text()
+            .map(TextBuffer::cursor_column)
+            .unwrap_or(0)
+    }
+
     fn move_selection_up(&mut self) {
         if self.fragment == FragmentId::TaskDescription
             && self.draft.field == DraftField::Heuristics
@@ -463,25 +1791,12 @@ impl AppState {
         {
             self.draft.selected_heuristic -= 1;
             self.draft.selected_image = 0;
-            self.cursor_pos = self
-                .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .map(|h| h.title.len())
-                .unwrap_or(0);
         } else if self.fragment == FragmentId::TaskDescription
             && self.draft.field == DraftField::Heuristics
             && self.draft.heuristics_focus == HeuristicsFocus::Images
             && self.draft.selected_image > 0
         {
             self.draft.selected_image -= 1;
-            self.cursor_pos = self
-                .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .and_then(|h| h.images.get(self.draft.selected_image))
-                .map(|s| s.len())
-                .unwrap_or(0);
         } else if self.fragment == FragmentId::TaskHypotheses
             && self.draft.selected_hypothesis > 0
         {
@@ -497,12 +1812,6 @@ impl AppState {
             let max = self.draft.heuristics.len().saturating_sub(1);
             self.draft.selected_heuristic = (self.draft.selected_heuristic + 1).min(max);
             self.draft.selected_image = 0;
-            self.cursor_pos = self
-                .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .map(|h| h.title.len())
-                .unwrap_or(0);
         } else if self.fragment == FragmentId::TaskDescription
             && self.draft.field == DraftField::Heuristics
             && self.draft.heuristics_focus == HeuristicsFocus::Images
@@ -515,13 +1824,6 @@ impl AppState {
                 .unwrap_or(0);
             let max = count.saturating_sub(1);
             self.draft.selected_image = (self.draft.selected_image + 1).min(max);
-            self.cursor_pos = self
-                .draft
-                .heuristics
-                .get(self.draft.selected_heuristic)
-                .and_then(|h| h.images.get(self.draft.selected_image))
-                .map(|s| s.len())
-                .unwrap_or(0);
         } else if self.fragment == FragmentId::TaskHypotheses {
             let max = self.draft.hypotheses.len().saturating_sub(1);
             self.draft.selected_hypothesis =
@@ -535,76 +1837,39 @@ impl AppState {
             .heuristics
             .get_mut(self.draft.selected_heuristic)
         {
-            heuristic.images.push(String::new());
+            heuristic.images.push(TextBuffer::default());
             self.draft.selected_image = heuristic.images.len().saturating_sub(1);
-            self.cursor_pos = 0;
         }
     }
+}
 
-    fn handle_backspace(&mut self) {
-        if self.fragment == FragmentId::TaskDescription && self.draft.field != DraftField::Heuristics {
-            if self.cursor_pos > 0 {
-                self.cursor_pos -= 1;
-                self.input.remove(self.cursor_pos);
-            }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field == DraftField::Heuristics
-            && self.draft.heuristics_focus == HeuristicsFocus::Titles
-        {
-            if let Some(heuristic) = self.draft.heuristics.get_mut(self.draft.selected_heuristic) {
-                if self.cursor_pos > 0 && self.cursor_pos <= heuristic.title.len() {
-                    self.cursor_pos -= 1;
-                    heuristic.title.remove(self.cursor_pos);
-                }
-            }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field == DraftField::Heuristics
-            && self.draft.heuristics_focus == HeuristicsFocus::Images
-        {
-            if let Some(heuristic) = self.draft.heuristics.get_mut(self.draft.selected_heuristic) {
-                if let Some(image) = heuristic.images.get_mut(self.draft.selected_image) {
-                    if self.cursor_pos > 0 && self.cursor_pos <= image.len() {
-                        self.cursor_pos -= 1;
-                        image.remove(self.cursor_pos);
-                    }
-                }
-            }
-        }
+/// Edits a single-line prompt whose cursor always sits at the end.
+fn edit_prompt(text: &mut String, event: &TextEditEvent) {
+    let mut buffer = TextBuffer::new(std::mem::take(text));
+    buffer.apply(event);
+    *text = buffer.into();
+}
+
+fn remove_selected<T>(items: &mut Vec<T>, selected: &mut usize) {
+    if *selected < items.len() {
+        items.remove(*selected);
+        *selected = (*selected).min(items.len().saturating_sub(1));
     }
+}
 
-    fn handle_insert_char(&mut self, ch: char) {
-        if self.fragment == FragmentId::TaskDescription && self.draft.field != DraftField::Heuristics {
-            if self.cursor_pos <= self.input.len() {
-                self.input.insert(self.cursor_pos, ch);
-                self.cursor_pos += 1;
-            }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field == DraftField::Heuristics
-            && self.draft.heuristics_focus == HeuristicsFocus::Titles
-        {
-            if let Some(heuristic) = self.draft.heuristics.get_mut(self.draft.selected_heuristic) {
-                if self.cursor_pos <= heuristic.title.len() {
-                    heuristic.title.insert(self.cursor_pos, ch);
-                    self.cursor_pos += 1;
-                }
-            }
-        } else if self.fragment == FragmentId::TaskDescription
-            && self.draft.field == DraftField::Heuristics
-            && self.draft.heuristics_focus == HeuristicsFocus::Images
-        {
-            if let Some(heuristic) = self.draft.heuristics.get_mut(self.draft.selected_heuristic) {
-                if heuristic.images.is_empty() {
-                    heuristic.images.push(String::new());
-                    self.draft.selected_image = 0;
-                    self.cursor_pos = 0;
-                }
-                if let Some(image) = heuristic.images.get_mut(self.draft.selected_image) {
-                    if self.cursor_pos <= image.len() {
-                        image.insert(self.cursor_pos, ch);
-                        self.cursor_pos += 1;
-                    }
-                }
-            }
-        }
+fn duplicate_selected<T: Clone>(items: &mut Vec<T>, selected: &mut usize) {
+    if let Some(item) = items.get(*selected).cloned() {
+        items.insert(*selected + 1, item);
+        *selected += 1;
+    }
+}
+
+fn move_selected<T>(items: &mut [T], selected: &mut usize, delta: isize) {
+    if let Some(to) = selected.checked_add_signed(delta)
+        && to < items.len()
+        && *selected < items.len()
+    {
+        items.swap(*selected, to);
+        *selected = to;
     }
 }
@@ -0,0 +1,281 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use tokio::sync::mpsc;
+
+use crate::config::AppConfig;
+use crate::engine::scheduler::{run_scheduler_with_config, LogLevel, TaskUpdate};
+use crate::gateway::Gateway;
+use crate::protocol::{EngineToUi, UiToEngine};
+use crate::report::{list_reports, report_dirs, ReportEntry};
+use crate::spec::TaskSpec;
+use crate::task::{TaskSnapshot, TaskStatus};
+
+pub const USAGE: &str = "\
+Usage:
+  revolver                          start the TUI
+  revolver run [--spec <file>] [--name <name>] [--json]
+                                    run one task headless and stream its logs
+  revolver list                     list generated reports
+  revolver report <id> [<run>]      print a task id's report, by default of its latest run";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Tui,
+    Run {
+        spec: Option<PathBuf>,
+        name: Option<String>,
+        json: bool,
+    },
+    List,
+    Report { id: usize, run: Option<u64> },
+    Help,
+}
+
+pub fn parse_args<I>(args: I) -> Result<CliCommand, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        return Ok(CliCommand::Tui);
+    };
+    match command.as_str() {
+        "run" => {
+            let mut spec = None;
+            let mut name = None;
+            let mut json = false;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--spec" => {
+                        spec = Some(PathBuf::from(
+                            args.next().ok_or("--spec expects a file")?,
+                        ));
+                    }
+                    "--name" => {
+                        name = Some(args.next().ok_or("--name expects a value")?);
+                    }
+                    "--json" => json = true,
+                    other => return Err(format!("unknown argument for run: {other}")),
+                }
+            }
+            if spec.is_none() && name.is_none() {
+                return Err("run requires --spec <file> or --name <name>".to_string());
+            }
+            Ok(CliCommand::Run { spec, name, json })
+        }
+        "list" => match args.next() {
+            None => Ok(CliCommand::List),
+            Some(other) => Err(format!("unexpected argument for list: {other}")),
+        },
+        "report" => {
+            let id = args.next().ok_or("report requires a task id")?;
+            let id = id
+                .parse()
+                .map_err(|_| format!("invalid task id: {id}"))?;
+            let run = match args.next() {
+                Some(run) => Some(run.parse().map_err(|_| format!("invalid run id: {run}"))?),
+                None => None,
+            };
+            if let Some(other) = args.next() {
+                return Err(format!("unexpected argument for report: {other}"));
+            }
+            Ok(CliCommand::Report { id, run })
+        }
+        "help" | "-h" | "--help" => Ok(CliCommand::Help),
+        other => Err(format!("unknown command: {other}")),
+    }
+}
+
+pub async fn run(command: CliCommand, config: &AppConfig) -> ExitCode {
+    let outcome = match command {
+        CliCommand::Tui => return ExitCode::SUCCESS,
+        CliCommand::Help => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        CliCommand::Run { spec, name, json } => {
+            let spec = match load_run_spec(spec, name, config) {
+                Ok(spec) => spec,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    return ExitCode::from(64);
+                }
+            };
+            let mut stdout = io::stdout();
+            run_headless(spec, json, config, &mut stdout).await.map(|snapshot| {
+                exit_code(&snapshot.status)
+            })
+        }
+        CliCommand::List => print_reports(&config.reports.dir).map(|_| ExitCode::SUCCESS),
+        CliCommand::Report { id, run } => print_report(&config.reports.dir, id, run),
+    };
+    outcome.unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        ExitCode::FAILURE
+    })
+}
+
+fn load_run_spec(
+    path: Option<PathBuf>,
+    name: Option<String>,
+    config: &AppConfig,
+) -> Result<TaskSpec, String> {
+    let mut spec = match path {
+        Some(path) => TaskSpec::load_over(path, &config.default_spec())?,
+        None => config.default_spec(),
+    };
+    if let Some(name) = name {
+        spec.name = name;
+    }
+    if spec.name.trim().is_empty() {
+        return Err("task spec has no name; pass --name".to_string());
+    }
+    let issues = spec.validate();
+    if !issues.is_empty() {
+        let messages = issues.into_iter().map(|issue| issue.message).collect::<Vec<_>>();
+        return Err(format!("invalid task spec: {}", messages.join("; ")));
+    }
+    Ok(spec)
+}
+
+/// Runs a single task through the scheduler without a terminal UI, writing one
+/// line per log message to `out`, and returns the final snapshot.
+pub async fn run_headless<W: Write>(
+    spec: TaskSpec,
+    json: bool,
+    config: &AppConfig,
+    out: &mut W,
+) -> io::Result<TaskSnapshot> {
+    let (ui_to_engine_tx, ui_to_engine_rx) = mpsc::channel(config.engine.command_channel);
+    let (engine_to_ui_tx, engine_to_ui_rx) = mpsc::channel(config.engine.update_channel);
+    tokio::spawn(run_scheduler_with_config(
+        ui_to_engine_rx,
+        engine_to_ui_tx,
+        config.clone(),
+    ));
+    let mut gateway = Gateway::new(ui_to_engine_tx, engine_to_ui_rx);
+
+    gateway.send(UiToEngine::AddTaskFromSpec { spec }).await;
+
+    let mut finished = None;
+    while let Some(message) = gateway.recv().await {
+        let EngineToUi::TaskUpdate(update) = &message;
+        match update {
+            TaskUpdate::Log {
+                id,
+                level,
+                message: text,
+            } => {
+                if json {
+                    writeln!(out, "{}", to_json(&message)?)?;
+                } else if *level == LogLevel::Info {
+                    writeln!(out, "[task {id}] {text}")?;
+                } else {
+                    writeln!(out, "[task {id}] {}: {text}", level.as_str())?;
+                }
+            }
+            TaskUpdate::Upsert(snapshot) => {
+                if is_terminal(&snapshot.status) {
+                    if json {
+                        writeln!(out, "{}", to_json(&message)?)?;
+                    } else {
+                        writeln!(out, "{}", summary_line(snapshot))?;
+                    }
+                    finished = Some(snapshot.clone());
+                    break;
+                }
+            }
+            // Headless runs never steer.
+            TaskUpdate::SteeringDropped { .. } => {}
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    finished.ok_or_else(|| io::Error::other("engine stopped before the task finished"))
+}
+
+pub fn exit_code(status: &TaskStatus) -> ExitCode {
+    match status {
+        TaskStatus::Done => ExitCode::SUCCESS,
+        TaskStatus::Failed(_) => ExitCode::from(1),
+        TaskStatus::Cancelled => ExitCode::from(2),
+        TaskStatus::Pending | TaskStatus::Running => ExitCode::from(3),
+    }
+}
+
+fn is_terminal(status: &TaskStatus) -> bool {
+    matches!(
+        status,
+        TaskStatus::Done | TaskStatus::Failed(_) | TaskStatus::Cancelled
+    )
+}
+
+fn summary_line(snapshot: &TaskSnapshot) -> String {
+    let status = match &snapshot.status {
+        TaskStatus::Failed(error) => format!("Failed ({error})"),
+        other => crate::ui::format_status(other),
+    };
+    format!(
+        "[task {}] {} | best {:.3} | iterations {} | report {}",
+        snapshot.id,
+        status,
+        snapshot.best_score,
+        snapshot.iteration,
+        snapshot.report_path.as_deref().unwrap_or("none")
+    )
+}
+
+fn to_json(message: &EngineToUi) -> io::Result<String> {
+    serde_json::to_string(message).map_err(io::Error::other)
+}
+
+/// Reports in the configured directory and in every per-task directory
+/// recorded there, sorted by task id and run.
+fn all_reports(dir: &str) -> io::Result<Vec<ReportEntry>> {
+    let mut reports = Vec::new();
+    for dir in report_dirs(Path::new(dir))? {
+        reports.extend(list_reports(dir)?);
+    }
+    reports.sort_by(|a, b| (a.task_id, a.run, &a.path).cmp(&(b.task_id, b.run, &b.path)));
+    Ok(reports)
+}
+
+fn print_reports(dir: &str) -> io::Result<()> {
+    let reports = all_reports(dir)?;
+    if reports.is_empty() {
+        println!("No reports in {dir}/");
+    }
+    for entry in reports {
+        println!("{:>4}  {:>13}  {}", entry.task_id, entry.run, entry.path.display());
+    }
+    Ok(())
+}
+
+fn print_report(dir: &str, id: usize, run: Option<u64>) -> io::Result<ExitCode> {
+    let runs = all_reports(dir)?
+        .into_iter()
+        .filter(|entry| entry.task_id == id)
+        .collect::<Vec<_>>();
+    let entry = match run {
+        Some(run) => runs.iter().find(|entry| entry.run == run),
+        None => runs.last(),
+    };
+    let Some(entry) = entry else {
+        match run {
+            Some(run) => eprintln!("No report found for run {run} of task {id}"),
+            None => eprintln!("No report found for task {id} in {dir}/"),
+        }
+        return Ok(ExitCode::FAILURE);
+    };
+    let other_runs = runs.iter().filter(|other| other.run != entry.run).count();
+    if run.is_none() && other_runs > 0 {
+        eprintln!(
+            "Task {id} has {other_runs} older run(s); showing run {}. \
+             Pick one with `revolver report {id} <run>` (see `revolver list`).",
+            entry.run
+        );
+    }
+    print!("{}", std::fs::read_to_string(&entry.path)?);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::{TaskFilter, TaskGrouping, TaskSort};
+use crate::report::ReportFormat;
+
+pub const COMMAND_NAMES: &[&str] = &["cancel", "retry", "export", "filter", "sort", "group"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Command {
+    Cancel(usize),
+    /// Runs the task's definition again as a new task.
+    Retry(usize),
+    Export { id: usize, format: ReportFormat },
+    Filter(TaskFilter),
+    Sort(TaskSort),
+    Group(TaskGrouping),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub line: String,
+    /// Every match when the word could not be completed uniquely.
+    pub candidates: Vec<String>,
+}
+
+impl Command {
+    /// Parses `cancel 3`, `retry 2`, `export 4 html`, `filter running`,
+    /// `sort score` or `group status`; a leading `:` is ignored.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim().trim_start_matches(':');
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((name, args)) = words.split_first() else {
+            return Err("Empty command.".to_string());
+        };
+        let command = match *name {
+            "cancel" => Command::Cancel(task_id(args.first())?),
+            "retry" => Command::Retry(task_id(args.first())?),
+            "export" => {
+                let id = task_id(args.first())?;
+                let format = match args.get(1) {
+                    Some(value) => ReportFormat::parse(value)
+                        .ok_or_else(|| format!("Unknown report format '{value}'."))?,
+                    None => return Err("Usage: export <task id> <markdown|html|json>".to_string()),
+                };
+                Command::Export { id, format }
+            }
+            "filter" => {
+                let value = args.first().copied().unwrap_or("all");
+                Command::Filter(
+                    TaskFilter::parse(value).ok_or_else(|| format!("Unknown filter '{value}'."))?,
+                )
+            }
+            "sort" => {
+                let value = args.first().copied().unwrap_or("created");
+                Command::Sort(
+                    TaskSort::parse(value).ok_or_else(|| format!("Unknown sort '{value}'."))?,
+                )
+            }
+            "group" => {
+                let value = args.first().copied().unwrap_or("none");
+                Command::Group(
+                    TaskGrouping::parse(value)
+                        .ok_or_else(|| format!("Unknown grouping '{value}'."))?,
+                )
+            }
+            other => return Err(format!("Unknown command '{other}'.")),
+        };
+        let expected = match command {
+            Command::Export { .. } => 2,
+            _ => 1,
+        };
+        if args.len() > expected {
+            return Err(format!("Too many arguments for '{name}'."));
+        }
+        Ok(command)
+    }
+}
+
+fn task_id(value: Option<&&str>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| "Missing task id.".to_string())?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid task id '{value}'."))
+}
+
+/// Completes the last word of `line`: command names first, then task ids,
+/// report formats, filters, sorts or groupings depending on the command.
+pub fn complete(line: &str, task_ids: &[usize]) -> Completion {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let (done, word) = if line.is_empty() || line.ends_with(char::is_whitespace) {
+        (&words[..], "")
+    } else {
+        (&words[..words.len() - 1], words[words.len() - 1])
+    };
+    let options: Vec<String> = match (done.first().copied(), done.len()) {
+        (None, _) => COMMAND_NAMES.iter().map(|name| name.to_string()).collect(),
+        (Some("cancel" | "retry" | "export"), 1) => {
+            task_ids.iter().map(|id| id.to_string()).collect()
+        }
+        (Some("export"), 2) => ReportFormat::ALL
+            .iter()
+            .map(|format| format.name().to_string())
+            .collect(),
+        (Some("filter"), 1) => TaskFilter::ALL
+            .iter()
+            .map(|filter| filter.label().to_string())
+            .collect(),
+        (Some("sort"), 1) => TaskSort::ALL
+            .iter()
+            .map(|sort| sort.label().to_string())
+            .collect(),
+        (Some("group"), 1) => TaskGrouping::ALL
+            .iter()
+            .map(|grouping| grouping.label().to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let candidates = options
+        .into_iter()
+        .filter(|option| option.starts_with(word))
+        .collect::<Vec<_>>();
+    let prefix = &line[..line.len() - word.len()];
+    match candidates.as_slice() {
+        [] => Completion {
+            line: line.to_string(),
+            candidates,
+        },
+        [only] => Completion {
+            line: format!("{prefix}{only} "),
+            candidates: Vec::new(),
+        },
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, candidate| {
+                let len = common
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a.len_utf8())
+                    .sum();
+                &common[..len]
+            });
+            Completion {
+                line: format!("{prefix}{common}"),
+                candidates,
+            }
+        }
+    }
+}
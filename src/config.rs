@@ -0,0 +1,335 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::preview::PreviewProtocol;
+use crate::report::ReportFormat;
+use crate::screens::keymap::Keymaps;
+use crate::screens::theme::{Theme, ThemeName};
+use crate::spec::{LlmSpec, TaskSpec};
+
+pub const PROJECT_CONFIG_FILE: &str = "revolver.toml";
+pub const ENV_PREFIX: &str = "REVOLVER_";
+
+/// Application settings, layered from defaults, the user config file, the
+/// project config file and `REVOLVER_<SECTION>__<KEY>` environment variables.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub engine: EngineConfig,
+    pub ui: UiConfig,
+    pub reports: ReportsConfig,
+    pub defaults: DefaultsConfig,
+    pub llm: LlmConfig,
+    pub keys: KeysConfig,
+    pub theme: ThemeConfig,
+    /// `REVOLVER_*` variables that name no setting or are not UTF-8, left out.
+    #[serde(skip)]
+    pub ignored_vars: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EngineConfig {
+    pub command_channel: usize,
+    pub update_channel: usize,
+    pub generating_sleep_ms: u64,
+    pub reducing_sleep_ms: u64,
+    pub synthesizing_sleep_ms: u64,
+    pub testing_sleep_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    pub tick_ms: u64,
+    pub splash_ms: u64,
+    /// Log lines kept per task; older lines are dropped.
+    pub log_capacity: usize,
+    pub image_protocol: PreviewProtocol,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportsConfig {
+    pub dir: String,
+    pub template: Option<String>,
+    pub formats: Vec<ReportFormat>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    pub dataset_folder: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmConfig {
+    pub model: String,
+}
+
+/// Key binding overrides per screen: action name to keys such as `["q", "ctrl+c"]`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeysConfig {
+    pub main: BTreeMap<String, Vec<String>>,
+    pub task_input: BTreeMap<String, Vec<String>>,
+    pub hypotheses: BTreeMap<String, Vec<String>>,
+}
+
+/// The color scheme: a built-in palette with per-role color overrides such as
+/// `focus = "magenta"` or `error = "#ff8800"`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: ThemeName,
+    /// Draws without colors, like setting `NO_COLOR`.
+    pub no_color: bool,
+    pub colors: BTreeMap<String, String>,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            command_channel: 32,
+            update_channel: 128,
+            generating_sleep_ms: 250,
+            reducing_sleep_ms: 200,
+            synthesizing_sleep_ms: 200,
+            testing_sleep_ms: 200,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: 200,
+            splash_ms: 3000,
+            log_capacity: 1000,
+            image_protocol: PreviewProtocol::default(),
+        }
+    }
+}
+
+impl Default for ReportsConfig {
+    fn default() -> Self {
+        Self {
+            dir: String::from("reports"),
+            template: None,
+            formats: vec![ReportFormat::Markdown, ReportFormat::Html, ReportFormat::Json],
+        }
+    }
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            dataset_folder: TaskSpec::default().dataset_folder,
+        }
+    }
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            model: TaskSpec::default().llm.model,
+        }
+    }
+}
+
+impl UiConfig {
+    pub fn tick(&self) -> Duration {
+        Duration::from_millis(self.tick_ms)
+    }
+
+    pub fn splash(&self) -> Duration {
+        Duration::from_millis(self.splash_ms)
+    }
+}
+
+impl AppConfig {
+    /// Loads the user file, the project file and the process environment.
+    pub fn load() -> Result<Self, String> {
+        let mut files = Vec::new();
+        if let Some(path) = user_config_path() {
+            files.push(path);
+        }
+        files.push(PathBuf::from(PROJECT_CONFIG_FILE));
+        Self::load_layers(&files, env::vars_os())
+    }
+
+    /// Merges `files` (missing files are skipped) and then `vars` over the
+    /// defaults, later layers winning, and validates the result.
+    pub fn load_layers<I>(files: &[PathBuf], vars: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (OsString, OsString)>,
+    {
+        let mut merged = serde_json::to_value(Self::default()).map_err(|e| e.to_string())?;
+        for path in files {
+            if let Some(layer) = read_layer(path)? {
+                merge_values(&mut merged, layer);
+            }
+        }
+        let (layer, ignored_vars) = env_layer(vars, &merged);
+        merge_values(&mut merged, layer);
+        let mut config: Self =
+            serde_json::from_value(merged).map_err(|e| format!("invalid config: {e}"))?;
+        config.validate()?;
+        config.ignored_vars = ignored_vars;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if self.engine.command_channel == 0 {
+            errors.push("engine.command_channel must be greater than 0");
+        }
+        if self.engine.update_channel == 0 {
+            errors.push("engine.update_channel must be greater than 0");
+        }
+        if self.ui.tick_ms == 0 {
+            errors.push("ui.tick_ms must be greater than 0");
+        }
+        if self.ui.log_capacity == 0 {
+            errors.push("ui.log_capacity must be greater than 0");
+        }
+        if self.reports.dir.trim().is_empty() {
+            errors.push("reports.dir must not be empty");
+        }
+        if self.reports.formats.is_empty() {
+            errors.push("reports.formats must list at least one format");
+        }
+        if self.llm.model.trim().is_empty() {
+            errors.push("llm.model must not be empty");
+        }
+        let keys_error = Keymaps::build(&self.keys).err();
+        if let Some(err) = &keys_error {
+            errors.push(err);
+        }
+        let theme_error = Theme::build(&self.theme, false).err();
+        if let Some(err) = &theme_error {
+            errors.push(err);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("invalid config: {}", errors.join("; ")))
+        }
+    }
+
+    /// The spec a new task starts from before a spec file or the user changes it.
+    pub fn default_spec(&self) -> TaskSpec {
+        let defaults = TaskSpec::default();
+        TaskSpec {
+            dataset_folder: self.defaults.dataset_folder.clone(),
+            llm: LlmSpec {
+                model: self.llm.model.clone(),
+                ..defaults.llm.clone()
+            },
+            ..defaults
+        }
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("revolver").join("config.toml"))
+}
+
+/// Recursively merges `overlay` into `base`; objects merge key by key, any
+/// other value replaces what was there.
+pub fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn read_layer(path: &Path) -> Result<Option<Value>, String> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("cannot read {}: {err}", path.display())),
+    };
+    toml::from_str(&source)
+        .map(Some)
+        .map_err(|e| format!("invalid config {}: {e}", path.display()))
+}
+
+/// `REVOLVER_UI__TICK_MS=100` becomes `{ "ui": { "tick_ms": 100 } }`. Each
+/// value is read as the type the setting has in `known`: text as is, anything
+/// else as JSON, and lists also as comma-separated words. Variables naming no
+/// setting in `known` are returned apart instead, since other tools may share
+/// the prefix; so are prefixed variables whose name or value is not UTF-8.
+fn env_layer<I>(vars: I, known: &Value) -> (Value, Vec<String>)
+where
+    I: IntoIterator<Item = (OsString, OsString)>,
+{
+    let mut layer = Value::Object(Default::default());
+    let mut ignored = Vec::new();
+    for (key, raw) in vars {
+        let (key, raw) = match (key.into_string(), raw.into_string()) {
+            (Ok(key), Ok(raw)) => (key, raw),
+            (key, _) => {
+                let key = key.unwrap_or_else(|key| key.to_string_lossy().into_owned());
+                if key.starts_with(ENV_PREFIX) {
+                    ignored.push(key);
+                }
+                continue;
+            }
+        };
+        let Some(path) = key.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let (section, field) = match path.split_once("__") {
+            Some((section, field)) => (section.to_ascii_lowercase(), field.to_ascii_lowercase()),
+            None => {
+                ignored.push(key);
+                continue;
+            }
+        };
+        let Some(target) = known.get(&section).and_then(|section| section.get(&field)) else {
+            ignored.push(key);
+            continue;
+        };
+        let value = env_value(raw, target);
+        let mut entry = serde_json::Map::new();
+        entry.insert(field, value);
+        let mut section_value = serde_json::Map::new();
+        section_value.insert(section, Value::Object(entry));
+        merge_values(&mut layer, Value::Object(section_value));
+    }
+    ignored.sort();
+    (layer, ignored)
+}
+
+fn env_value(raw: String, target: &Value) -> Value {
+    match target {
+        Value::String(_) | Value::Null => Value::String(raw),
+        Value::Array(_) => serde_json::from_str(&raw).unwrap_or_else(|_| {
+            raw.split(',')
+                .map(|word| Value::String(word.trim().to_string()))
+                .collect()
+        }),
+        _ => serde_json::from_str(&raw).unwrap_or(Value::String(raw)),
+    }
+}
@@ -1 +1,2 @@
 pub mod scheduler;
+pub mod steering;
@@ -1,29 +1,80 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
 
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{mpsc, watch};
 use tokio::time::sleep;
 
-use crate::llm::RigLlm;
+use crate::config::AppConfig;
+use crate::engine::steering::{Steering, SteeringState};
+use crate::llm::{HypothesisProposal, RigLlm};
 use crate::protocol::{EngineToUi, UiToEngine};
-use crate::report::generate_markdown_report;
+use crate::report::{
+    generate_reports, load_template, remember_report_dir, ReportFormat, TaskOutcome,
+};
 use crate::task::{
-    Heuristics, Hypothesis, TaskDefinition, TaskPhase, TaskSnapshot, TaskStatus,
+    dataset_error, Heuristics, Hypothesis, ImageResult, IterationRecord, Prediction, TaskDefinition,
+    TaskPhase, TaskSnapshot, TaskStatus,
 };
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum TaskUpdate {
     Upsert(TaskSnapshot),
-    Log { id: usize, message: String },
+    Log {
+        id: usize,
+        level: LogLevel,
+        message: String,
+    },
+    /// Steering the task could no longer take; any mark the UI set for it
+    /// should be cleared.
+    SteeringDropped {
+        id: usize,
+        hypothesis_id: Option<usize>,
+        message: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+struct TaskHandle {
+    definition: TaskDefinition,
+    cancel: watch::Sender<bool>,
+    steering: mpsc::Sender<Steering>,
+    /// Set once the task has finished iterating; kept for exports.
+    outcome: watch::Receiver<Option<TaskOutcome>>,
 }
 
 pub async fn run_scheduler(
+    cmd_rx: mpsc::Receiver<UiToEngine>,
+    ui_tx: mpsc::Sender<EngineToUi>,
+) {
+    run_scheduler_with_config(cmd_rx, ui_tx, AppConfig::default()).await;
+}
+
+pub async fn run_scheduler_with_config(
     mut cmd_rx: mpsc::Receiver<UiToEngine>,
     ui_tx: mpsc::Sender<EngineToUi>,
+    config: AppConfig,
 ) {
     let mut next_id = 1usize;
-    let mut cancels: HashMap<usize, watch::Sender<bool>> = HashMap::new();
+    let mut tasks: HashMap<usize, TaskHandle> = HashMap::new();
     let llm = RigLlm::new_mock();
 
     while let Some(cmd) = cmd_rx.recv().await {
@@ -32,42 +83,258 @@ pub async fn run_scheduler(
                 let id = next_id;
                 next_id += 1;
                 let definition = TaskDefinition::mock(id, name);
-                let (cancel_tx, cancel_rx) = watch::channel(false);
-                cancels.insert(id, cancel_tx);
-                let llm_clone = llm.clone();
-                let ui_tx_clone = ui_tx.clone();
-                tokio::spawn(async move {
-                    run_task(definition, cancel_rx, ui_tx_clone, llm_clone).await;
-                });
+                spawn_task(definition, &mut tasks, &ui_tx, &llm, &config);
+            }
+            UiToEngine::AddTaskFromSpec { spec } => {
+                let id = next_id;
+                next_id += 1;
+                let definition = TaskDefinition::from_spec(id, &spec);
+                spawn_task(definition, &mut tasks, &ui_tx, &llm, &config);
             }
             UiToEngine::CancelTask { id } => {
-                if let Some(cancel) = cancels.get(&id) {
-                    let _ = cancel.send(true);
+                if let Some(task) = tasks.get(&id) {
+                    let _ = task.cancel.send(true);
+                }
+            }
+            UiToEngine::RetryTask { id } => {
+                if let Some(task) = tasks.get(&id) {
+                    let mut definition = task.definition.clone();
+                    definition.id = next_id;
+                    definition.created_at = SystemTime::now();
+                    next_id += 1;
+                    spawn_task(definition, &mut tasks, &ui_tx, &llm, &config);
+                }
+            }
+            UiToEngine::ExportReport { id, format } => {
+                if let Some(task) = tasks.get(&id) {
+                    export_report(task, format, &ui_tx, &config).await;
                 }
             }
+            UiToEngine::InjectHypothesis { task_id, text } => {
+                steer(&tasks, task_id, Steering::Inject(text), &ui_tx).await;
+            }
+            UiToEngine::PinHypothesis {
+                task_id,
+                hypothesis_id,
+            } => steer(&tasks, task_id, Steering::Pin(hypothesis_id), &ui_tx).await,
+            UiToEngine::BanHypothesis {
+                task_id,
+                hypothesis_id,
+            } => steer(&tasks, task_id, Steering::Ban(hypothesis_id), &ui_tx).await,
+            UiToEngine::ReleaseHypothesis {
+                task_id,
+                hypothesis_id,
+            } => steer(&tasks, task_id, Steering::Release(hypothesis_id), &ui_tx).await,
             UiToEngine::Shutdown => break,
         }
     }
 }
 
+fn spawn_task(
+    definition: TaskDefinition,
+    tasks: &mut HashMap<usize, TaskHandle>,
+    ui_tx: &mpsc::Sender<EngineToUi>,
+    llm: &RigLlm,
+    config: &AppConfig,
+) {
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let (steering_tx, steering_rx) = mpsc::channel(config.engine.command_channel);
+    let (outcome_tx, outcome_rx) = watch::channel(None);
+    tasks.insert(
+        definition.id,
+        TaskHandle {
+            definition: definition.clone(),
+            cancel: cancel_tx,
+            steering: steering_tx,
+            outcome: outcome_rx,
+        },
+    );
+    let llm_clone = llm.clone();
+    let ui_tx_clone = ui_tx.clone();
+    let config_clone = config.clone();
+    tokio::spawn(async move {
+        run_task(
+            definition,
+            cancel_rx,
+            steering_rx,
+            outcome_tx,
+            ui_tx_clone,
+            llm_clone,
+            config_clone,
+        )
+        .await;
+    });
+}
+
+async fn export_report(
+    task: &TaskHandle,
+    format: ReportFormat,
+    ui_tx: &mpsc::Sender<EngineToUi>,
+    config: &AppConfig,
+) {
+    let id = task.definition.id;
+    let outcome = task.outcome.borrow().clone();
+    let Some(outcome) = outcome else {
+        send_log(ui_tx, id, LogLevel::Warn, "No results to export yet.".to_string()).await;
+        return;
+    };
+    match write_reports(&task.definition, &outcome, config, &[format]) {
+        Ok(paths) => {
+            for path in paths {
+                send_log(ui_tx, id, LogLevel::Info, format!("Exported report to {path}.")).await;
+            }
+        }
+        Err(err) => {
+            send_log(ui_tx, id, LogLevel::Error, format!("Export failed: {err}")).await;
+        }
+    }
+}
+
+/// Writes `formats` reports of `outcome` to the task's report directory,
+/// preferring the spec's `[report]` settings over the config.
+fn write_reports(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    config: &AppConfig,
+    formats: &[ReportFormat],
+) -> Result<Vec<String>, String> {
+    let reports_dir = definition
+        .report
+        .dir
+        .clone()
+        .unwrap_or_else(|| config.reports.dir.clone());
+    let template = definition
+        .report
+        .template
+        .as_deref()
+        .or(config.reports.template.as_deref());
+    let template = load_template(template)?;
+    let paths = generate_reports(definition, outcome, Path::new(&reports_dir), &template, formats)?;
+    remember_report_dir(Path::new(&config.reports.dir), Path::new(&reports_dir))
+        .map_err(|err| format!("cannot record report directory {reports_dir}: {err}"))?;
+    Ok(paths)
+}
+
+/// Forwards `steering` without blocking the scheduler. Steering a task can no
+/// longer take, because it has stopped steering or its queue is full, is
+/// reported back to the UI instead.
+async fn steer(
+    tasks: &HashMap<usize, TaskHandle>,
+    id: usize,
+    steering: Steering,
+    ui_tx: &mpsc::Sender<EngineToUi>,
+) {
+    let (steering, reason) = match tasks.get(&id) {
+        None => (steering, "does not exist"),
+        Some(task) => match task.steering.try_send(steering) {
+            Ok(()) => return,
+            Err(TrySendError::Full(steering)) => (steering, "has too much steering queued"),
+            Err(TrySendError::Closed(steering)) => (steering, "is no longer running"),
+        },
+    };
+    let update = TaskUpdate::SteeringDropped {
+        id,
+        hypothesis_id: steering.hypothesis_id(),
+        message: steering.dropped_message(id, reason),
+    };
+    let _ = ui_tx.send(EngineToUi::TaskUpdate(update)).await;
+}
+
 async fn run_task(
     definition: TaskDefinition,
     cancel_rx: watch::Receiver<bool>,
+    mut steering_rx: mpsc::Receiver<Steering>,
+    outcome_tx: watch::Sender<Option<TaskOutcome>>,
     ui_tx: mpsc::Sender<EngineToUi>,
     llm: RigLlm,
+    config: AppConfig,
 ) {
+    let sleeps = &config.engine;
+    let started_at = SystemTime::now();
     let mut snapshot = TaskSnapshot::from_definition(&definition);
     snapshot.status = TaskStatus::Running;
     snapshot.phase = TaskPhase::GeneratingHypotheses;
+    if !definition.uses_mock_dataset()
+        && let Some(err) = dataset_error(&definition.dataset_folder, &definition.dataset)
+    {
+        snapshot.status = TaskStatus::Failed(err);
+        snapshot.phase = TaskPhase::Finished;
+        send_update(&ui_tx, snapshot).await;
+        return;
+    }
     send_update(&ui_tx, snapshot.clone()).await;
-    send_log(&ui_tx, definition.id, "Task started with mock dataset.".to_string()).await;
+    let started = if definition.uses_mock_dataset() {
+        "Task started with mock dataset.".to_string()
+    } else {
+        format!(
+            "Task started with {} images from {}.",
+            definition.dataset.len(),
+            definition.dataset_folder
+        )
+    };
+    send_log(&ui_tx, definition.id, LogLevel::Info, started).await;
 
     let mut verified: Vec<Hypothesis> = Vec::new();
     let mut discarded: Vec<Hypothesis> = Vec::new();
-    let mut best_score = 0.0f32;
-    let mut best_program = String::from("uninitialized");
+    let mut best = Synthesis::none();
+    let mut iterations: Vec<IterationRecord> = Vec::new();
+    let mut steering = SteeringState::new(definition.verify_threshold);
+    // Hypothesis ids are unique within the task; steering and marks key on them.
+    let mut next_id = 0usize;
+    // Set when pins or bans changed the verified list since the last synthesis.
+    let mut steered = false;
     let mut no_improve_streak = 0usize;
 
+    if !definition.seed_hypotheses.is_empty() {
+        let seeds_started = Instant::now();
+        send_log(
+            &ui_tx,
+            definition.id,
+            LogLevel::Info,
+            format!(
+                "Iteration 0: evaluating {} seed hypotheses.",
+                definition.seed_hypotheses.len()
+            ),
+        )
+        .await;
+        let proposals = definition
+            .seed_hypotheses
+            .iter()
+            .map(|seed| HypothesisProposal {
+                description: seed.clone(),
+                rationale: "Seed hypothesis from the task definition.".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let generated = proposals.len();
+        let mut rng = iteration_rng(&definition, 0);
+        let seed_best = evaluate_proposals(
+            &definition,
+            0,
+            proposals,
+            &steering,
+            &mut rng,
+            &mut next_id,
+            &mut verified,
+            &mut discarded,
+        );
+        best = Synthesis::from_verified(&definition, &verified, 0, &mut rng);
+        iterations.push(IterationRecord {
+            iteration: 0,
+            generated,
+            verified: verified.len(),
+            best_score: best.score,
+            last_score: seed_best,
+            elapsed_ms: seeds_started.elapsed().as_millis() as u64,
+            program_version: best.version,
+        });
+        snapshot.verified = verified.clone();
+        snapshot.discarded = discarded.clone();
+        snapshot.best_score = best.score;
+        snapshot.last_score = seed_best;
+        snapshot.history = iterations.clone();
+        send_update(&ui_tx, snapshot.clone()).await;
+    }
+
     for iteration in 1..=definition.max_iters {
         if *cancel_rx.borrow() {
             snapshot.status = TaskStatus::Cancelled;
@@ -76,6 +343,17 @@ async fn run_task(
             return;
         }
 
+        steered |= apply_steering(
+            &mut steering_rx,
+            &mut steering,
+            &mut verified,
+            &mut discarded,
+            &ui_tx,
+            definition.id,
+        )
+        .await;
+
+        let iteration_started = Instant::now();
         snapshot.iteration = iteration;
         snapshot.phase = TaskPhase::GeneratingHypotheses;
         snapshot.progress = (iteration as f32 - 0.5) / definition.max_iters as f32;
@@ -83,64 +361,83 @@ async fn run_task(
         send_log(
             &ui_tx,
             definition.id,
+            LogLevel::Info,
             format!("Iteration {iteration}: generating hypotheses."),
         )
         .await;
-        sleep(Duration::from_millis(250)).await;
+        sleep(Duration::from_millis(sleeps.generating_sleep_ms)).await;
 
-        let hypothesis_texts = llm.generate_hypotheses(&definition, iteration).await;
+        let mut proposals = llm.generate_hypotheses(&definition, iteration).await;
+        proposals.extend(steering.take_injected().into_iter().map(|text| HypothesisProposal {
+            description: text,
+            rationale: "Injected by the user.".to_string(),
+        }));
+        let generated = proposals.len();
+        let verified_before = verified.len();
         snapshot.phase = TaskPhase::EvaluatingHypotheses;
         send_update(&ui_tx, snapshot.clone()).await;
         send_log(
             &ui_tx,
             definition.id,
+            LogLevel::Info,
             format!("Iteration {iteration}: evaluating hypotheses."),
         )
         .await;
 
-        let mut rng = StdRng::seed_from_u64(
-            (definition.id as u64).wrapping_mul(31) ^ (iteration as u64).wrapping_mul(997),
+        let mut rng = iteration_rng(&definition, iteration);
+        let iteration_best = evaluate_proposals(
+            &definition,
+            iteration,
+            proposals,
+            &steering,
+            &mut rng,
+            &mut next_id,
+            &mut verified,
+            &mut discarded,
         );
 
-        let mut iteration_best = 0.0f32;
-        for (idx, text) in hypothesis_texts.into_iter().enumerate() {
-            let score = evaluate_hypothesis(
-                &text,
-                &definition.heuristics,
-                definition.dataset.len(),
-                &mut rng,
-            );
-            let hypothesis = Hypothesis {
-                id: iteration * 100 + idx,
-                description: text,
-                score,
-            };
-            if score >= 0.6 {
-                iteration_best = iteration_best.max(score);
-                verified.push(hypothesis);
-            } else {
-                discarded.push(hypothesis);
-            }
-        }
-
         snapshot.phase = TaskPhase::Reducing;
-        snapshot.verified = tail_hypotheses(&verified, 5);
-        snapshot.discarded = tail_hypotheses(&discarded, 5);
+        snapshot.verified = verified.clone();
+        snapshot.discarded = discarded.clone();
         snapshot.last_score = iteration_best;
         send_update(&ui_tx, snapshot.clone()).await;
-        sleep(Duration::from_millis(200)).await;
+        sleep(Duration::from_millis(sleeps.reducing_sleep_ms)).await;
 
         snapshot.phase = TaskPhase::Synthesizing;
-        if iteration_best > best_score + 0.02 {
-            best_score = iteration_best;
-            best_program = format!(
-                "fn solve(images: &[Image]) -> Vec<Label> {{ /* v{iteration} */ }}"
-            );
+        // Steering sent while the iteration ran still shapes its synthesis.
+        steered |= apply_steering(
+            &mut steering_rx,
+            &mut steering,
+            &mut verified,
+            &mut discarded,
+            &ui_tx,
+            definition.id,
+        )
+        .await;
+        let candidate = Synthesis::from_verified(&definition, &verified, iteration, &mut rng);
+        if candidate.score > best.score + 0.02 {
+            best = candidate;
+            steered = false;
             no_improve_streak = 0;
             send_log(
                 &ui_tx,
                 definition.id,
-                format!("Iteration {iteration}: new best score {:.2}.", best_score),
+                LogLevel::Info,
+                format!("Iteration {iteration}: new best score {:.2}.", best.score),
+            )
+            .await;
+        } else if steered {
+            best = candidate;
+            steered = false;
+            no_improve_streak += 1;
+            send_log(
+                &ui_tx,
+                definition.id,
+                LogLevel::Info,
+                format!(
+                    "Iteration {iteration}: resynthesized after steering, best score {:.2}.",
+                    best.score
+                ),
             )
             .await;
         } else {
@@ -148,48 +445,264 @@ async fn run_task(
             send_log(
                 &ui_tx,
                 definition.id,
+                LogLevel::Warn,
                 format!("Iteration {iteration}: no improvement."),
             )
             .await;
         }
-        snapshot.best_score = best_score;
+        snapshot.verified = verified.clone();
+        snapshot.discarded = discarded.clone();
+        snapshot.best_score = best.score;
         send_update(&ui_tx, snapshot.clone()).await;
-        sleep(Duration::from_millis(200)).await;
+        sleep(Duration::from_millis(sleeps.synthesizing_sleep_ms)).await;
 
+        iterations.push(IterationRecord {
+            iteration,
+            generated,
+            verified: verified.len().saturating_sub(verified_before),
+            best_score: best.score,
+            last_score: iteration_best,
+            elapsed_ms: iteration_started.elapsed().as_millis() as u64,
+            program_version: best.version,
+        });
+        snapshot.history = iterations.clone();
         snapshot.phase = TaskPhase::Testing;
         snapshot.progress = iteration as f32 / definition.max_iters as f32;
         send_update(&ui_tx, snapshot.clone()).await;
-        sleep(Duration::from_millis(200)).await;
+        sleep(Duration::from_millis(sleeps.testing_sleep_ms)).await;
 
-        if no_improve_streak >= 2 {
+        if no_improve_streak >= definition.patience {
             break;
         }
     }
 
+    // Steering from here on is refused; what is already queued still counts.
+    steering_rx.close();
+    steered |= apply_steering(
+        &mut steering_rx,
+        &mut steering,
+        &mut verified,
+        &mut discarded,
+        &ui_tx,
+        definition.id,
+    )
+    .await;
+    // No iteration is left to pick up late injections, so evaluate them here.
+    let injected = steering.take_injected();
+    if !injected.is_empty() {
+        send_log(
+            &ui_tx,
+            definition.id,
+            LogLevel::Info,
+            format!("Evaluating {} injected hypotheses after the last iteration.", injected.len()),
+        )
+        .await;
+        let proposals = injected
+            .into_iter()
+            .map(|text| HypothesisProposal {
+                description: text,
+                rationale: "Injected by the user.".to_string(),
+            })
+            .collect();
+        let mut rng = iteration_rng(&definition, snapshot.iteration);
+        evaluate_proposals(
+            &definition,
+            snapshot.iteration,
+            proposals,
+            &steering,
+            &mut rng,
+            &mut next_id,
+            &mut verified,
+            &mut discarded,
+        );
+        steered = true;
+    }
+    if steered {
+        let version = snapshot.iteration;
+        let mut rng = iteration_rng(&definition, version);
+        best = Synthesis::from_verified(&definition, &verified, version, &mut rng);
+        send_log(
+            &ui_tx,
+            definition.id,
+            LogLevel::Info,
+            format!("Resynthesized after steering, best score {:.2}.", best.score),
+        )
+        .await;
+    }
+
     snapshot.phase = TaskPhase::Reporting;
     snapshot.status = TaskStatus::Running;
+    snapshot.verified = verified.clone();
+    snapshot.discarded = discarded.clone();
+    snapshot.best_score = best.score;
     send_update(&ui_tx, snapshot.clone()).await;
-    send_log(&ui_tx, definition.id, "Generating report.".to_string()).await;
+    send_log(
+        &ui_tx,
+        definition.id,
+        LogLevel::Info,
+        "Generating report.".to_string(),
+    )
+    .await;
 
-    match generate_markdown_report(&definition, &verified, &discarded, &best_program, best_score) {
-        Ok(path) => {
-            snapshot.report_path = Some(path);
+    let outcome = TaskOutcome {
+        verified: verified.clone(),
+        discarded: discarded.clone(),
+        program: best.program,
+        best_score: best.score,
+        predictions: best.predictions,
+        iterations,
+        started_at,
+        finished_at: SystemTime::now(),
+    };
+    let report = write_reports(&definition, &outcome, &config, &config.reports.formats);
+    outcome_tx.send_replace(Some(outcome));
+    match report {
+        Ok(paths) => {
+            snapshot.report_path = paths.into_iter().next();
             snapshot.status = TaskStatus::Done;
             snapshot.phase = TaskPhase::Finished;
         }
         Err(err) => {
+            send_log(
+                &ui_tx,
+                definition.id,
+                LogLevel::Error,
+                format!("Report generation failed: {err}"),
+            )
+            .await;
             snapshot.status = TaskStatus::Failed(err);
             snapshot.phase = TaskPhase::Finished;
         }
     }
 
     snapshot.progress = 1.0;
-    snapshot.verified = tail_hypotheses(&verified, 5);
-    snapshot.discarded = tail_hypotheses(&discarded, 5);
-    snapshot.best_score = best_score;
+    snapshot.verified = verified.clone();
+    snapshot.discarded = discarded.clone();
+    snapshot.best_score = best.score;
     send_update(&ui_tx, snapshot).await;
 }
 
+/// Applies the steering queued so far and logs each change; steering for an
+/// unknown hypothesis is reported as dropped. Returns whether a pin, ban or
+/// release changed what synthesis may use.
+async fn apply_steering(
+    steering_rx: &mut mpsc::Receiver<Steering>,
+    steering: &mut SteeringState,
+    verified: &mut Vec<Hypothesis>,
+    discarded: &mut Vec<Hypothesis>,
+    ui_tx: &mpsc::Sender<EngineToUi>,
+    id: usize,
+) -> bool {
+    let mut changed = false;
+    while let Ok(instruction) = steering_rx.try_recv() {
+        let hypothesis_id = instruction.hypothesis_id();
+        match steering.apply(instruction.clone(), verified, discarded) {
+            Ok(message) => {
+                changed |= hypothesis_id.is_some();
+                send_log(ui_tx, id, LogLevel::Info, message).await;
+            }
+            Err(reason) => {
+                let update = TaskUpdate::SteeringDropped {
+                    id,
+                    hypothesis_id,
+                    message: instruction.dropped_message(id, &reason),
+                };
+                let _ = ui_tx.send(EngineToUi::TaskUpdate(update)).await;
+            }
+        }
+    }
+    changed
+}
+
+struct Synthesis {
+    score: f32,
+    program: String,
+    predictions: Vec<Prediction>,
+    version: Option<usize>,
+}
+
+impl Synthesis {
+    fn none() -> Self {
+        Self {
+            score: 0.0,
+            program: String::from("uninitialized"),
+            predictions: Vec::new(),
+            version: None,
+        }
+    }
+
+    /// Synthesizes program `version` from every verified hypothesis, pinned
+    /// ones included whatever their score; it scores as the best of them.
+    fn from_verified(
+        definition: &TaskDefinition,
+        verified: &[Hypothesis],
+        version: usize,
+        rng: &mut StdRng,
+    ) -> Self {
+        if verified.is_empty() {
+            return Self::none();
+        }
+        let score = verified.iter().map(|h| h.score).fold(0.0f32, f32::max);
+        let ids = verified.iter().map(|h| h.id.to_string()).collect::<Vec<_>>();
+        Self {
+            score,
+            program: format!(
+                "fn solve(images: &[Image]) -> Vec<Label> {{ /* v{version}: hypotheses {} */ }}",
+                ids.join(", ")
+            ),
+            predictions: predict_labels(definition, score, rng),
+            version: Some(version),
+        }
+    }
+}
+
+fn iteration_rng(definition: &TaskDefinition, iteration: usize) -> StdRng {
+    StdRng::seed_from_u64(
+        (definition.id as u64).wrapping_mul(31) ^ (iteration as u64).wrapping_mul(997),
+    )
+}
+
+/// Scores `proposals` and files them as verified or discarded under ids taken
+/// from `next_id`; returns the best verified score, or 0 if none passed.
+#[allow(clippy::too_many_arguments)]
+fn evaluate_proposals(
+    definition: &TaskDefinition,
+    iteration: usize,
+    proposals: Vec<HypothesisProposal>,
+    steering: &SteeringState,
+    rng: &mut StdRng,
+    next_id: &mut usize,
+    verified: &mut Vec<Hypothesis>,
+    discarded: &mut Vec<Hypothesis>,
+) -> f32 {
+    let mut iteration_best = 0.0f32;
+    for proposal in proposals {
+        let score = evaluate_hypothesis(
+            &proposal.description,
+            &definition.heuristics,
+            definition.dataset.len(),
+            rng,
+        );
+        let id = *next_id;
+        *next_id += 1;
+        let hypothesis = Hypothesis {
+            id,
+            iteration,
+            description: proposal.description,
+            rationale: proposal.rationale,
+            score,
+            results: image_results(definition, id, score),
+        };
+        if score >= definition.verify_threshold && !steering.is_banned(&hypothesis.description) {
+            iteration_best = iteration_best.max(score);
+            verified.push(hypothesis);
+        } else {
+            discarded.push(hypothesis);
+        }
+    }
+    iteration_best
+}
+
 fn evaluate_hypothesis(
     hypothesis: &str,
     heuristics: &Heuristics,
@@ -208,19 +721,58 @@ fn evaluate_hypothesis(
     (0.4 * score + 0.4 * heuristic_bias + 0.2 * dataset_bias + noise).clamp(0.0, 1.0)
 }
 
-fn tail_hypotheses(all: &[Hypothesis], limit: usize) -> Vec<Hypothesis> {
-    if all.len() <= limit {
-        return all.to_vec();
-    }
-    all[all.len() - limit..].to_vec()
+/// Simulates running the best program over the dataset: each image is labelled
+/// correctly with probability `accuracy`, otherwise with another label.
+fn predict_labels(definition: &TaskDefinition, accuracy: f32, rng: &mut StdRng) -> Vec<Prediction> {
+    let labels = definition.label_set();
+    definition
+        .dataset
+        .iter()
+        .map(|image| {
+            let guess = labels[rng.gen_range(0..labels.len())].clone();
+            let predicted = match &image.label {
+                Some(expected) if rng.gen_range(0.0..1.0) < accuracy => expected.clone(),
+                Some(expected) if labels.len() > 1 && guess == *expected => labels
+                    .iter()
+                    .find(|label| *label != expected)
+                    .cloned()
+                    .unwrap_or(guess),
+                _ => guess,
+            };
+            Prediction {
+                image_id: image.id,
+                image: image.name.clone(),
+                predicted,
+                expected: image.label.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Simulates checking a hypothesis on each image; it holds on roughly a
+/// `score` share of them. Seeded per hypothesis so scores stay reproducible.
+fn image_results(definition: &TaskDefinition, hypothesis_id: usize, score: f32) -> Vec<ImageResult> {
+    let mut rng = StdRng::seed_from_u64(
+        (definition.id as u64).wrapping_mul(7_919) ^ (hypothesis_id as u64).wrapping_mul(104_729),
+    );
+    definition
+        .dataset
+        .iter()
+        .map(|image| ImageResult {
+            image_id: image.id,
+            image: image.name.clone(),
+            path: image.path.clone(),
+            passed: rng.gen_range(0.0..1.0) < score,
+        })
+        .collect()
 }
 
 async fn send_update(ui_tx: &mpsc::Sender<EngineToUi>, snapshot: TaskSnapshot) {
     let _ = ui_tx.send(EngineToUi::TaskUpdate(TaskUpdate::Upsert(snapshot))).await;
 }
 
-async fn send_log(ui_tx: &mpsc::Sender<EngineToUi>, id: usize, message: String) {
+async fn send_log(ui_tx: &mpsc::Sender<EngineToUi>, id: usize, level: LogLevel, message: String) {
     let _ = ui_tx
-        .send(EngineToUi::TaskUpdate(TaskUpdate::Log { id, message }))
+        .send(EngineToUi::TaskUpdate(TaskUpdate::Log { id, level, message }))
         .await;
 }
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use crate::task::Hypothesis;
+
+/// A user instruction for one running task, forwarded by the scheduler.
+#[derive(Clone, Debug)]
+pub enum Steering {
+    Inject(String),
+    Pin(usize),
+    Ban(usize),
+    Release(usize),
+}
+
+impl Steering {
+    pub fn hypothesis_id(&self) -> Option<usize> {
+        match self {
+            Steering::Inject(_) => None,
+            Steering::Pin(id) | Steering::Ban(id) | Steering::Release(id) => Some(*id),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Steering::Inject(text) => format!("Injected hypothesis \"{text}\""),
+            Steering::Pin(id) => format!("Pin of hypothesis {id}"),
+            Steering::Ban(id) => format!("Ban of hypothesis {id}"),
+            Steering::Release(id) => format!("Release of hypothesis {id}"),
+        }
+    }
+
+    pub fn dropped_message(&self, task_id: usize, reason: &str) -> String {
+        format!("{} was dropped: task {task_id} {reason}.", self.describe())
+    }
+}
+
+/// Steering received so far by a task.
+///
+/// Injected hypotheses wait here until the next iteration evaluates them.
+/// Pinned hypotheses stay verified whatever their score; banned ones, and any
+/// later hypothesis with the same description, never reach synthesis.
+#[derive(Clone, Debug, Default)]
+pub struct SteeringState {
+    verify_threshold: f32,
+    injected: Vec<String>,
+    pinned: HashSet<usize>,
+    banned: HashSet<usize>,
+    banned_descriptions: HashSet<String>,
+}
+
+impl SteeringState {
+    pub fn new(verify_threshold: f32) -> Self {
+        Self {
+            verify_threshold,
+            ..Self::default()
+        }
+    }
+
+    /// Injected hypotheses not yet evaluated, in the order they arrived.
+    pub fn take_injected(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.injected)
+    }
+
+    /// Applies `steering` and moves already evaluated hypotheses between the
+    /// lists accordingly. Returns a log line describing the change, or why it
+    /// was refused when it names a hypothesis the task does not have.
+    pub fn apply(
+        &mut self,
+        steering: Steering,
+        verified: &mut Vec<Hypothesis>,
+        discarded: &mut Vec<Hypothesis>,
+    ) -> Result<String, String> {
+        if let Some(id) = steering.hypothesis_id()
+            && !verified.iter().chain(discarded.iter()).any(|h| h.id == id)
+        {
+            return Err(format!("has no hypothesis {id}"));
+        }
+        Ok(match steering {
+            Steering::Inject(text) => {
+                let message = format!("Queued injected hypothesis: {text}");
+                self.injected.push(text);
+                message
+            }
+            Steering::Pin(id) => {
+                self.banned.remove(&id);
+                self.pinned.insert(id);
+                move_hypothesis(id, discarded, verified);
+                if let Some(hypothesis) = verified.iter().find(|h| h.id == id) {
+                    self.banned_descriptions.remove(&hypothesis.description);
+                }
+                format!("Hypothesis {id} pinned.")
+            }
+            Steering::Ban(id) => {
+                self.pinned.remove(&id);
+                self.banned.insert(id);
+                move_hypothesis(id, verified, discarded);
+                if let Some(hypothesis) = discarded.iter().find(|h| h.id == id) {
+                    self.banned_descriptions.insert(hypothesis.description.clone());
+                }
+                format!("Hypothesis {id} banned.")
+            }
+            Steering::Release(id) => {
+                // Back to whichever list its score earns.
+                if self.pinned.remove(&id)
+                    && verified
+                        .iter()
+                        .any(|h| h.id == id && h.score < self.verify_threshold)
+                {
+                    move_hypothesis(id, verified, discarded);
+                }
+                if self.banned.remove(&id)
+                    && let Some(hypothesis) = discarded.iter().find(|h| h.id == id)
+                {
+                    self.banned_descriptions.remove(&hypothesis.description);
+                    if hypothesis.score >= self.verify_threshold {
+                        move_hypothesis(id, discarded, verified);
+                    }
+                }
+                format!("Hypothesis {id} released.")
+            }
+        })
+    }
+
+    pub fn is_banned(&self, description: &str) -> bool {
+        self.banned_descriptions.contains(description)
+    }
+}
+
+fn move_hypothesis(id: usize, from: &mut Vec<Hypothesis>, to: &mut Vec<Hypothesis>) {
+    if let Some(pos) = from.iter().position(|h| h.id == id) {
+        to.push(from.remove(pos));
+    }
+}
@@ -1,9 +1,16 @@
 pub mod gateway;
 pub mod llm;
+pub mod path_picker;
+pub mod preview;
 pub mod app;
+pub mod cli;
+pub mod command;
+pub mod config;
 pub mod screens;
 pub mod ui;
 pub mod protocol;
 pub mod report;
+pub mod spec;
 pub mod engine;
 pub mod task;
+pub mod text;
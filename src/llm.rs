@@ -2,6 +2,12 @@ use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::task::TaskDefinition;
 
+#[derive(Clone, Debug)]
+pub struct HypothesisProposal {
+    pub description: String,
+    pub rationale: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct RigLlm {
     seed: u64,
@@ -16,17 +22,31 @@ impl RigLlm {
         &self,
         task: &TaskDefinition,
         iteration: usize,
-    ) -> Vec<String> {
+    ) -> Vec<HypothesisProposal> {
         #[cfg(feature = "real-llm")]
         {
-            use rig::{client::CompletionClient, completion::Prompt, providers::openai};
+            use rig::{
+                client::{CompletionClient, ProviderClient},
+                completion::Prompt,
+                providers::openai,
+            };
 
             let client = openai::Client::from_env();
-            let agent = client.agent("gpt-4o-mini").build();
+            let agent = client
+                .agent(&task.llm.model)
+                .temperature(task.llm.temperature)
+                .build();
+            let hints = task
+                .hints
+                .iter()
+                .map(|hint| hint.title.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
             let prompt = format!(
-                "Generate 5 short hypotheses for task '{}' using heuristics {:?} on {} images.",
+                "Generate 5 short hypotheses for task '{}' using heuristics {:?} and hints [{}] on {} images.",
                 task.name,
                 task.heuristics,
+                hints,
                 task.dataset.len()
             );
 
@@ -35,7 +55,10 @@ impl RigLlm {
                     .lines()
                     .map(|line| line.trim().trim_start_matches('-').trim())
                     .filter(|line| !line.is_empty())
-                    .map(|line| line.to_string())
+                    .map(|line| HypothesisProposal {
+                        description: line.to_string(),
+                        rationale: format!("Suggested by {}.", task.llm.model),
+                    })
                     .collect::<Vec<_>>();
                 if !lines.is_empty() {
                     return lines;
@@ -48,12 +71,29 @@ impl RigLlm {
         );
         let count = rng.gen_range(4..=7);
         (0..count)
-            .map(|i| {
-                format!(
-                    "Iter {iteration} hypothesis {i}: focus on blobs>={} and edge {:.2}",
-                    task.heuristics.min_blob_area,
-                    task.heuristics.edge_threshold
-                )
+            .map(|i| match task.hints.get(i % task.hints.len().max(1)) {
+                Some(hint) => HypothesisProposal {
+                    description: format!(
+                        "Iter {iteration} hypothesis {i}: apply '{}' with blobs>={} and edge {:.2}",
+                        hint.title,
+                        task.heuristics.min_blob_area,
+                        task.heuristics.edge_threshold
+                    ),
+                    rationale: format!(
+                        "Hint '{}' ({} example images) points at this pattern.",
+                        hint.title,
+                        hint.images.len()
+                    ),
+                },
+                None => HypothesisProposal {
+                    description: format!(
+                        "Iter {iteration} hypothesis {i}: focus on blobs>={} and edge {:.2}",
+                        task.heuristics.min_blob_area,
+                        task.heuristics.edge_threshold
+                    ),
+                    rationale: "No hints given; derived from the blob and edge heuristics."
+                        .to_string(),
+                },
             })
             .collect()
     }
@@ -1,64 +1,106 @@
-mod app;
-mod llm;
-mod report;
-mod engine;
-mod task;
-mod ui;
-mod screens;
-mod protocol;
-mod gateway;
-
 use std::io::{self, Stdout};
+use std::process::ExitCode;
 use std::time::Duration;
 
-use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyEventKind,
+};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
 use crossterm::execute;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use tokio::sync::mpsc;
 use tokio::time::{interval, sleep};
 
-use crate::app::AppState;
-use crate::gateway::Gateway;
-use crate::protocol::{EngineToUi, UiToEngine};
-use crate::engine::scheduler::run_scheduler;
-use crate::screens::dispatch_key;
-use crate::app::AppEvent;
+use revolver::app::AppState;
+use revolver::cli::{self, parse_args, CliCommand};
+use revolver::config::AppConfig;
+use revolver::gateway::Gateway;
+use revolver::protocol::{EngineToUi, UiToEngine};
+use revolver::engine::scheduler::run_scheduler_with_config;
+use revolver::screens::{self, dispatch_key, dispatch_mouse, dispatch_paste};
+use revolver::ui;
+use revolver::app::AppEvent;
 
 #[tokio::main]
-async fn main() -> io::Result<()> {
+async fn main() -> io::Result<ExitCode> {
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return Ok(ExitCode::from(78));
+        }
+    };
+    for name in &config.ignored_vars {
+        eprintln!("warning: ignoring {name}: no such setting or not UTF-8");
+    }
+    match parse_args(std::env::args().skip(1)) {
+        Ok(CliCommand::Tui) => run_tui(config).await.map(|_| ExitCode::SUCCESS),
+        Ok(command) => Ok(cli::run(command, &config).await),
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            Ok(ExitCode::from(64))
+        }
+    }
+}
+
+async fn run_tui(config: AppConfig) -> io::Result<()> {
     let mut terminal = setup_terminal()?;
 
-    terminal.draw(|frame| ui::draw_splash(frame))?;
-    sleep(Duration::from_secs(3)).await;
+    terminal.draw(ui::draw_splash)?;
+    sleep(config.ui.splash()).await;
 
-    let (ui_to_engine_tx, ui_to_engine_rx) = mpsc::channel(32);
-    let (engine_to_ui_tx, engine_to_ui_rx) = mpsc::channel(128);
-    tokio::spawn(run_scheduler(ui_to_engine_rx, engine_to_ui_tx));
+    let (ui_to_engine_tx, ui_to_engine_rx) = mpsc::channel(config.engine.command_channel);
+    let (engine_to_ui_tx, engine_to_ui_rx) = mpsc::channel(config.engine.update_channel);
+    tokio::spawn(run_scheduler_with_config(
+        ui_to_engine_rx,
+        engine_to_ui_tx,
+        config.clone(),
+    ));
     let mut gateway = Gateway::new(ui_to_engine_tx, engine_to_ui_rx);
 
-    let mut app = AppState::new();
-    let mut tick = interval(Duration::from_millis(200));
+    let mut tick = interval(config.ui.tick());
+    let mut app = AppState::with_config(config);
+    measure_cell_size(&mut app);
 
     loop {
         terminal.draw(|frame| screens::draw(frame, &app))?;
+        if let Some(graphics) = app.previews.take_graphics() {
+            if graphics.redraw {
+                // Old sixel pixels only disappear when their cells are repainted.
+                terminal.clear()?;
+                terminal.draw(|frame| screens::draw(frame, &app))?;
+                app.previews.take_graphics();
+            }
+            graphics.write(terminal.backend_mut())?;
+        }
         while event::poll(Duration::from_millis(0))? {
-            let event = event::read()?;
-            if let Event::Key(key) = event {
-                if key.kind != KeyEventKind::Press {
-                    continue;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if dispatch_key(key, &mut app)? {
+                        gateway.send(UiToEngine::Shutdown).await;
+                        restore_terminal(&mut terminal)?;
+                        return Ok(());
+                    }
                 }
-                if dispatch_key(key.code, &mut app)? {
-                    gateway.send(UiToEngine::Shutdown).await;
-                    restore_terminal(&mut terminal)?;
-                    return Ok(());
-                }
-                if process_events(&mut app, &gateway).await? {
-                    gateway.send(UiToEngine::Shutdown).await;
-                    restore_terminal(&mut terminal)?;
-                    return Ok(());
+                Event::Mouse(mouse) => dispatch_mouse(mouse, &mut app),
+                Event::Paste(text) => dispatch_paste(&text, &mut app),
+                Event::Resize(..) => {
+                    // Repaint from scratch: graphics and stale cells don't survive a resize.
+                    terminal.autoresize()?;
+                    terminal.clear()?;
+                    measure_cell_size(&mut app);
+                    app.previews.invalidate();
                 }
+                _ => continue,
+            }
+            if process_events(&mut app, &gateway).await? {
+                gateway.send(UiToEngine::Shutdown).await;
+                restore_terminal(&mut terminal)?;
+                return Ok(());
             }
         }
 
@@ -78,10 +120,22 @@ async fn main() -> io::Result<()> {
     }
 }
 
+/// Pixels per cell for sixel and kitty previews; kept when the terminal
+/// doesn't report its pixel size.
+fn measure_cell_size(app: &mut AppState) {
+    if let Ok(size) = terminal::window_size()
+        && size.columns > 0
+        && size.rows > 0
+        && size.width > 0
+    {
+        app.previews.cell_size = (size.width / size.columns, size.height / size.rows);
+    }
+}
+
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     Terminal::new(backend)
 }
@@ -113,7 +167,8 @@ fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Re
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
     Ok(())
@@ -0,0 +1,210 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::task::is_image_file;
+
+/// What the picked path is used for; decides whether files or folders can be chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathTarget {
+    DatasetFolder,
+    Image,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Chooses the directory being browsed; only offered for folder targets.
+    Current,
+    Parent,
+    Dir,
+    File,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerEntry {
+    pub name: String,
+    pub kind: EntryKind,
+    pub is_image: bool,
+}
+
+/// A directory browser with a fuzzy filter, opened over the task input screen.
+#[derive(Debug, Clone)]
+pub struct PathPicker {
+    pub target: PathTarget,
+    pub dir: PathBuf,
+    pub filter: String,
+    pub selected: usize,
+    entries: Vec<PickerEntry>,
+    pub error: Option<String>,
+}
+
+impl PathPicker {
+    /// Opens at `current` if it is a folder, at its parent if it is a file,
+    /// else at the nearest existing ancestor or the working directory.
+    pub fn open(target: PathTarget, current: &str) -> Self {
+        let start = Path::new(current.trim())
+            .ancestors()
+            .find(|path| path.is_dir() && !path.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let mut picker = Self {
+            target,
+            dir: PathBuf::new(),
+            filter: String::new(),
+            selected: 0,
+            entries: Vec::new(),
+            error: None,
+        };
+        picker.change_dir(start);
+        picker
+    }
+
+    pub fn change_dir(&mut self, dir: PathBuf) {
+        self.dir = fs::canonicalize(&dir).unwrap_or(dir);
+        self.filter.clear();
+        self.selected = 0;
+        self.error = None;
+        self.entries = match read_entries(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.error = Some(err);
+                Vec::new()
+            }
+        };
+        if self.dir.parent().is_some() {
+            self.entries.insert(0, entry("..", EntryKind::Parent, false));
+        }
+        if self.target == PathTarget::DatasetFolder {
+            self.entries.insert(0, entry(".", EntryKind::Current, false));
+        }
+    }
+
+    /// Entries matching the filter, in listing order.
+    pub fn visible(&self) -> Vec<&PickerEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| fuzzy_match(&entry.name, &self.filter))
+            .collect()
+    }
+
+    pub fn selected_entry(&self) -> Option<&PickerEntry> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn select_next(&mut self) {
+        let max = self.visible().len().saturating_sub(1);
+        self.selected = (self.selected + 1).min(max);
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn push_filter(&mut self, ch: char) {
+        self.filter.push(ch);
+        self.selected = 0;
+    }
+
+    /// Removes a filter character, or goes up a folder when the filter is empty.
+    pub fn pop_filter(&mut self) {
+        if self.filter.pop().is_none()
+            && let Some(parent) = self.dir.parent()
+        {
+            self.change_dir(parent.to_path_buf());
+        }
+        self.selected = 0;
+    }
+
+    /// Completes the filter like a shell: a single matching folder is entered,
+    /// several matches extend the filter to their common prefix.
+    pub fn complete(&mut self) {
+        let names = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry.kind, EntryKind::Dir | EntryKind::File))
+            .filter(|entry| entry.name.starts_with(&self.filter))
+            .map(|entry| (entry.name.clone(), entry.kind))
+            .collect::<Vec<_>>();
+        match names.as_slice() {
+            [] => {}
+            [(name, EntryKind::Dir)] => self.change_dir(self.dir.join(name)),
+            [(name, _)] => self.filter = name.clone(),
+            [(first, _), rest @ ..] => {
+                let common = rest.iter().fold(first.as_str(), |common, (name, _)| {
+                    let len = common
+                        .chars()
+                        .zip(name.chars())
+                        .take_while(|(a, b)| a == b)
+                        .map(|(a, _)| a.len_utf8())
+                        .sum();
+                    &common[..len]
+                });
+                self.filter = common.to_string();
+            }
+        }
+        self.selected = 0;
+    }
+
+    /// Acts on the selected entry: folders are entered, and the chosen path is
+    /// returned once a valid one for the target is confirmed.
+    pub fn confirm(&mut self) -> Option<String> {
+        let entry = self.selected_entry()?.clone();
+        match (entry.kind, self.target) {
+            (EntryKind::Current, _) => Some(self.dir.to_string_lossy().to_string()),
+            (EntryKind::Parent, _) => {
+                if let Some(parent) = self.dir.parent() {
+                    self.change_dir(parent.to_path_buf());
+                }
+                None
+            }
+            (EntryKind::Dir, _) => {
+                self.change_dir(self.dir.join(&entry.name));
+                None
+            }
+            (EntryKind::File, PathTarget::Image) => {
+                Some(self.dir.join(&entry.name).to_string_lossy().to_string())
+            }
+            (EntryKind::File, PathTarget::DatasetFolder) => None,
+        }
+    }
+}
+
+fn entry(name: &str, kind: EntryKind, is_image: bool) -> PickerEntry {
+    PickerEntry {
+        name: name.to_string(),
+        kind,
+        is_image,
+    }
+}
+
+/// Folders first, then files, each sorted by name; hidden entries are skipped.
+fn read_entries(dir: &Path) -> Result<Vec<PickerEntry>, String> {
+    let mut entries = fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter_map(|item| {
+            let name = item.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                return None;
+            }
+            let path = item.path();
+            Some(if path.is_dir() {
+                entry(&name, EntryKind::Dir, false)
+            } else {
+                entry(&name, EntryKind::File, is_image_file(&path))
+            })
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| {
+        (a.kind != EntryKind::Dir, &a.name).cmp(&(b.kind != EntryKind::Dir, &b.name))
+    });
+    Ok(entries)
+}
+
+/// Case-insensitive subsequence match: `nim` matches `new_image.png`.
+pub fn fuzzy_match(text: &str, pattern: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|wanted| chars.any(|ch| ch == wanted))
+}
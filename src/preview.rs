@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::Path;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use crossterm::queue;
+use crossterm::style::Print;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Decoded thumbnails kept before the cache is emptied.
+const CACHE_LIMIT: usize = 32;
+/// Base64 bytes per kitty graphics escape.
+const KITTY_CHUNK: usize = 4096;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewProtocol {
+    /// Picks kitty or sixel from the environment, else half-blocks.
+    Auto,
+    /// Two pixels per cell drawn with `▀`; works in any truecolor terminal.
+    #[default]
+    HalfBlocks,
+    Sixel,
+    Kitty,
+}
+
+impl PreviewProtocol {
+    /// Replaces `Auto` with the protocol the current terminal supports.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => {
+                let var = |name| std::env::var(name).unwrap_or_default();
+                Self::detect(
+                    &var("TERM"),
+                    &var("TERM_PROGRAM"),
+                    std::env::var_os("KITTY_WINDOW_ID").is_some(),
+                )
+            }
+            other => other,
+        }
+    }
+
+    pub fn detect(term: &str, term_program: &str, kitty_window: bool) -> Self {
+        if kitty_window || term.contains("kitty") || matches!(term_program, "WezTerm" | "ghostty") {
+            Self::Kitty
+        } else if term.contains("sixel") || term.starts_with("foot") || term == "mlterm" {
+            Self::Sixel
+        } else {
+            Self::HalfBlocks
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major pixels.
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Thumbnail {
+    /// Decodes `path` and scales it to fit `max_width`×`max_height` pixels,
+    /// keeping the aspect ratio.
+    pub fn load(path: &Path, max_width: u32, max_height: u32) -> Result<Self, String> {
+        let image = image::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let rgb = image
+            .thumbnail(max_width.max(1), max_height.max(1))
+            .to_rgb8();
+        Ok(Self {
+            width: rgb.width(),
+            height: rgb.height(),
+            pixels: rgb.pixels().map(|pixel| pixel.0).collect(),
+        })
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    /// Cells covered when drawn with `cell` pixels per cell.
+    pub fn cells(&self, cell: (u16, u16)) -> (u16, u16) {
+        let (cell_w, cell_h) = (cell.0.max(1) as u32, cell.1.max(1) as u32);
+        (
+            self.width.div_ceil(cell_w) as u16,
+            self.height.div_ceil(cell_h) as u16,
+        )
+    }
+
+    /// Draws the thumbnail at the top-left of `area`, one `▀` per two pixel rows.
+    pub fn render_half_blocks(&self, area: Rect, buf: &mut Buffer) {
+        let (cols, rows) = self.cells((1, 2));
+        for row in 0..rows.min(area.height) {
+            for col in 0..cols.min(area.width) {
+                let (x, y) = (col as u32, row as u32 * 2);
+                let [r, g, b] = self.pixel(x, y);
+                let cell = buf.get_mut(area.x + col, area.y + row);
+                cell.set_symbol("▀").set_fg(Color::Rgb(r, g, b));
+                if y + 1 < self.height {
+                    let [r, g, b] = self.pixel(x, y + 1);
+                    cell.set_bg(Color::Rgb(r, g, b));
+                }
+            }
+        }
+    }
+
+    /// Encodes the thumbnail as a DEC sixel image using a 6×6×6 color cube.
+    pub fn sixel(&self) -> String {
+        let level = |value: u8| (value as u16 * 5 + 127) / 255;
+        let indexes = self
+            .pixels
+            .iter()
+            .map(|[r, g, b]| (level(*r) * 36 + level(*g) * 6 + level(*b)) as usize)
+            .collect::<Vec<_>>();
+        let mut used = [false; 216];
+        for &index in &indexes {
+            used[index] = true;
+        }
+
+        let mut out = format!("\x1bPq\"1;1;{};{}", self.width, self.height);
+        for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+            let percent = |level: usize| level * 100 / 5;
+            let _ = write!(
+                out,
+                "#{index};2;{};{};{}",
+                percent(index / 36),
+                percent(index / 6 % 6),
+                percent(index % 6)
+            );
+        }
+        for band in (0..self.height).step_by(6) {
+            let rows = band..(band + 6).min(self.height);
+            let mut colors = rows
+                .clone()
+                .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+                .map(|(x, y)| indexes[(y * self.width + x) as usize])
+                .collect::<Vec<_>>();
+            colors.sort_unstable();
+            colors.dedup();
+            for color in colors {
+                let _ = write!(out, "#{color}");
+                let sixels = (0..self.width).map(|x| {
+                    let bits = rows
+                        .clone()
+                        .filter(|&y| indexes[(y * self.width + x) as usize] == color)
+                        .fold(0u8, |bits, y| bits | 1 << (y - band));
+                    (63 + bits) as char
+                });
+                push_run_length(&mut out, sixels);
+                out.push('$');
+            }
+            out.push('-');
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+
+    /// Encodes the thumbnail as kitty graphics escapes scaled to `cols`×`rows` cells.
+    pub fn kitty(&self, cols: u16, rows: u16) -> String {
+        let bytes = self.pixels.iter().flatten().copied().collect::<Vec<_>>();
+        let payload = BASE64.encode(bytes);
+        let chunks = payload.as_bytes().chunks(KITTY_CHUNK).collect::<Vec<_>>();
+        let mut out = String::new();
+        for (idx, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(idx + 1 < chunks.len());
+            let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+            if idx == 0 {
+                let _ = write!(
+                    out,
+                    "\x1b_Ga=T,f=24,s={},v={},c={cols},r={rows},C=1,q=2,m={more};{chunk}\x1b\\",
+                    self.width, self.height
+                );
+            } else {
+                let _ = write!(out, "\x1b_Gm={more};{chunk}\x1b\\");
+            }
+        }
+        out
+    }
+}
+
+/// Appends sixel characters, collapsing runs of four or more into `!n`.
+fn push_run_length(out: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |out: &mut String, (ch, count): (char, usize)| {
+        if count > 3 {
+            let _ = write!(out, "!{count}{ch}");
+        } else {
+            out.extend(std::iter::repeat_n(ch, count));
+        }
+    };
+    for ch in sixels {
+        run = match run {
+            Some((last, count)) if last == ch => Some((last, count + 1)),
+            Some(previous) => {
+                flush(out, previous);
+                Some((ch, 1))
+            }
+            None => Some((ch, 1)),
+        };
+    }
+    if let Some(previous) = run {
+        flush(out, previous);
+    }
+}
+
+/// An image drawn with a graphics protocol in the last frame.
+#[derive(Clone, Debug)]
+struct Placement {
+    path: String,
+    area: Rect,
+    thumbnail: Rc<Thumbnail>,
+}
+
+impl PartialEq for Placement {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.area == other.area
+    }
+}
+
+/// Graphics escapes to write after a frame whose previews changed.
+#[derive(Debug)]
+pub struct Graphics {
+    pub protocol: PreviewProtocol,
+    /// Sixel output cannot be erased, so the screen must be redrawn before writing.
+    pub redraw: bool,
+    placements: Vec<Placement>,
+}
+
+impl Graphics {
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.protocol == PreviewProtocol::Kitty {
+            queue!(out, Print("\x1b_Ga=d,d=A,q=2\x1b\\"))?;
+        }
+        for placement in &self.placements {
+            let escape = match self.protocol {
+                PreviewProtocol::Kitty => placement
+                    .thumbnail
+                    .kitty(placement.area.width, placement.area.height),
+                _ => placement.thumbnail.sixel(),
+            };
+            queue!(
+                out,
+                SavePosition,
+                MoveTo(placement.area.x, placement.area.y),
+                Print(escape),
+                RestorePosition
+            )?;
+        }
+        out.flush()
+    }
+}
+
+/// Path and maximum pixel size of a cached thumbnail.
+type ThumbnailKey = (String, u32, u32);
+
+/// Image previews shared by the screens: a thumbnail cache plus the graphics
+/// placements of the current frame. Drawing only has `&AppState`, hence the cells.
+#[derive(Debug)]
+pub struct ImagePreviews {
+    pub protocol: PreviewProtocol,
+    /// Pixels per terminal cell, used to size sixel and kitty images.
+    pub cell_size: (u16, u16),
+    cache: RefCell<HashMap<ThumbnailKey, Result<Rc<Thumbnail>, String>>>,
+    frame: RefCell<Vec<Placement>>,
+    shown: RefCell<Vec<Placement>>,
+}
+
+impl ImagePreviews {
+    pub fn new(protocol: PreviewProtocol) -> Self {
+        Self {
+            protocol: protocol.resolve(),
+            cell_size: (8, 16),
+            cache: RefCell::new(HashMap::new()),
+            frame: RefCell::new(Vec::new()),
+            shown: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Loads a thumbnail fitting `max_width`×`max_height` pixels, decoding each size once.
+    pub fn thumbnail(
+        &self,
+        path: &str,
+        max_width: u32,
+        max_height: u32,
+    ) -> Result<Rc<Thumbnail>, String> {
+        let mut cache = self.cache.borrow_mut();
+        let key = (path.to_string(), max_width, max_height);
+        if let Some(cached) = cache.get(&key) {
+            return cached.clone();
+        }
+        if cache.len() >= CACHE_LIMIT {
+            cache.clear();
+        }
+        let loaded = Thumbnail::load(Path::new(path), max_width, max_height).map(Rc::new);
+        cache.insert(key, loaded.clone());
+        loaded
+    }
+
+    /// Draws `path` centered in `area`. Half-blocks go straight into the buffer;
+    /// sixel and kitty images are queued for `take_graphics`.
+    pub fn render(&self, path: &str, area: Rect, buf: &mut Buffer) -> Result<(), String> {
+        if area.width == 0 || area.height == 0 {
+            return Ok(());
+        }
+        let cell = match self.protocol {
+            PreviewProtocol::HalfBlocks | PreviewProtocol::Auto => (1, 2),
+            PreviewProtocol::Sixel | PreviewProtocol::Kitty => self.cell_size,
+        };
+        let thumbnail = self.thumbnail(
+            path,
+            area.width as u32 * cell.0 as u32,
+            area.height as u32 * cell.1 as u32,
+        )?;
+        let (cols, rows) = thumbnail.cells(cell);
+        let (cols, rows) = (cols.min(area.width), rows.min(area.height));
+        let target = Rect::new(
+            area.x + (area.width - cols) / 2,
+            area.y + (area.height - rows) / 2,
+            cols,
+            rows,
+        );
+        match self.protocol {
+            PreviewProtocol::HalfBlocks | PreviewProtocol::Auto => {
+                thumbnail.render_half_blocks(target, buf)
+            }
+            PreviewProtocol::Sixel | PreviewProtocol::Kitty => {
+                self.frame.borrow_mut().push(Placement {
+                    path: path.to_string(),
+                    area: target,
+                    thumbnail,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Forgets what is on screen so the next `take_graphics` writes every
+    /// placement again, e.g. after a resize wiped the terminal.
+    pub fn invalidate(&self) {
+        self.shown.borrow_mut().clear();
+    }
+
+    /// Takes the placements queued by the last draw; `None` when nothing changed
+    /// since the graphics were last written.
+    pub fn take_graphics(&self) -> Option<Graphics> {
+        let placements = self.frame.take();
+        let mut shown = self.shown.borrow_mut();
+        if *shown == placements {
+            return None;
+        }
+        let redraw = self.protocol == PreviewProtocol::Sixel && !shown.is_empty();
+        *shown = placements.clone();
+        Some(Graphics {
+            protocol: self.protocol,
+            redraw,
+            placements,
+        })
+    }
+}
@@ -1,11 +1,26 @@
 use serde::{Deserialize, Serialize};
 
 use crate::engine::scheduler::TaskUpdate;
+use crate::report::ReportFormat;
+use crate::spec::TaskSpec;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum UiToEngine {
     AddTask { name: String },
+    AddTaskFromSpec { spec: TaskSpec },
     CancelTask { id: usize },
+    /// Starts a new task from the definition of task `id`.
+    RetryTask { id: usize },
+    /// Writes one more report of a finished task in `format`.
+    ExportReport { id: usize, format: ReportFormat },
+    /// Adds a user-written hypothesis to the task's next iteration.
+    InjectHypothesis { task_id: usize, text: String },
+    /// Keeps a hypothesis in synthesis regardless of its score.
+    PinHypothesis { task_id: usize, hypothesis_id: usize },
+    /// Excludes a hypothesis, and repeats of it, from synthesis.
+    BanHypothesis { task_id: usize, hypothesis_id: usize },
+    /// Drops an earlier pin or ban.
+    ReleaseHypothesis { task_id: usize, hypothesis_id: usize },
     Shutdown,
 }
 
@@ -0,0 +1,329 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use image::codecs::jpeg::JpegEncoder;
+
+use crate::task::{Hypothesis, ImageItem, IterationRecord, Prediction, TaskDefinition};
+
+use super::{write_report, TaskOutcome};
+
+/// Sample cards shown in the report; larger datasets are truncated.
+pub const MAX_SAMPLES: usize = 48;
+/// Longest side of an embedded thumbnail, in pixels.
+pub const THUMBNAIL_SIZE: u32 = 160;
+
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:72rem;color:#222;padding:0 1rem}
+h1{margin-bottom:.2rem}.muted{color:#777}
+table{border-collapse:collapse;width:100%;margin:.5rem 0}
+td,th{border-bottom:1px solid #ddd;padding:.3rem .5rem;text-align:left;vertical-align:top}
+.stats{display:flex;gap:1rem;flex-wrap:wrap}
+.stat{background:#f4f4f4;border-radius:6px;padding:.6rem 1rem}
+.stat b{display:block;font-size:1.4rem}
+.grid{display:grid;grid-template-columns:repeat(auto-fill,minmax(9rem,1fr));gap:.6rem}
+.card{border:2px solid #ddd;border-radius:6px;padding:.3rem;font-size:.8rem;overflow-wrap:anywhere}
+.card.ok{border-color:#2e9e44}.card.bad{border-color:#d0342c}
+.card img,.card .placeholder{width:100%;height:7rem;object-fit:cover;border-radius:4px}
+.card .placeholder{background:#eee;display:flex;align-items:center;justify-content:center;color:#999}
+.ok .predicted{color:#2e9e44}.bad .predicted{color:#d0342c}
+details{margin:.6rem 0}summary{cursor:pointer;font-weight:bold}
+pre{background:#f4f4f4;padding:.8rem;border-radius:6px;overflow-x:auto}
+";
+
+/// Renders a single self-contained HTML page: thumbnails are inlined as data
+/// URIs and the score chart is inline SVG, so the file can be shared as is.
+pub fn render_html_report(definition: &TaskDefinition, outcome: &TaskOutcome) -> String {
+    let mut out = String::new();
+    let title = format!("Task {} — {}", definition.id, escape(&definition.name));
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    let _ = writeln!(
+        out,
+        "<p class=\"muted\">Dataset <code>{}</code> · {} images · model {}</p>",
+        escape(&definition.dataset_folder),
+        definition.dataset.len(),
+        escape(&definition.llm.model)
+    );
+
+    out.push_str("<div class=\"stats\">\n");
+    stat(&mut out, "Best score", &format!("{:.3}", outcome.best_score));
+    if let Some(accuracy) = outcome.accuracy() {
+        stat(&mut out, "Accuracy", &format!("{:.1}%", accuracy * 100.0));
+    }
+    stat(&mut out, "Verified", &outcome.verified.len().to_string());
+    stat(&mut out, "Discarded", &outcome.discarded.len().to_string());
+    stat(&mut out, "Iterations", &outcome.iterations.len().to_string());
+    out.push_str("</div>\n");
+
+    out.push_str("<h2>Definition</h2>\n<table>\n");
+    let labels = if definition.labels.is_empty() {
+        "(none)".to_string()
+    } else {
+        definition.labels.join(", ")
+    };
+    row(&mut out, "Labels", &escape(&labels));
+    row(
+        &mut out,
+        "Heuristics",
+        &format!(
+            "edge threshold {:.2}, min blob area {}, contrast boost {:.2}",
+            definition.heuristics.edge_threshold,
+            definition.heuristics.min_blob_area,
+            definition.heuristics.contrast_boost
+        ),
+    );
+    for hint in &definition.hints {
+        row(
+            &mut out,
+            "Hint",
+            &format!("{} ({} images)", escape(&hint.title), hint.images.len()),
+        );
+    }
+    for seed in &definition.seed_hypotheses {
+        row(&mut out, "Seed hypothesis", &escape(seed));
+    }
+    row(
+        &mut out,
+        "Budget",
+        &format!(
+            "{} iterations, patience {}, verify threshold {:.2}",
+            definition.max_iters, definition.patience, definition.verify_threshold
+        ),
+    );
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>Scores per iteration</h2>\n");
+    out.push_str(&score_chart(&outcome.iterations, definition.verify_threshold));
+    iteration_table(&mut out, &outcome.iterations);
+
+    let heading = if outcome.predictions.is_empty() {
+        "Samples"
+    } else {
+        "Predictions of the best program"
+    };
+    let _ = writeln!(out, "<h2>{heading}</h2>");
+    sample_grid(&mut out, &definition.dataset, &outcome.predictions);
+
+    hypothesis_section(&mut out, "Verified hypotheses", &outcome.verified, true);
+    hypothesis_section(&mut out, "Discarded hypotheses", &outcome.discarded, false);
+
+    let _ = write!(
+        out,
+        "<h2>Synthesized program</h2>\n<pre><code>{}</code></pre>\n</body>\n</html>\n",
+        escape(&outcome.program)
+    );
+    out
+}
+
+pub fn generate_html_report(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    reports_dir: &Path,
+) -> Result<String, String> {
+    let report = render_html_report(definition, outcome);
+    write_report(reports_dir, definition, "html", &report)
+}
+
+fn stat(out: &mut String, label: &str, value: &str) {
+    let _ = writeln!(out, "<div class=\"stat\">{label}<b>{value}</b></div>");
+}
+
+fn row(out: &mut String, key: &str, value: &str) {
+    let _ = writeln!(out, "<tr><th>{key}</th><td>{value}</td></tr>");
+}
+
+fn sample_grid(out: &mut String, dataset: &[ImageItem], predictions: &[Prediction]) {
+    if dataset.is_empty() {
+        out.push_str("<p class=\"muted\">No images.</p>\n");
+        return;
+    }
+    out.push_str("<div class=\"grid\">\n");
+    for image in dataset.iter().take(MAX_SAMPLES) {
+        let prediction = predictions.iter().find(|p| p.image_id == image.id);
+        let class = match prediction.and_then(Prediction::is_correct) {
+            Some(true) => "card ok",
+            Some(false) => "card bad",
+            None => "card",
+        };
+        let thumbnail = match image.path.as_deref().and_then(|path| thumbnail_uri(Path::new(path)))
+        {
+            Some(uri) => format!("<img src=\"{uri}\" alt=\"{}\">", escape(&image.name)),
+            None => "<div class=\"placeholder\">no preview</div>".to_string(),
+        };
+        let _ = write!(out, "<div class=\"{class}\">{thumbnail}<div>{}</div>", escape(&image.name));
+        if let Some(prediction) = prediction {
+            let _ = write!(
+                out,
+                "<div class=\"predicted\">predicted: {}</div>",
+                escape(&prediction.predicted)
+            );
+        }
+        if let Some(label) = &image.label {
+            let _ = write!(out, "<div>expected: {}</div>", escape(label));
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</div>\n");
+    if dataset.len() > MAX_SAMPLES {
+        let _ = writeln!(
+            out,
+            "<p class=\"muted\">Showing {MAX_SAMPLES} of {} images.</p>",
+            dataset.len()
+        );
+    }
+}
+
+fn iteration_table(out: &mut String, iterations: &[IterationRecord]) {
+    if iterations.is_empty() {
+        return;
+    }
+    out.push_str(
+        "<table>\n<tr><th>Iteration</th><th>Generated</th><th>Verified</th><th>Best score</th>\
+         <th>Iteration score</th><th>Program</th><th>Elapsed</th></tr>\n",
+    );
+    for record in iterations {
+        let program = record
+            .program_version
+            .map(|version| format!("v{version}"))
+            .unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.3}</td><td>{:.3}</td><td>{program}</td>\
+             <td>{} ms</td></tr>",
+            record.iteration,
+            record.generated,
+            record.verified,
+            record.best_score,
+            record.last_score,
+            record.elapsed_ms
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+fn hypothesis_section(out: &mut String, title: &str, hypotheses: &[Hypothesis], open: bool) {
+    let _ = writeln!(
+        out,
+        "<details{}>\n<summary>{title} ({})</summary>",
+        if open { " open" } else { "" },
+        hypotheses.len()
+    );
+    if hypotheses.is_empty() {
+        out.push_str("<p class=\"muted\">None.</p>\n</details>\n");
+        return;
+    }
+    let mut sorted = hypotheses.to_vec();
+    sorted.sort_by(|a, b| b.score.total_cmp(&a.score));
+    out.push_str("<table>\n<tr><th>Score</th><th>Hypothesis</th></tr>\n");
+    for hypothesis in &sorted {
+        let _ = writeln!(
+            out,
+            "<tr><td>{:.3}</td><td>{}</td></tr>",
+            hypothesis.score,
+            escape(&hypothesis.description)
+        );
+    }
+    out.push_str("</table>\n</details>\n");
+}
+
+/// Line chart of best and per-iteration scores with the verify threshold dashed.
+fn score_chart(iterations: &[IterationRecord], threshold: f32) -> String {
+    const WIDTH: f32 = 640.0;
+    const HEIGHT: f32 = 200.0;
+    const PAD: f32 = 30.0;
+    if iterations.is_empty() {
+        return "<p class=\"muted\">No iterations ran.</p>\n".to_string();
+    }
+    let step = (WIDTH - 2.0 * PAD) / (iterations.len().max(2) - 1) as f32;
+    let x = |idx: usize| PAD + idx as f32 * step;
+    let y = |score: f32| HEIGHT - PAD - score.clamp(0.0, 1.0) * (HEIGHT - 2.0 * PAD);
+    let points = |score: fn(&IterationRecord) -> f32| {
+        iterations
+            .iter()
+            .enumerate()
+            .map(|(idx, record)| format!("{:.1},{:.1}", x(idx), y(score(record))))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg viewBox=\"0 0 {WIDTH} {HEIGHT}\" width=\"{WIDTH}\" height=\"{HEIGHT}\" \
+         role=\"img\" aria-label=\"Scores per iteration\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{PAD}\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"#999\"/>\
+         <line x1=\"{PAD}\" y1=\"{PAD}\" x2=\"{PAD}\" y2=\"{0}\" stroke=\"#999\"/>",
+        HEIGHT - PAD,
+        WIDTH - PAD
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{PAD}\" y1=\"{0:.1}\" x2=\"{1}\" y2=\"{0:.1}\" stroke=\"#d0342c\" \
+         stroke-dasharray=\"4 4\"/><text x=\"{1}\" y=\"{2:.1}\" font-size=\"10\" \
+         text-anchor=\"end\" fill=\"#d0342c\">threshold {threshold:.2}</text>",
+        y(threshold),
+        WIDTH - PAD,
+        y(threshold) - 4.0
+    );
+    for (score, label) in [(0.0f32, "0"), (1.0, "1")] {
+        let _ = writeln!(
+            svg,
+            "<text x=\"{0}\" y=\"{1:.1}\" font-size=\"10\" text-anchor=\"end\">{label}</text>",
+            PAD - 4.0,
+            y(score) + 3.0
+        );
+    }
+    for (idx, record) in iterations.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\" font-size=\"10\" text-anchor=\"middle\">{}</text>",
+            x(idx),
+            HEIGHT - PAD + 14.0,
+            record.iteration
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#aaa\" stroke-width=\"1.5\"/>",
+        points(|record| record.last_score)
+    );
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#2a6fdb\" stroke-width=\"2.5\"/>",
+        points(|record| record.best_score)
+    );
+    svg.push_str("</svg>\n<p class=\"muted\">Blue: best score so far. Grey: best verified score of the iteration.</p>\n");
+    svg
+}
+
+/// Inlines a JPEG thumbnail of the image as a base64 data URI, skipping
+/// files that can't be decoded.
+fn thumbnail_uri(path: &Path) -> Option<String> {
+    let thumbnail = image::open(path).ok()?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, 80).encode_image(&thumbnail).ok()?;
+    Some(format!("data:image/jpeg;base64,{}", BASE64.encode(jpeg)))
+}
+
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            other => out.push(other),
+        }
+    }
+    out
+}
@@ -0,0 +1,163 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec::{BudgetSpec, HeuristicSpec, LlmSpec};
+use crate::task::{
+    Heuristics, ImageItem, ImageResult, IterationRecord, Prediction, TaskDefinition,
+};
+
+use super::{write_report, TaskOutcome};
+
+/// Bumped whenever a field is renamed or removed; new fields may be added
+/// without a bump, so consumers should ignore keys they do not know.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The complete result of a task, written as `task_<id>_run-<run>_<name>.json`.
+///
+/// Unlike the markdown report nothing is truncated: every image, every
+/// hypothesis and every iteration is included.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonReport {
+    pub schema_version: u32,
+    pub task: JsonTask,
+    pub metrics: JsonMetrics,
+    pub timings: JsonTimings,
+    pub program: String,
+    pub hypotheses: Vec<JsonHypothesis>,
+    pub iterations: Vec<IterationRecord>,
+    pub predictions: Vec<Prediction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonTask {
+    pub id: usize,
+    pub name: String,
+    pub dataset_folder: String,
+    pub dataset: Vec<ImageItem>,
+    pub labels: Vec<String>,
+    pub heuristics: Heuristics,
+    pub hints: Vec<HeuristicSpec>,
+    pub seed_hypotheses: Vec<String>,
+    pub budget: BudgetSpec,
+    pub llm: LlmSpec,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonMetrics {
+    pub best_score: f32,
+    /// Share of labelled images the best program predicts correctly; null
+    /// when the dataset has no labels or no program was verified.
+    pub accuracy: Option<f32>,
+    pub hypotheses_total: usize,
+    pub verified_count: usize,
+    pub discarded_count: usize,
+    pub iterations_run: usize,
+}
+
+/// Wall-clock times in milliseconds since the Unix epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonTimings {
+    pub created_unix_ms: u64,
+    pub started_unix_ms: u64,
+    pub finished_unix_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonHypothesis {
+    pub id: usize,
+    pub iteration: usize,
+    pub description: String,
+    pub rationale: String,
+    pub score: f32,
+    pub verified: bool,
+    pub results: Vec<ImageResult>,
+}
+
+impl JsonReport {
+    pub fn new(definition: &TaskDefinition, outcome: &TaskOutcome) -> Self {
+        let mut hypotheses = outcome
+            .verified
+            .iter()
+            .map(|hypothesis| (hypothesis, true))
+            .chain(outcome.discarded.iter().map(|hypothesis| (hypothesis, false)))
+            .map(|(hypothesis, verified)| JsonHypothesis {
+                id: hypothesis.id,
+                iteration: hypothesis.iteration,
+                description: hypothesis.description.clone(),
+                rationale: hypothesis.rationale.clone(),
+                score: hypothesis.score,
+                verified,
+                results: hypothesis.results.clone(),
+            })
+            .collect::<Vec<_>>();
+        hypotheses.sort_by_key(|hypothesis| hypothesis.id);
+        Self {
+            schema_version: SCHEMA_VERSION,
+            task: JsonTask {
+                id: definition.id,
+                name: definition.name.clone(),
+                dataset_folder: definition.dataset_folder.clone(),
+                dataset: definition.dataset.clone(),
+                labels: definition.labels.clone(),
+                heuristics: definition.heuristics.clone(),
+                hints: definition.hints.clone(),
+                seed_hypotheses: definition.seed_hypotheses.clone(),
+                budget: BudgetSpec {
+                    max_iters: definition.max_iters,
+                    patience: definition.patience,
+                    verify_threshold: definition.verify_threshold,
+                },
+                llm: definition.llm.clone(),
+            },
+            metrics: JsonMetrics {
+                best_score: outcome.best_score,
+                accuracy: outcome.accuracy(),
+                hypotheses_total: hypotheses.len(),
+                verified_count: outcome.verified.len(),
+                discarded_count: outcome.discarded.len(),
+                iterations_run: outcome.iterations.len(),
+            },
+            timings: JsonTimings {
+                created_unix_ms: unix_ms(definition.created_at),
+                started_unix_ms: unix_ms(outcome.started_at),
+                finished_unix_ms: unix_ms(outcome.finished_at),
+                duration_ms: outcome
+                    .finished_at
+                    .duration_since(outcome.started_at)
+                    .map(|duration| duration.as_millis() as u64)
+                    .unwrap_or(0),
+            },
+            program: outcome.program.clone(),
+            hypotheses,
+            iterations: outcome.iterations.clone(),
+            predictions: outcome.predictions.clone(),
+        }
+    }
+}
+
+pub fn render_json_report(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+) -> Result<String, String> {
+    serde_json::to_string_pretty(&JsonReport::new(definition, outcome))
+        .map(|json| json + "\n")
+        .map_err(|e| e.to_string())
+}
+
+pub fn generate_json_report(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    reports_dir: &Path,
+) -> Result<String, String> {
+    let report = render_json_report(definition, outcome)?;
+    write_report(reports_dir, definition, "json", &report)
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,297 @@
+pub mod html;
+pub mod json;
+pub mod template;
+
+use std::fs::{self, create_dir_all, read_dir, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::task::{Hypothesis, IterationRecord, Prediction, TaskDefinition};
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("templates/default.md");
+
+/// The index of other report directories, kept in the configured one.
+const REPORT_DIRS_FILE: &str = "report_dirs";
+
+/// Precedes the run in report names; sanitized task names never contain `-`,
+/// so names written before runs existed can't be mistaken for one.
+const RUN_MARKER: &str = "run-";
+
+#[derive(Clone, Debug)]
+pub struct ReportEntry {
+    pub task_id: usize,
+    /// The run that wrote it, see [`run_id`]; 0 for reports named before runs were.
+    pub run: u64,
+    pub path: PathBuf,
+}
+
+/// What a finished run produced; the input to every report format.
+#[derive(Clone, Debug)]
+pub struct TaskOutcome {
+    pub verified: Vec<Hypothesis>,
+    pub discarded: Vec<Hypothesis>,
+    pub program: String,
+    pub best_score: f32,
+    /// Per-image labels from the best program; empty until a program verified.
+    pub predictions: Vec<Prediction>,
+    pub iterations: Vec<IterationRecord>,
+    pub started_at: SystemTime,
+    pub finished_at: SystemTime,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [ReportFormat::Markdown, ReportFormat::Html, ReportFormat::Json];
+
+    /// The name used in config files and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+
+    /// Parses a format name or file extension, ignoring case.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == value || format.extension() == value)
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+            ReportFormat::Json => "json",
+        }
+    }
+}
+
+impl TaskOutcome {
+    /// Share of predictions matching a known label, if any image has one.
+    pub fn accuracy(&self) -> Option<f32> {
+        let graded = self
+            .predictions
+            .iter()
+            .filter_map(Prediction::is_correct)
+            .collect::<Vec<_>>();
+        if graded.is_empty() {
+            return None;
+        }
+        let correct = graded.iter().filter(|correct| **correct).count();
+        Some(correct as f32 / graded.len() as f32)
+    }
+}
+
+/// Reads the template at `path`, or returns the built-in layout when there is none.
+pub fn load_template(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("cannot read report template {path}: {e}")),
+        None => Ok(DEFAULT_TEMPLATE.to_string()),
+    }
+}
+
+pub fn report_context(definition: &TaskDefinition, outcome: &TaskOutcome) -> Value {
+    let created_unix = definition
+        .created_at
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs());
+    json!({
+        "task": {
+            "id": definition.id,
+            "name": definition.name,
+            "created_unix": created_unix,
+            "dataset_folder": definition.dataset_folder,
+            "dataset_size": definition.dataset.len(),
+            "dataset": definition.dataset,
+            "labels": definition.labels,
+            "heuristics": definition.heuristics,
+            "hints": definition.hints,
+            "seed_hypotheses": definition.seed_hypotheses,
+            "max_iters": definition.max_iters,
+            "llm_model": definition.llm.model,
+        },
+        "summary": {
+            "best_score": outcome.best_score,
+            "verified_count": outcome.verified.len(),
+            "discarded_count": outcome.discarded.len(),
+            "accuracy": outcome.accuracy(),
+        },
+        "verified": outcome.verified,
+        "discarded": outcome.discarded,
+        "program": outcome.program,
+        "predictions": outcome.predictions,
+        "iterations": outcome.iterations,
+    })
+}
+
+pub fn render_markdown_report(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    template: &str,
+) -> Result<String, String> {
+    template::render(template, &report_context(definition, outcome))
+        .map_err(|e| format!("report template: {e}"))
+}
+
+pub fn generate_markdown_report(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    reports_dir: &Path,
+    template: &str,
+) -> Result<String, String> {
+    let report = render_markdown_report(definition, outcome, template)?;
+    write_report(reports_dir, definition, "md", &report)
+}
+
+/// Writes one report per format and returns their paths in the same order.
+/// `template` only applies to the markdown report.
+pub fn generate_reports(
+    definition: &TaskDefinition,
+    outcome: &TaskOutcome,
+    reports_dir: &Path,
+    template: &str,
+    formats: &[ReportFormat],
+) -> Result<Vec<String>, String> {
+    formats
+        .iter()
+        .map(|format| match format {
+            ReportFormat::Markdown => {
+                generate_markdown_report(definition, outcome, reports_dir, template)
+            }
+            ReportFormat::Html => html::generate_html_report(definition, outcome, reports_dir),
+            ReportFormat::Json => json::generate_json_report(definition, outcome, reports_dir),
+        })
+        .collect()
+}
+
+pub(crate) fn write_report(
+    reports_dir: &Path,
+    definition: &TaskDefinition,
+    extension: &str,
+    contents: &str,
+) -> Result<String, String> {
+    create_dir_all(reports_dir).map_err(|e| e.to_string())?;
+    let sanitized = sanitize_filename(&definition.name);
+    let path = reports_dir.join(format!(
+        "task_{}_{RUN_MARKER}{}_{sanitized}.{extension}",
+        definition.id,
+        run_id(definition)
+    ));
+    let mut file = File::create(&path).map_err(|e| e.to_string())?;
+    file.write_all(contents.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Tells runs of the same task id apart: ids restart with every process, so
+/// report names also carry when the run was created, in unix milliseconds.
+pub fn run_id(definition: &TaskDefinition) -> u64 {
+    definition
+        .created_at
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Records `dir` in the index kept in `reports_dir`, so `revolver list` and
+/// `revolver report` also find reports a spec wrote somewhere else.
+pub fn remember_report_dir(reports_dir: &Path, dir: &Path) -> io::Result<()> {
+    create_dir_all(reports_dir)?;
+    let dir = dir.canonicalize()?;
+    if dir == reports_dir.canonicalize()? {
+        return Ok(());
+    }
+    let index = reports_dir.join(REPORT_DIRS_FILE);
+    let known = match fs::read_to_string(&index) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    if known.lines().any(|line| Path::new(line) == dir) {
+        return Ok(());
+    }
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(index)?;
+    writeln!(file, "{}", dir.display())
+}
+
+/// `reports_dir` followed by the directories recorded by [`remember_report_dir`].
+pub fn report_dirs(reports_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut dirs = vec![reports_dir.to_path_buf()];
+    match fs::read_to_string(reports_dir.join(REPORT_DIRS_FILE)) {
+        Ok(text) => dirs.extend(text.lines().filter(|line| !line.is_empty()).map(PathBuf::from)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    Ok(dirs)
+}
+
+/// Lists markdown reports named `task_<id>_run-<run>_<name>.md` (or the older
+/// `task_<id>_<name>.md`) in `dir`, sorted by task id and run. A missing
+/// directory yields an empty list.
+pub fn list_reports(dir: impl AsRef<Path>) -> io::Result<Vec<ReportEntry>> {
+    let entries = match read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut reports = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let Some((task_id, run)) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(parse_report_stem)
+        else {
+            continue;
+        };
+        reports.push(ReportEntry { task_id, run, path });
+    }
+    reports.sort_by(|a, b| (a.task_id, a.run, &a.path).cmp(&(b.task_id, b.run, &b.path)));
+    Ok(reports)
+}
+
+fn parse_report_stem(stem: &str) -> Option<(usize, u64)> {
+    let (task_id, rest) = stem.strip_prefix("task_")?.split_once('_')?;
+    let task_id = task_id.parse().ok()?;
+    let run = rest
+        .strip_prefix(RUN_MARKER)
+        .and_then(|rest| rest.split_once('_'))
+        .and_then(|(run, _)| run.parse().ok())
+        .unwrap_or(0);
+    Some((task_id, run))
+}
+
+fn sanitize_filename(input: &str) -> String {
+    let mut out = String::new();
+    for ch in input.chars() {
+        if ch.is_ascii_alphanumeric() {
+            out.push(ch.to_ascii_lowercase());
+        } else if ch == ' ' || ch == '-' || ch == '_' {
+            out.push('_');
+        }
+    }
+    if out.is_empty() {
+        "task".to_string()
+    } else {
+        out
+    }
+}
@@ -0,0 +1,363 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+/// A small handlebars-like template language used for reports.
+///
+/// - `{{path.to.value}}` inserts a value; `{{score:.2}}` formats numbers with two decimals.
+///   Arrays are joined with `, `, missing values render empty.
+/// - `{{#each list limit=20 sort=-score}} ... {{else}} ... {{/each}}` repeats its body per
+///   item (`this` and the item's fields are in scope, plus `@index`/`@number`); `sort`
+///   orders by a field, `-` for descending; the `else` branch renders for empty lists.
+/// - `{{#if path}} ... {{else}} ... {{/if}}` treats null, false, "", [] and {} as false;
+///   numbers, 0 included, are always true.
+///
+/// Block tags alone on a line consume that line, so templates can be laid out readably.
+#[derive(Clone, Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Text(String),
+    Var {
+        path: String,
+        precision: Option<usize>,
+    },
+    Each {
+        path: String,
+        limit: Option<usize>,
+        sort: Option<(String, bool)>,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    If {
+        path: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum Token {
+    Text(String),
+    Tag(String),
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = trim_standalone(tokenize(source)?);
+        let mut iter = tokens.into_iter();
+        let (nodes, end) = parse_nodes(&mut iter)?;
+        match end {
+            None => Ok(Self { nodes }),
+            Some(tag) => Err(format!("unexpected {{{{{tag}}}}}")),
+        }
+    }
+
+    pub fn render(&self, context: &Value) -> String {
+        let mut out = String::new();
+        let mut scopes = vec![context.clone()];
+        render_nodes(&self.nodes, &mut scopes, &mut out);
+        out
+    }
+}
+
+pub fn render(source: &str, context: &Value) -> Result<String, String> {
+    Ok(Template::parse(source)?.render(context))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unclosed {{ in template".to_string())?;
+        tokens.push(Token::Tag(after[..end].trim().to_string()));
+        rest = &after[end + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+fn is_block_tag(tag: &str) -> bool {
+    tag.starts_with('#') || tag.starts_with('/') || tag == "else"
+}
+
+/// Drops the indentation and line break around block tags that sit alone on a line.
+fn trim_standalone(mut tokens: Vec<Token>) -> Vec<Token> {
+    fn text_at(tokens: &[Token], idx: Option<usize>) -> Option<&str> {
+        match idx.and_then(|i| tokens.get(i)) {
+            Some(Token::Text(text)) => Some(text.as_str()),
+            _ => None,
+        }
+    }
+    let mut cuts = Vec::new();
+    for (idx, token) in tokens.iter().enumerate() {
+        let Token::Tag(tag) = token else {
+            continue;
+        };
+        if !is_block_tag(tag) {
+            continue;
+        }
+        let line_start = match (idx, text_at(&tokens, idx.checked_sub(1))) {
+            (0, _) => Some(0),
+            (_, Some(text)) => match text.rfind('\n') {
+                Some(pos) if text[pos + 1..].trim().is_empty() => Some(pos + 1),
+                None if idx == 1 && text.trim().is_empty() => Some(0),
+                _ => None,
+            },
+            (_, None) => None,
+        };
+        let line_end = match (idx + 1 == tokens.len(), text_at(&tokens, Some(idx + 1))) {
+            (true, _) => Some(0),
+            (false, Some(text)) => match text.find('\n') {
+                Some(pos) if text[..pos].trim().is_empty() => Some(pos + 1),
+                None if idx + 2 == tokens.len() && text.trim().is_empty() => Some(text.len()),
+                _ => None,
+            },
+            (false, None) => None,
+        };
+        if let (Some(line_start), Some(line_end)) = (line_start, line_end) {
+            cuts.push((idx, line_start, line_end));
+        }
+    }
+    // Truncate before draining: a line break shared by two standalone tags is
+    // always cut after the second tag's line start.
+    for &(idx, line_start, _) in &cuts {
+        if let Some(Token::Text(text)) = idx.checked_sub(1).and_then(|prev| tokens.get_mut(prev)) {
+            text.truncate(line_start);
+        }
+    }
+    for &(idx, _, line_end) in &cuts {
+        if let Some(Token::Text(text)) = tokens.get_mut(idx + 1) {
+            text.drain(..line_end.min(text.len()));
+        }
+    }
+    tokens
+}
+
+fn parse_nodes<I>(tokens: &mut I) -> Result<(Vec<Node>, Option<String>), String>
+where
+    I: Iterator<Item = Token>,
+{
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+        if tag == "else" || tag.starts_with('/') {
+            return Ok((nodes, Some(tag)));
+        }
+        if let Some(args) = tag.strip_prefix("#each") {
+            let mut parts = args.split_whitespace();
+            let path = parts
+                .next()
+                .ok_or_else(|| "{{#each}} needs a list".to_string())?
+                .to_string();
+            let mut limit = None;
+            let mut sort = None;
+            for option in parts {
+                match option.split_once('=') {
+                    Some(("limit", value)) => {
+                        limit = Some(
+                            value
+                                .parse()
+                                .map_err(|_| format!("invalid limit in {{{{{tag}}}}}"))?,
+                        );
+                    }
+                    Some(("sort", value)) => {
+                        sort = Some(match value.strip_prefix('-') {
+                            Some(field) => (field.to_string(), true),
+                            None => (value.to_string(), false),
+                        });
+                    }
+                    _ => return Err(format!("unknown option '{option}' in {{{{{tag}}}}}")),
+                }
+            }
+            let (body, otherwise) = parse_block(tokens, "each")?;
+            nodes.push(Node::Each {
+                path,
+                limit,
+                sort,
+                body,
+                otherwise,
+            });
+        } else if let Some(path) = tag.strip_prefix("#if") {
+            let path = path.trim().to_string();
+            if path.is_empty() {
+                return Err("{{#if}} needs a value".to_string());
+            }
+            let (then, otherwise) = parse_block(tokens, "if")?;
+            nodes.push(Node::If {
+                path,
+                then,
+                otherwise,
+            });
+        } else if tag.starts_with('#') {
+            return Err(format!("unknown block {{{{{tag}}}}}"));
+        } else {
+            let (path, precision) = match tag.split_once(":.") {
+                Some((path, digits)) => (
+                    path.trim().to_string(),
+                    Some(
+                        digits
+                            .parse()
+                            .map_err(|_| format!("invalid precision in {{{{{tag}}}}}"))?,
+                    ),
+                ),
+                None => (tag, None),
+            };
+            nodes.push(Node::Var { path, precision });
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn parse_block<I>(tokens: &mut I, name: &str) -> Result<(Vec<Node>, Vec<Node>), String>
+where
+    I: Iterator<Item = Token>,
+{
+    let close = format!("/{name}");
+    let (body, end) = parse_nodes(tokens)?;
+    match end.as_deref() {
+        Some(tag) if tag == close => Ok((body, Vec::new())),
+        Some("else") => {
+            let (otherwise, end) = parse_nodes(tokens)?;
+            if end.as_deref() == Some(close.as_str()) {
+                Ok((body, otherwise))
+            } else {
+                Err(format!("missing {{{{{close}}}}}"))
+            }
+        }
+        _ => Err(format!("missing {{{{{close}}}}}")),
+    }
+}
+
+fn render_nodes(nodes: &[Node], scopes: &mut Vec<Value>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var { path, precision } => {
+                if let Some(value) = lookup(scopes, path) {
+                    out.push_str(&format_value(&value, *precision));
+                }
+            }
+            Node::Each {
+                path,
+                limit,
+                sort,
+                body,
+                otherwise,
+            } => {
+                let mut items = match lookup(scopes, path) {
+                    Some(Value::Array(items)) => items,
+                    _ => Vec::new(),
+                };
+                if let Some((field, descending)) = sort {
+                    items.sort_by(|a, b| {
+                        let ordering = compare(a.get(field), b.get(field));
+                        if *descending { ordering.reverse() } else { ordering }
+                    });
+                }
+                if let Some(limit) = limit {
+                    items.truncate(*limit);
+                }
+                if items.is_empty() {
+                    render_nodes(otherwise, scopes, out);
+                }
+                for (index, item) in items.into_iter().enumerate() {
+                    let mut scope = match &item {
+                        Value::Object(fields) => fields.clone(),
+                        _ => serde_json::Map::new(),
+                    };
+                    scope.insert("this".to_string(), item);
+                    scope.insert("@index".to_string(), Value::from(index));
+                    scope.insert("@number".to_string(), Value::from(index + 1));
+                    scopes.push(Value::Object(scope));
+                    render_nodes(body, scopes, out);
+                    scopes.pop();
+                }
+            }
+            Node::If {
+                path,
+                then,
+                otherwise,
+            } => {
+                if lookup(scopes, path).is_some_and(|value| truthy(&value)) {
+                    render_nodes(then, scopes, out);
+                } else {
+                    render_nodes(otherwise, scopes, out);
+                }
+            }
+        }
+    }
+}
+
+fn lookup(scopes: &[Value], path: &str) -> Option<Value> {
+    let mut segments = path.split('.');
+    let first = segments.next()?;
+    let mut value = scopes.iter().rev().find_map(|scope| scope.get(first))?;
+    for segment in segments {
+        value = match value {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            other => other.get(segment)?,
+        };
+    }
+    Some(value.clone())
+}
+
+/// Numbers are always true, so `{{#if}}` on an optional count or version
+/// only checks that it is set.
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::Number(_) => true,
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+fn format_value(value: &Value, precision: Option<usize>) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) => match (precision, number.as_f64()) {
+            (Some(digits), Some(n)) => format!("{n:.digits$}"),
+            _ => number.to_string(),
+        },
+        Value::Array(items) => items
+            .iter()
+            .map(|item| format_value(item, precision))
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn compare(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        _ => Ordering::Equal,
+    }
+}
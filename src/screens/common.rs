@@ -1,21 +1,82 @@
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
-pub fn draw_header(frame: &mut Frame, area: Rect) {
-    let text = Text::from(Line::from(Span::styled(
-        "Revolver",
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-    )));
-    let block = Block::default().borders(Borders::ALL).title("---");
+use crate::app::AppState;
+
+/// Below this width, panes that sit side by side are stacked instead.
+pub const NARROW_WIDTH: u16 = 100;
+/// Below this height the header and controls bar shrink to one borderless line.
+pub const SHORT_HEIGHT: u16 = 30;
+/// The smallest terminal the screens are laid out for.
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 20;
+
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+pub fn bar_height(area: Rect) -> u16 {
+    if area.height < SHORT_HEIGHT { 1 } else { 3 }
+}
+
+/// A bordered block, or none when `area` is a collapsed one-line bar.
+pub fn bar_block(area: Rect, title: &str) -> Block<'static> {
+    if area.height < 3 {
+        Block::default()
+    } else {
+        Block::default().borders(Borders::ALL).title(title.to_string())
+    }
+}
+
+pub fn draw_header(frame: &mut Frame, area: Rect, app: &AppState) {
+    let text = Text::from(Line::from(Span::styled("Revolver", app.theme.header())));
+    let block = bar_block(area, "---");
     frame.render_widget(Paragraph::new(text).alignment(Alignment::Center).block(block), area);
 }
 
-pub fn selected_list_style() -> Style {
-    Style::default()
-        .bg(Color::Green)
-        .fg(Color::Black)
-        .add_modifier(Modifier::BOLD)
+/// Shown instead of a screen when the terminal is below the minimum size.
+pub fn draw_too_small(frame: &mut Frame, area: Rect, app: &AppState) {
+    let lines = vec![
+        Line::from(Span::styled("Terminal too small", app.theme.popup_title())),
+        Line::from(format!(
+            "{}x{}, need at least {MIN_WIDTH}x{MIN_HEIGHT}",
+            area.width, area.height
+        )),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let area = Rect::new(area.x, area.y + top, area.width, area.height - top);
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Draws a bordered image preview of `path`, or `placeholder` in gray when
+/// there is no path or the image cannot be decoded.
+pub fn draw_image_preview(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    path: Option<&str>,
+    placeholder: &str,
+    app: &AppState,
+) {
+    let block = Block::default().borders(Borders::ALL).title(title.to_string());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let message = match path {
+        Some(path) => match app.previews.render(path, inner, frame.buffer_mut()) {
+            Ok(()) => return,
+            Err(err) => err,
+        },
+        None => placeholder.to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(message)
+            .style(app.theme.muted())
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
 }
@@ -0,0 +1,205 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::{AppEvent, AppState, DialogEvent, TextEditEvent};
+use crate::screens::keymap;
+use crate::text::TextBuffer;
+use crate::ui::dashed_border_set;
+
+/// Widest a dialog gets, border included.
+const MAX_WIDTH: u16 = 64;
+
+/// A modal popup drawn over any screen. While one is open it takes every key
+/// and click; dialogs opened on top of it wait below until it closes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dialog {
+    pub title: String,
+    pub message: String,
+    pub kind: DialogKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DialogKind {
+    /// Yes/no question; yes sends the event.
+    Confirm(Box<AppEvent>),
+    /// Asks for the last argument of `command` and runs the completed line.
+    Prompt { command: String, input: TextBuffer },
+    Message,
+    Error,
+}
+
+impl Dialog {
+    pub fn confirm(title: impl Into<String>, message: impl Into<String>, event: AppEvent) -> Self {
+        Self::new(title, message, DialogKind::Confirm(Box::new(event)))
+    }
+
+    pub fn prompt(
+        title: impl Into<String>,
+        message: impl Into<String>,
+        command: impl Into<String>,
+        input: impl Into<String>,
+    ) -> Self {
+        let kind = DialogKind::Prompt {
+            command: command.into(),
+            input: TextBuffer::new(input),
+        };
+        Self::new(title, message, kind)
+    }
+
+    pub fn message(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(title, message, DialogKind::Message)
+    }
+
+    pub fn error(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(title, message, DialogKind::Error)
+    }
+
+    fn new(title: impl Into<String>, message: impl Into<String>, kind: DialogKind) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            kind,
+        }
+    }
+
+    /// Button labels with the event each sends; the first is the default.
+    fn buttons(&self) -> Vec<(&'static str, DialogEvent)> {
+        match self.kind {
+            DialogKind::Confirm(_) => {
+                vec![("y Yes", DialogEvent::Confirm), ("n No", DialogEvent::Cancel)]
+            }
+            DialogKind::Prompt { .. } => vec![
+                ("Enter OK", DialogEvent::Confirm),
+                ("Esc Cancel", DialogEvent::Cancel),
+            ],
+            DialogKind::Message | DialogKind::Error => vec![("Enter OK", DialogEvent::Cancel)],
+        }
+    }
+}
+
+/// Keys of the topmost dialog: Enter/y confirm, Esc/n cancel, and a prompt
+/// edits its input with the rest.
+pub fn handle_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let Some(dialog) = app.dialogs.last() else {
+        return Ok(false);
+    };
+    let event = match (&dialog.kind, key.code) {
+        (_, KeyCode::Enter) => DialogEvent::Confirm,
+        (_, KeyCode::Esc) => DialogEvent::Cancel,
+        (DialogKind::Prompt { .. }, _) => match keymap::text_edit_event(&key) {
+            Some(edit) => DialogEvent::Edit(edit),
+            None => return Ok(false),
+        },
+        (DialogKind::Confirm(_), KeyCode::Char('y' | 'Y')) => DialogEvent::Confirm,
+        (DialogKind::Confirm(_), KeyCode::Char('n' | 'N' | 'q')) => DialogEvent::Cancel,
+        (DialogKind::Message | DialogKind::Error, KeyCode::Char(' ' | 'q')) => {
+            DialogEvent::Cancel
+        }
+        _ => return Ok(false),
+    };
+    app.enqueue_event(AppEvent::Dialog(event));
+    Ok(false)
+}
+
+pub fn handle_paste(text: &str, app: &mut AppState) {
+    if let Some(Dialog {
+        kind: DialogKind::Prompt { .. },
+        ..
+    }) = app.dialogs.last()
+    {
+        let edit = TextEditEvent::Paste(text.to_string());
+        app.enqueue_event(AppEvent::Dialog(DialogEvent::Edit(edit)));
+    }
+}
+
+/// Draws the topmost dialog centered over `area`. It is modal: the hotspots
+/// of the screen below are dropped and only its buttons stay clickable.
+pub fn draw(frame: &mut Frame, area: Rect, dialog: &Dialog, waiting: usize, app: &AppState) {
+    app.mouse.clear();
+    let theme = &app.theme;
+    let width = MAX_WIDTH.min(area.width.saturating_sub(4));
+    let text_width = width.saturating_sub(4).max(1);
+    let message_rows = wrapped_rows(&dialog.message, text_width);
+    let input_rows = if matches!(dialog.kind, DialogKind::Prompt { .. }) { 2 } else { 0 };
+    // Borders, padding, a blank line above the buttons and the buttons.
+    let height = (message_rows + input_rows + 4).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, popup);
+
+    let (border, title_style) = match dialog.kind {
+        DialogKind::Error => (theme.error(), theme.error().add_modifier(Modifier::BOLD)),
+        _ => (theme.popup(), theme.popup_title()),
+    };
+    let mut title = dialog.title.clone();
+    if waiting > 0 {
+        title.push_str(&format!(" ({waiting} more)"));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(dashed_border_set())
+        .border_style(border)
+        .title(Span::styled(title, title_style));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    let body = Rect::new(
+        inner.x + 1,
+        inner.y,
+        inner.width.saturating_sub(2),
+        inner.height.saturating_sub(2),
+    );
+    frame.render_widget(
+        Paragraph::new(dialog.message.clone()).wrap(Wrap { trim: false }),
+        Rect::new(body.x, body.y, body.width, body.height.saturating_sub(input_rows)),
+    );
+
+    if let DialogKind::Prompt { input, .. } = &dialog.kind
+        && body.height >= input_rows
+    {
+        let y = body.bottom() - 1;
+        let (visible, column) = input.scrolled((body.width as usize).saturating_sub(2));
+        let cursor_x = body.x + 2 + column as u16;
+        let line = Line::from(vec![Span::styled("> ", theme.key()), Span::raw(visible)]);
+        frame.render_widget(Paragraph::new(line), Rect::new(body.x, y, body.width, 1));
+        if app.cursor_visible {
+            frame.set_cursor(cursor_x, y);
+        }
+    }
+
+    let buttons = dialog.buttons();
+    let labels = buttons.iter().map(|(label, _)| format!("[ {label} ]")).collect::<Vec<_>>();
+    let total = labels.iter().map(|label| label.width() as u16 + 2).sum::<u16>() - 2;
+    let row = Rect::new(inner.x, inner.bottom().saturating_sub(1), inner.width, 1);
+    let mut x = row.x + row.width.saturating_sub(total) / 2;
+    let mut spans = Vec::new();
+    for (index, ((_, event), label)) in buttons.into_iter().zip(labels).enumerate() {
+        let style = if index == 0 { theme.selected() } else { theme.key() };
+        let label_width = (label.width() as u16).min(row.right().saturating_sub(x));
+        app.mouse.on_click(Rect::new(x, row.y, label_width, 1), vec![AppEvent::Dialog(event)]);
+        x = x.saturating_add(label_width + 2);
+        if index > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(label, style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), row);
+}
+
+/// Rows `text` takes when wrapped at `width` columns, roughly as the
+/// paragraph wraps it.
+fn wrapped_rows(text: &str, width: u16) -> u16 {
+    text.lines()
+        .map(|line| (line.width() as u16).div_ceil(width).max(1))
+        .sum::<u16>()
+        .max(1)
+}
@@ -0,0 +1,101 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::{AppEvent, AppState, HypothesesEvent, HypothesisMark};
+use crate::screens::common::draw_image_preview;
+
+/// Lines above the per-image results.
+const HEADER_LINES: usize = 8;
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &AppState) {
+    // The wheel steps through the per-image results.
+    app.mouse.on_scroll(
+        area,
+        vec![AppEvent::Hypotheses(HypothesesEvent::PrevImage)],
+        vec![AppEvent::Hypotheses(HypothesesEvent::NextImage)],
+    );
+    let row = app.selected_explorer_row();
+    let image = row
+        .as_ref()
+        .and_then(|row| row.hypothesis.results.get(app.explorer.image));
+    let area = match image {
+        Some(result) => {
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(area);
+            let verdict = if result.passed { "pass" } else { "fail" };
+            draw_image_preview(
+                frame,
+                parts[1],
+                &format!("{} ({verdict}) [←/→]", result.image),
+                result.path.as_deref(),
+                "No image file (mock dataset).",
+                app,
+            );
+            parts[0]
+        }
+        None => area,
+    };
+    let content = match &row {
+        Some(row) => {
+            let hypothesis = &row.hypothesis;
+            let passed = hypothesis.results.iter().filter(|r| r.passed).count();
+            let status = match (row.verified, row.mark) {
+                (_, Some(HypothesisMark::Pinned)) => "pinned",
+                (_, Some(HypothesisMark::Rejected)) => "rejected",
+                (true, None) => "verified",
+                (false, None) => "discarded",
+            };
+            let bold = Style::default().add_modifier(Modifier::BOLD);
+            let mut lines = vec![
+                Line::from(Span::styled(hypothesis.description.clone(), bold)),
+                Line::from(""),
+                Line::from(format!(
+                    "Score {:.3} | iteration {} | {status}",
+                    hypothesis.score, hypothesis.iteration
+                )),
+                Line::from(""),
+                Line::from(Span::styled("Rationale", bold)),
+                Line::from(hypothesis.rationale.clone()),
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("Per-image results ({passed}/{} passed)", hypothesis.results.len()),
+                    bold,
+                )),
+            ];
+            lines.extend(hypothesis.results.iter().enumerate().map(|(idx, result)| {
+                let (symbol, style) = if result.passed {
+                    ("pass", app.theme.success())
+                } else {
+                    ("fail", app.theme.error())
+                };
+                let name_style = if idx == app.explorer.image {
+                    app.theme.selected()
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{symbol} "), style),
+                    Span::styled(result.image.clone(), name_style),
+                ])
+            }));
+            Text::from(lines)
+        }
+        None => Text::from("No hypotheses yet."),
+    };
+    // Keep the selected result line in view; wrapped lines make this approximate.
+    let selected_line = (HEADER_LINES + app.explorer.image + 1) as u16;
+    let scroll = selected_line.saturating_sub(area.height.saturating_sub(2));
+    let block = Block::default().borders(Borders::ALL).title("Hypothesis");
+    frame.render_widget(
+        Paragraph::new(content)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0))
+            .block(block),
+        area,
+    );
+}
@@ -0,0 +1,8 @@
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::screens::common::draw_header;
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    draw_header(frame, area, app);
+}
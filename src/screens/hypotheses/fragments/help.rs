@@ -0,0 +1,14 @@
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::app::AppState;
+use crate::screens::common::bar_block;
+use crate::screens::keymap::help_bar;
+use crate::screens::hypotheses::{action_event, HELP};
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    let (help, buttons) = help_bar(&app.keymaps.hypotheses, HELP, app.theme.key());
+    let block = bar_block(area, "Controls");
+    app.mouse.on_help_buttons(block.inner(area), &buttons, action_event);
+    frame.render_widget(Paragraph::new(help).block(block), area);
+}
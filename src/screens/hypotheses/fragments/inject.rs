@@ -0,0 +1,29 @@
+use ratatui::text::Span;
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::screens::theme::Theme;
+use crate::text::TextBuffer;
+use crate::ui::dashed_border_set;
+
+/// Prompt for a hypothesis to inject; returns the cursor position.
+pub fn draw(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    text: &TextBuffer,
+    theme: &Theme,
+) -> (u16, u16) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(dashed_border_set())
+        .border_style(theme.popup())
+        .title(Span::styled(
+            "Inject hypothesis [Enter send / Esc cancel]",
+            theme.popup_title(),
+        ));
+    let width = area.width.saturating_sub(2) as usize;
+    let (visible, column) = text.scrolled(width);
+    let cursor_x = area.x + 1 + column as u16;
+    frame.render_widget(Paragraph::new(visible).block(block), area);
+    (cursor_x, area.y + 1)
+}
@@ -0,0 +1,81 @@
+use ratatui::layout::Constraint;
+use ratatui::text::Span;
+use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
+use ratatui::Frame;
+
+use crate::app::{AppEvent, AppState, HypothesesEvent, HypothesisMark};
+use crate::screens::mouse::row_areas;
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    let rows = app.explorer_rows();
+    let header = Row::new(vec!["", "ID", "Iter", "Score", "", "Hypothesis"])
+        .style(app.theme.table_header());
+
+    let table_rows = rows.iter().map(|row| {
+        let mark = match row.mark {
+            Some(HypothesisMark::Pinned) => Cell::from("*").style(app.theme.accent()),
+            Some(HypothesisMark::Rejected) => Cell::from("x").style(app.theme.error()),
+            None => Cell::from(""),
+        };
+        let verdict = if row.verified {
+            Cell::from("V").style(app.theme.success())
+        } else {
+            Cell::from("X").style(app.theme.muted())
+        };
+        Row::new(vec![
+            mark,
+            Cell::from(row.hypothesis.id.to_string()),
+            Cell::from(row.hypothesis.iteration.to_string()),
+            Cell::from(format!("{:.3}", row.hypothesis.score)),
+            verdict,
+            Cell::from(row.hypothesis.description.clone()),
+        ])
+    });
+
+    let title = match app.explorer_task() {
+        Some(task) => format!(
+            "Hypotheses: task {} {} [sort: {} | filter: {} | {}]",
+            task.id,
+            task.name,
+            app.explorer.sort.label(),
+            app.explorer.filter.label(),
+            rows.len()
+        ),
+        None => "Hypotheses".to_string(),
+    };
+    // The list always has focus on this screen.
+    let (border_set, border_style) = app.theme.focus_border(true);
+    let table = Table::new(table_rows, [
+        Constraint::Length(1),
+        Constraint::Length(5),
+        Constraint::Length(4),
+        Constraint::Length(6),
+        Constraint::Length(1),
+        Constraint::Min(10),
+    ])
+    .header(header)
+    .highlight_style(app.theme.selected())
+    .highlight_symbol(" ")
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_set(border_set)
+            .border_style(border_style)
+            .title(Span::styled(title, app.theme.focus_title(true))),
+    );
+
+    let mut state = TableState::default();
+    if !rows.is_empty() {
+        state.select(Some(app.explorer.selected.min(rows.len() - 1)));
+    }
+    frame.render_stateful_widget(table, area, &mut state);
+
+    app.mouse.on_scroll(
+        area,
+        vec![AppEvent::Hypotheses(HypothesesEvent::SelectPrev)],
+        vec![AppEvent::Hypotheses(HypothesesEvent::SelectNext)],
+    );
+    for (index, row) in row_areas(area, 1, state.offset(), rows.len()) {
+        app.mouse.on_click(row, vec![AppEvent::Hypotheses(HypothesesEvent::Select(index))]);
+    }
+}
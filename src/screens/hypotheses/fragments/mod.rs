@@ -0,0 +1,5 @@
+pub mod detail;
+pub mod header;
+pub mod help;
+pub mod inject;
+pub mod list;
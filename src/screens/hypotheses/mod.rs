@@ -0,0 +1,235 @@
+pub mod fragments;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::Frame;
+
+use crate::app::{AppEvent, AppState, HypothesesEvent, TextEditEvent};
+use crate::screens::keymap::{self, BindableAction, HelpEntry, KeyChord};
+use crate::screens::common::{bar_height, is_narrow};
+use crate::screens::{KeyBinding, Screen};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Close,
+    Next,
+    Prev,
+    NextImage,
+    PrevImage,
+    CycleSort,
+    CycleFilter,
+    TogglePin,
+    ToggleReject,
+    Inject,
+}
+
+impl BindableAction for Action {
+    const ALL: &'static [Self] = &[
+        Action::Close,
+        Action::Next,
+        Action::Prev,
+        Action::NextImage,
+        Action::PrevImage,
+        Action::CycleSort,
+        Action::CycleFilter,
+        Action::TogglePin,
+        Action::ToggleReject,
+        Action::Inject,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Action::Close => "close",
+            Action::Next => "next",
+            Action::Prev => "prev",
+            Action::NextImage => "next_image",
+            Action::PrevImage => "prev_image",
+            Action::CycleSort => "cycle_sort",
+            Action::CycleFilter => "cycle_filter",
+            Action::TogglePin => "toggle_pin",
+            Action::ToggleReject => "toggle_reject",
+            Action::Inject => "inject",
+        }
+    }
+}
+
+pub const HELP: &[HelpEntry<Action>] = &[
+    (&[Action::Next, Action::Prev], "move"),
+    (&[Action::PrevImage, Action::NextImage], "image"),
+    (&[Action::CycleSort], "sort"),
+    (&[Action::CycleFilter], "filter"),
+    (&[Action::TogglePin], "pin"),
+    (&[Action::ToggleReject], "reject"),
+    (&[Action::Inject], "inject"),
+    (&[Action::Close], "back"),
+];
+
+pub const KEY_BINDINGS: &[KeyBinding<Action>] = &[
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Esc),
+        action: Action::Close,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('q')),
+        action: Action::Close,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('j')),
+        action: Action::Next,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('k')),
+        action: Action::Prev,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Down),
+        action: Action::Next,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Up),
+        action: Action::Prev,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Right),
+        action: Action::NextImage,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Left),
+        action: Action::PrevImage,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('s')),
+        action: Action::CycleSort,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Tab),
+        action: Action::CycleFilter,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('p')),
+        action: Action::TogglePin,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('x')),
+        action: Action::ToggleReject,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('a')),
+        action: Action::Inject,
+    },
+];
+
+pub struct HypothesesScreen;
+
+impl Screen for HypothesesScreen {
+    fn draw(&self, frame: &mut Frame, app: &AppState) {
+        draw(frame, app);
+    }
+
+    fn handle_key(
+        &self,
+        key: KeyEvent,
+        app: &mut AppState,
+    ) -> std::io::Result<bool> {
+        handle_key(key, app)
+    }
+
+    fn handle_paste(&self, text: &str, app: &mut AppState) {
+        handle_paste(text, app);
+    }
+}
+
+pub fn handle_key(
+    key: KeyEvent,
+    app: &mut AppState,
+) -> std::io::Result<bool> {
+    if app.explorer.inject.is_some() {
+        return handle_inject_key(key, app);
+    }
+    if let Some(action) = keymap::lookup(&app.keymaps.hypotheses, &key) {
+        app.enqueue_event(action_event(action));
+    }
+    Ok(false)
+}
+
+pub fn action_event(action: Action) -> AppEvent {
+    let event = match action {
+        Action::Close => HypothesesEvent::Close,
+        Action::Next => HypothesesEvent::SelectNext,
+        Action::Prev => HypothesesEvent::SelectPrev,
+        Action::NextImage => HypothesesEvent::NextImage,
+        Action::PrevImage => HypothesesEvent::PrevImage,
+        Action::CycleSort => HypothesesEvent::CycleSort,
+        Action::CycleFilter => HypothesesEvent::CycleFilter,
+        Action::TogglePin => HypothesesEvent::TogglePin,
+        Action::ToggleReject => HypothesesEvent::ToggleReject,
+        Action::Inject => HypothesesEvent::StartInject,
+    };
+    AppEvent::Hypotheses(event)
+}
+
+/// Pastes only go to the inject prompt.
+pub fn handle_paste(text: &str, app: &mut AppState) {
+    if app.explorer.inject.is_some() {
+        app.enqueue_event(AppEvent::Hypotheses(HypothesesEvent::InjectEdit(
+            TextEditEvent::Paste(text.to_string()),
+        )));
+    }
+}
+
+/// While the inject prompt is open every key edits the new hypothesis.
+fn handle_inject_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let event = match key.code {
+        KeyCode::Enter => HypothesesEvent::SubmitInject,
+        KeyCode::Esc => HypothesesEvent::CancelInject,
+        _ => match keymap::text_edit_event(&key) {
+            Some(edit) => HypothesesEvent::InjectEdit(edit),
+            None => return Ok(false),
+        },
+    };
+    app.enqueue_event(AppEvent::Hypotheses(event));
+    Ok(false)
+}
+
+pub fn draw(frame: &mut Frame, app: &AppState) {
+    let area = frame.size();
+    let bar = bar_height(area);
+    let root = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(bar),
+            Constraint::Min(8),
+            Constraint::Length(bar),
+        ])
+        .split(area);
+
+    fragments::header::draw(frame, root[0], app);
+
+    let body = if is_narrow(area) {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(root[1])
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(root[1])
+    };
+    match &app.explorer.inject {
+        Some(text) => {
+            let list = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(3)])
+                .split(body[0]);
+            fragments::list::draw(frame, list[0], app);
+            let cursor = fragments::inject::draw(frame, list[1], text, &app.theme);
+            if app.cursor_visible {
+                frame.set_cursor(cursor.0, cursor.1);
+            }
+        }
+        None => fragments::list::draw(frame, body[0], app),
+    }
+    fragments::detail::draw(frame, body[1], app);
+    fragments::help::draw(frame, root[2], app);
+}
@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::TextEditEvent;
+use crate::config::KeysConfig;
+use crate::screens::{hypotheses, main, task_input, KeyBinding};
+
+/// A key and the modifiers held with it, written `ctrl+s`, `alt+x`, `F5` or `esc`
+/// in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// The chord a key event matches. Shift is folded into characters
+    /// (`:` arrives as Shift+`:`) and into BackTab.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers
+            & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self::new(event.code, modifiers)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (prefix, key) = match text.strip_suffix("++") {
+            Some(prefix) => (prefix, "+"),
+            None => match text.rsplit_once('+') {
+                Some((prefix, key)) if !key.is_empty() => (prefix, key),
+                _ => ("", text),
+            },
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for name in prefix.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{name}' in '{text}'")),
+            };
+        }
+        let mut code = parse_code(key).ok_or_else(|| format!("unknown key '{text}'"))?;
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            match code {
+                KeyCode::Char(ch) => {
+                    code = KeyCode::Char(ch.to_ascii_uppercase());
+                    modifiers.remove(KeyModifiers::SHIFT);
+                }
+                KeyCode::Tab | KeyCode::BackTab => {
+                    code = KeyCode::BackTab;
+                    modifiers.remove(KeyModifiers::SHIFT);
+                }
+                _ => {}
+            }
+        }
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+fn parse_code(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(ch));
+    }
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "ins" | "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let number = lower.strip_prefix('f')?.parse().ok()?;
+            return (1..=24).contains(&number).then_some(KeyCode::F(number));
+        }
+    };
+    Some(code)
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::Insert => f.write_str("Ins"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Up => f.write_str("Up"),
+            KeyCode::Down => f.write_str("Down"),
+            KeyCode::Left => f.write_str("Left"),
+            KeyCode::Right => f.write_str("Right"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Actions a screen exposes to `[keys.<screen>]` config tables.
+pub trait BindableAction: Copy + PartialEq + 'static {
+    const ALL: &'static [Self];
+
+    /// The action's snake_case name in config files.
+    fn name(self) -> &'static str;
+}
+
+/// The active bindings of every screen: defaults with config overrides applied.
+#[derive(Debug, Clone)]
+pub struct Keymaps {
+    pub main: Vec<KeyBinding<main::Action>>,
+    pub task_input: Vec<KeyBinding<task_input::Action>>,
+    pub hypotheses: Vec<KeyBinding<hypotheses::Action>>,
+}
+
+impl Default for Keymaps {
+    fn default() -> Self {
+        Self {
+            main: main::KEY_BINDINGS.to_vec(),
+            task_input: task_input::KEY_BINDINGS.to_vec(),
+            hypotheses: hypotheses::KEY_BINDINGS.to_vec(),
+        }
+    }
+}
+
+impl Keymaps {
+    pub fn build(config: &KeysConfig) -> Result<Self, String> {
+        let keymaps = Self {
+            main: apply_overrides("main", main::KEY_BINDINGS, &config.main)?,
+            task_input: apply_overrides("task_input", task_input::KEY_BINDINGS, &config.task_input)?,
+            hypotheses: apply_overrides("hypotheses", hypotheses::KEY_BINDINGS, &config.hypotheses)?,
+        };
+        reject_typed_keys("task_input", &config.task_input)?;
+        Ok(keymaps)
+    }
+}
+
+/// Every task input field takes text, so characters typed without Ctrl or Alt
+/// are inserted before any binding is looked up; binding them there would
+/// never fire.
+fn reject_typed_keys(
+    screen: &str,
+    overrides: &BTreeMap<String, Vec<String>>,
+) -> Result<(), String> {
+    for (name, keys) in overrides {
+        for key in keys {
+            let chord = KeyChord::parse(key).map_err(|e| format!("keys.{screen}.{name}: {e}"))?;
+            if matches!(chord.code, KeyCode::Char(_))
+                && !chord.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            {
+                return Err(format!(
+                    "keys.{screen}.{name}: '{}' types text in a field; add ctrl or alt",
+                    key.trim()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An overridden action loses its default keys, and its new keys are taken
+/// away from any other action; an empty list unbinds the action.
+fn apply_overrides<A: BindableAction>(
+    screen: &str,
+    defaults: &[KeyBinding<A>],
+    overrides: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<KeyBinding<A>>, String> {
+    let mut bindings = Vec::new();
+    for (name, keys) in overrides {
+        let action = A::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+            .ok_or_else(|| format!("keys.{screen}: unknown action '{name}'"))?;
+        for key in keys {
+            let key = KeyChord::parse(key).map_err(|e| format!("keys.{screen}.{name}: {e}"))?;
+            bindings.push(KeyBinding { key, action });
+        }
+    }
+    let overridden = bindings.clone();
+    bindings.extend(defaults.iter().copied().filter(|binding| {
+        !overrides.contains_key(binding.action.name())
+            && !overridden.iter().any(|taken| taken.key == binding.key)
+    }));
+    Ok(bindings)
+}
+
+pub fn lookup<A: Copy>(bindings: &[KeyBinding<A>], event: &KeyEvent) -> Option<A> {
+    let chord = KeyChord::from_event(event);
+    bindings
+        .iter()
+        .find(|binding| binding.key == chord)
+        .map(|binding| binding.action)
+}
+
+/// The fixed line-editing keys shared by every text field and prompt. Only
+/// unmodified (or shifted) characters are typed, so Ctrl and Alt chords never
+/// insert text.
+pub fn text_edit_event(event: &KeyEvent) -> Option<TextEditEvent> {
+    let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = event.modifiers.contains(KeyModifiers::ALT);
+    let edit = match event.code {
+        KeyCode::Char(ch) if !ctrl && !alt => TextEditEvent::InsertChar(ch),
+        KeyCode::Char('a') if ctrl => TextEditEvent::Home,
+        KeyCode::Char('e') if ctrl => TextEditEvent::End,
+        KeyCode::Char('w') if ctrl => TextEditEvent::DeleteWordBack,
+        KeyCode::Char('u') if ctrl => TextEditEvent::DeleteToStart,
+        KeyCode::Char('k') if ctrl => TextEditEvent::DeleteToEnd,
+        KeyCode::Char('b') if alt => TextEditEvent::WordLeft,
+        KeyCode::Char('f') if alt => TextEditEvent::WordRight,
+        KeyCode::Backspace if ctrl || alt => TextEditEvent::DeleteWordBack,
+        KeyCode::Backspace => TextEditEvent::Backspace,
+        KeyCode::Delete => TextEditEvent::Delete,
+        KeyCode::Left if ctrl || alt => TextEditEvent::WordLeft,
+        KeyCode::Right if ctrl || alt => TextEditEvent::WordRight,
+        KeyCode::Left => TextEditEvent::CursorLeft,
+        KeyCode::Right => TextEditEvent::CursorRight,
+        KeyCode::Home => TextEditEvent::Home,
+        KeyCode::End => TextEditEvent::End,
+        _ => return None,
+    };
+    Some(edit)
+}
+
+/// Keys bound to `action`, in binding order.
+pub fn keys_for<A: BindableAction>(bindings: &[KeyBinding<A>], action: A) -> Vec<KeyChord> {
+    bindings
+        .iter()
+        .filter(|binding| binding.action == action)
+        .map(|binding| binding.key)
+        .collect()
+}
+
+/// A help bar entry: the actions it describes and its label. A single action
+/// lists all of its keys; a group lists the first key of each action.
+pub type HelpEntry<A> = (&'static [A], &'static str);
+
+/// Columns of a help bar (from the start of the line) that run an action
+/// when clicked.
+pub type HelpButton<A> = (Range<u16>, A);
+
+/// Renders a help bar from the active bindings, keys in `key_style`;
+/// unbound entries are left out.
+pub fn help_line<A: BindableAction>(
+    bindings: &[KeyBinding<A>],
+    entries: &[HelpEntry<A>],
+    key_style: Style,
+) -> Line<'static> {
+    help_bar(bindings, entries, key_style).0
+}
+
+/// A help bar and its buttons: every key runs its own action, and an entry's
+/// label runs the action of its first key.
+pub fn help_bar<A: BindableAction>(
+    bindings: &[KeyBinding<A>],
+    entries: &[HelpEntry<A>],
+    key_style: Style,
+) -> (Line<'static>, Vec<HelpButton<A>>) {
+    let mut spans = Vec::new();
+    let mut buttons = Vec::new();
+    let mut column = 0u16;
+    for (actions, label) in entries {
+        let keys = match actions {
+            [action] => keys_for(bindings, *action)
+                .into_iter()
+                .map(|key| (key, *action))
+                .collect::<Vec<_>>(),
+            group => group
+                .iter()
+                .filter_map(|action| {
+                    keys_for(bindings, *action).first().map(|key| (*key, *action))
+                })
+                .collect(),
+        };
+        let Some(&(_, first)) = keys.first() else {
+            continue;
+        };
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+            column += 2;
+        }
+        let mut text = Vec::new();
+        for (index, (key, action)) in keys.iter().enumerate() {
+            if index > 0 {
+                column += 1;
+            }
+            let key = key.to_string();
+            let width = key.width() as u16;
+            buttons.push((column..column + width, *action));
+            column += width;
+            text.push(key);
+        }
+        spans.push(Span::styled(text.join("/"), key_style));
+        let label = format!(" {label}");
+        let width = label.width() as u16;
+        buttons.push((column..column + width, first));
+        column += width;
+        spans.push(Span::raw(label));
+    }
+    (Line::from(spans), buttons)
+}
@@ -1,13 +1,21 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::symbols::Marker;
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline};
 use ratatui::Frame;
 
-use crate::app::AppState;
+use crate::app::{AppEvent, AppState, MainScreenEvent};
+use crate::screens::theme::Theme;
 use crate::screens::FragmentId;
-use crate::ui::{format_phase, format_status, truncate, dashed_border_set};
+use crate::task::TaskSnapshot;
+use crate::ui::{format_phase, format_status, truncate};
 
-pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+/// Below this many rows the score history collapses to a one-line sparkline.
+const MIN_CHART_HEIGHT: u16 = 6;
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &AppState) {
+    app.mouse.on_click(area, vec![AppEvent::Main(MainScreenEvent::FocusDetail)]);
     let content = if let Some(task) = app.selected_task() {
         let mut lines = vec![
             Line::from(vec![Span::styled(
@@ -44,9 +52,8 @@ pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
             )),
             Line::from(format!(
                 "Recent: {}",
-                app.recent_logs()
-                    .last()
-                    .cloned()
+                app.last_log(task.id)
+                    .map(|entry| entry.message.clone())
                     .unwrap_or_else(|| "none".to_string())
             )),
         ]);
@@ -56,20 +63,98 @@ pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
     };
 
     let active = app.fragment == FragmentId::MainDetail;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let (border_set, border_style) = if active {
-        (dashed_border_set(), Style::default().fg(Color::Green))
-    } else {
-        (ratatui::symbols::border::PLAIN, Style::default())
-    };
+    let title_style = app.theme.focus_title(active);
+    let (border_set, border_style) = app.theme.focus_border(active);
     let block = Block::default()
         .borders(Borders::ALL)
         .border_set(border_set)
         .border_style(border_style)
         .title(Span::styled("Task Detail [D]", title_style));
-    frame.render_widget(Paragraph::new(content).block(block), area);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let text_height = (content.height() as u16).min(inner.height);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(text_height), Constraint::Min(0)])
+        .split(inner);
+    frame.render_widget(Paragraph::new(content), rows[0]);
+    if let Some(task) = app.selected_task() {
+        draw_scores(frame, rows[1], &task, &app.theme);
+    }
+}
+
+/// Best and per-iteration score over the run, with the verify threshold as a
+/// reference line.
+fn draw_scores(frame: &mut Frame, area: Rect, task: &TaskSnapshot, theme: &Theme) {
+    if area.height == 0 || task.history.is_empty() {
+        return;
+    }
+    if area.height < MIN_CHART_HEIGHT {
+        let best = task
+            .history
+            .iter()
+            .map(|record| (record.best_score * 100.0).round() as u64)
+            .collect::<Vec<_>>();
+        let sparkline = Sparkline::default()
+            .data(&best)
+            .max(100)
+            .style(theme.success());
+        frame.render_widget(sparkline, Rect { height: 1, ..area });
+        return;
+    }
+
+    let best = task
+        .history
+        .iter()
+        .map(|record| (record.iteration as f64, record.best_score as f64))
+        .collect::<Vec<_>>();
+    let last = task
+        .history
+        .iter()
+        .map(|record| (record.iteration as f64, record.last_score as f64))
+        .collect::<Vec<_>>();
+    // Seed hypotheses are recorded as iteration 0.
+    let min_x = task.history.first().map_or(1, |record| record.iteration.min(1));
+    let max_x = task.max_iters.max(2) as f64;
+    let threshold = [
+        (min_x as f64, task.verify_threshold as f64),
+        (max_x, task.verify_threshold as f64),
+    ];
+    let datasets = vec![
+        Dataset::default()
+            .name(format!("threshold {:.2}", task.verify_threshold))
+            .marker(Marker::Dot)
+            .graph_type(GraphType::Line)
+            .style(theme.error())
+            .data(&threshold),
+        Dataset::default()
+            .name("last")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(theme.muted())
+            .data(&last),
+        Dataset::default()
+            .name("best")
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(theme.success())
+            .data(&best),
+    ];
+    let axis_style = theme.axis();
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([min_x as f64, max_x])
+                .labels(vec![Span::raw(min_x.to_string()), Span::raw(format!("{}", task.max_iters))]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, 1.0])
+                .labels(vec![Span::raw("0"), Span::raw("1")]),
+        )
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+    frame.render_widget(chart, area);
 }
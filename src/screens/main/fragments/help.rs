@@ -1,21 +1,14 @@
-use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect) {
-    let help = Line::from(vec![
-        Span::styled("n", Style::default().fg(Color::Yellow)),
-        Span::raw(" new task  "),
-        Span::styled("t/d/i", Style::default().fg(Color::Yellow)),
-        Span::raw(" fragment  "),
-        Span::styled("j/k", Style::default().fg(Color::Yellow)),
-        Span::raw(" move (tasks)  "),
-        Span::styled("c", Style::default().fg(Color::Yellow)),
-        Span::raw(" cancel  "),
-        Span::styled("q", Style::default().fg(Color::Yellow)),
-        Span::raw(" quit"),
-    ]);
-    let block = Block::default().borders(Borders::ALL).title("Controls");
+use crate::app::AppState;
+use crate::screens::common::bar_block;
+use crate::screens::keymap::help_bar;
+use crate::screens::main::{action_event, HELP};
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    let (help, buttons) = help_bar(&app.keymaps.main, HELP, app.theme.key());
+    let block = bar_block(area, "Controls");
+    app.mouse.on_help_buttons(block.inner(area), &buttons, action_event);
     frame.render_widget(Paragraph::new(help).block(block), area);
 }
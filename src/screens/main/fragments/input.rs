@@ -1,29 +1,41 @@
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
-use crate::app::AppState;
+use crate::app::{AppEvent, AppState, MainScreenEvent};
 use crate::screens::FragmentId;
-use crate::ui::dashed_border_set;
 
-pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+/// The command line; returns the cursor position while it is focused.
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) -> Option<(u16, u16)> {
+    app.mouse.on_click(area, vec![AppEvent::Main(MainScreenEvent::FocusInput)]);
     let active = app.fragment == FragmentId::MainInput;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let (border_set, border_style) = if active {
-        (dashed_border_set(), Style::default().fg(Color::Green))
-    } else {
-        (ratatui::symbols::border::PLAIN, Style::default())
-    };
+    let title_style = app.theme.focus_title(active);
+    let (border_set, border_style) = app.theme.focus_border(active);
+    let mut title = vec![Span::styled("Command [:]", title_style)];
+    if let Some((level, message)) = &app.command_line.message {
+        title.push(Span::styled(format!(" {message}"), app.theme.log_level(*level)));
+    }
     let block = Block::default()
         .borders(Borders::ALL)
         .border_set(border_set)
         .border_style(border_style)
-        .title(Span::styled("Task Input [N]", title_style));
-    let value = "Press 'n' to open task input activity.".to_string();
-    frame.render_widget(Paragraph::new(value).block(block), area);
+        .title(Line::from(title));
+
+    if !active && app.command_line.text.is_empty() {
+        let hint = Span::styled(
+            "Press ':' for commands: cancel, retry, export, filter.",
+            app.theme.muted(),
+        );
+        frame.render_widget(Paragraph::new(Line::from(hint)).block(block), area);
+        return None;
+    }
+    let width = area.width.saturating_sub(3) as usize;
+    let (visible, column) = app.command_line.text.scrolled(width);
+    let cursor_x = area.x + 2 + column as u16;
+    let line = Line::from(vec![
+        Span::styled(":", app.theme.key()),
+        Span::raw(visible),
+    ]);
+    frame.render_widget(Paragraph::new(line).block(block), area);
+    active.then_some((cursor_x, area.y + 1))
 }
@@ -0,0 +1,93 @@
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{AppEvent, AppState, LogEntry, MainScreenEvent};
+use crate::engine::scheduler::LogLevel;
+use crate::screens::mouse::WHEEL_LINES;
+use crate::screens::theme::Theme;
+use crate::screens::FragmentId;
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    let entries = app.visible_logs();
+    let view = &app.log_view;
+    let height = area.height.saturating_sub(2) as usize;
+    let scroll = view.scroll.min(entries.len().saturating_sub(height));
+    let end = entries.len() - scroll;
+    let start = end.saturating_sub(height);
+    let lines = entries[start..end]
+        .iter()
+        .map(|entry| log_line(entry, &view.query, &app.theme))
+        .collect::<Vec<_>>();
+
+    let active = app.fragment == FragmentId::MainLogs;
+    let title_style = app.theme.focus_title(active);
+    let (border_set, border_style) = app.theme.focus_border(active);
+    let mut title = vec![Span::styled("Logs [L]", title_style)];
+    if view.searching || !view.query.is_empty() {
+        let cursor = if view.searching && app.cursor_visible { "_" } else { "" };
+        title.push(Span::styled(
+            format!(" /{}{cursor} ({} matches)", view.query, entries.len()),
+            app.theme.warning(),
+        ));
+    }
+    if scroll > 0 {
+        title.push(Span::raw(format!(" [{}-{} of {}]", start + 1, end, entries.len())));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(border_set)
+        .border_style(border_style)
+        .title(Line::from(title));
+    let content = if lines.is_empty() {
+        vec![Line::from(if view.query.is_empty() {
+            "No logs for this task."
+        } else {
+            "No matching log lines."
+        })]
+    } else {
+        lines
+    };
+    frame.render_widget(Paragraph::new(content).block(block), area);
+
+    app.mouse.on_click(area, vec![AppEvent::Main(MainScreenEvent::FocusLogs)]);
+    app.mouse.on_scroll(
+        area,
+        vec![AppEvent::Main(MainScreenEvent::ScrollLogs(WHEEL_LINES))],
+        vec![AppEvent::Main(MainScreenEvent::ScrollLogs(-WHEEL_LINES))],
+    );
+}
+
+fn log_line<'a>(entry: &'a LogEntry, query: &str, theme: &Theme) -> Line<'a> {
+    let tag = match entry.level {
+        LogLevel::Info => "INFO ",
+        LogLevel::Warn => "WARN ",
+        LogLevel::Error => "ERROR",
+    };
+    let mut spans = vec![Span::styled(format!("{tag} "), theme.log_level(entry.level))];
+    let message = entry.message.as_str();
+    let highlight = theme.highlight();
+    let mut rest = 0;
+    for (start, end) in match_ranges(message, query) {
+        spans.push(Span::raw(&message[rest..start]));
+        spans.push(Span::styled(&message[start..end], highlight));
+        rest = end;
+    }
+    spans.push(Span::raw(&message[rest..]));
+    Line::from(spans)
+}
+
+/// Byte ranges of case-insensitive matches of `query` in `text`.
+fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths outside ASCII; skip highlighting then.
+    if query.is_empty() || lower.len() != text.len() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    lower
+        .match_indices(&query)
+        .map(|(start, found)| (start, start + found.len()))
+        .filter(|(start, end)| text.is_char_boundary(*start) && text.is_char_boundary(*end))
+        .collect()
+}
@@ -2,4 +2,5 @@ pub mod detail;
 pub mod header;
 pub mod help;
 pub mod input;
+pub mod logs;
 pub mod tasks;
@@ -1,23 +1,33 @@
 use ratatui::layout::Constraint;
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Row, Table, TableState};
 use ratatui::Frame;
 
-use crate::app::AppState;
-use crate::screens::common::selected_list_style;
+use crate::app::{AppEvent, AppState, MainScreenEvent, TaskFilter, TaskGrouping, TaskSort};
+use crate::screens::mouse::row_areas;
 use crate::screens::{FragmentId};
-use crate::ui::{dashed_border_set, truncate, format_status};
+use crate::ui::{truncate, format_status};
 
 pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
     let tasks = app.tasks_in_order();
     let header = Row::new(vec![
         "ID", "Name", "Status", "Progress", "Iter", "V", "X",
     ])
-    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    .style(app.theme.table_header());
 
-    let rows = tasks.iter().map(|task| {
-        Row::new(vec![
+    // With grouping on, each status group starts with a header row; `row_tasks`
+    // maps table rows back to task indices.
+    let grouped = app.task_grouping == TaskGrouping::Status;
+    let mut rows = Vec::new();
+    let mut row_tasks = Vec::new();
+    for (index, task) in tasks.iter().enumerate() {
+        let group = TaskFilter::of(&task.status);
+        let starts_group = index == 0 || TaskFilter::of(&tasks[index - 1].status) != group;
+        if grouped && starts_group {
+            rows.push(Row::new(vec!["", group.label()]).style(app.theme.table_header()));
+            row_tasks.push(None);
+        }
+        rows.push(Row::new(vec![
             task.id.to_string(),
             truncate(&task.name, 16),
             format_status(&task.status),
@@ -25,21 +35,36 @@ pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
             format!("{}/{}", task.iteration, task.max_iters),
             task.verified.len().to_string(),
             task.discarded.len().to_string(),
-        ])
-    });
+        ]));
+        row_tasks.push(Some(index));
+    }
 
     let active = app.fragment == FragmentId::MainTasks;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let (border_set, border_style) = if active {
-        (dashed_border_set(), Style::default().fg(Color::Green))
-    } else {
-        (ratatui::symbols::border::PLAIN, Style::default())
-    };
+    let title_style = app.theme.focus_title(active);
+    let (border_set, border_style) = app.theme.focus_border(active);
 
+    let mut view = Vec::new();
+    if app.task_filter != TaskFilter::All {
+        view.push(app.task_filter.label().to_string());
+    }
+    if app.task_sort != TaskSort::Created {
+        view.push(format!("by {}", app.task_sort.label()));
+    }
+    if grouped {
+        view.push("grouped".to_string());
+    }
+    let mut title = vec![Span::styled("Tasks [T]", title_style)];
+    if !view.is_empty() {
+        title.push(Span::styled(format!(" ({})", view.join(", ")), title_style));
+    }
+    let search = &app.task_search;
+    if search.searching || !search.query.is_empty() {
+        let cursor = if search.searching && app.cursor_visible { "_" } else { "" };
+        title.push(Span::styled(
+            format!(" /{}{cursor} ({} matches)", search.query, tasks.len()),
+            app.theme.warning(),
+        ));
+    }
     let table = Table::new(rows, [
         Constraint::Length(4),
         Constraint::Length(16),
@@ -50,19 +75,32 @@ pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
         Constraint::Length(3),
     ])
     .header(header)
-    .highlight_style(selected_list_style())
+    .highlight_style(app.theme.selected())
     .highlight_symbol(" ")
     .block(
         Block::default()
             .borders(Borders::ALL)
             .border_set(border_set)
             .border_style(border_style)
-            .title(Span::styled("Tasks [T]", title_style)),
+            .title(Line::from(title)),
     );
 
     let mut state = TableState::default();
     if !tasks.is_empty() {
-        state.select(Some(app.selected));
+        state.select(row_tasks.iter().position(|task| *task == Some(app.selected)));
     }
     frame.render_stateful_widget(table, area, &mut state);
+
+    let focus = AppEvent::Main(MainScreenEvent::FocusTasks);
+    app.mouse.on_click(area, vec![focus.clone()]);
+    app.mouse.on_scroll(
+        area,
+        vec![focus.clone(), AppEvent::Main(MainScreenEvent::SelectTaskPrev)],
+        vec![focus, AppEvent::Main(MainScreenEvent::SelectTaskNext)],
+    );
+    for (row_index, row) in row_areas(area, 1, state.offset(), row_tasks.len()) {
+        if let Some(index) = row_tasks[row_index] {
+            app.mouse.on_click(row, vec![AppEvent::Main(MainScreenEvent::SelectTask(index))]);
+        }
+    }
 }
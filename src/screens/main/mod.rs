@@ -1,13 +1,15 @@
 pub mod fragments;
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Frame;
 
-use crate::app::{AppEvent, AppState, MainScreenEvent};
-use crate::screens::{KeyBinding, Screen};
+use crate::app::{AppEvent, AppState, CommandLineEvent, MainScreenEvent, TextEditEvent};
+use crate::screens::keymap::{self, BindableAction, HelpEntry, KeyChord};
+use crate::screens::common::{bar_height, is_narrow};
+use crate::screens::{FragmentId, KeyBinding, Screen};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Quit,
     OpenTaskInput,
@@ -17,49 +19,189 @@ pub enum Action {
     NextTask,
     PrevTask,
     CancelTask,
+    ExportReport,
+    FocusLogs,
+    SearchLogs,
+    ClearLogSearch,
+    LogsPageUp,
+    LogsPageDown,
+    LogsTop,
+    LogsBottom,
+    OpenHypotheses,
+    SearchTasks,
+    CycleFilter,
+    CycleSort,
+    CycleGrouping,
 }
 
-const KEY_BINDINGS: &[KeyBinding<Action>] = &[
+impl BindableAction for Action {
+    const ALL: &'static [Self] = &[
+        Action::Quit,
+        Action::OpenTaskInput,
+        Action::FocusTasks,
+        Action::FocusDetail,
+        Action::FocusInput,
+        Action::NextTask,
+        Action::PrevTask,
+        Action::CancelTask,
+        Action::ExportReport,
+        Action::FocusLogs,
+        Action::SearchLogs,
+        Action::ClearLogSearch,
+        Action::LogsPageUp,
+        Action::LogsPageDown,
+        Action::LogsTop,
+        Action::LogsBottom,
+        Action::OpenHypotheses,
+        Action::SearchTasks,
+        Action::CycleFilter,
+        Action::CycleSort,
+        Action::CycleGrouping,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::OpenTaskInput => "open_task_input",
+            Action::FocusTasks => "focus_tasks",
+            Action::FocusDetail => "focus_detail",
+            Action::FocusInput => "focus_input",
+            Action::NextTask => "next_task",
+            Action::PrevTask => "prev_task",
+            Action::CancelTask => "cancel_task",
+            Action::ExportReport => "export_report",
+            Action::FocusLogs => "focus_logs",
+            Action::SearchLogs => "search_logs",
+            Action::ClearLogSearch => "clear_log_search",
+            Action::LogsPageUp => "logs_page_up",
+            Action::LogsPageDown => "logs_page_down",
+            Action::LogsTop => "logs_top",
+            Action::LogsBottom => "logs_bottom",
+            Action::OpenHypotheses => "open_hypotheses",
+            Action::SearchTasks => "search_tasks",
+            Action::CycleFilter => "cycle_filter",
+            Action::CycleSort => "cycle_sort",
+            Action::CycleGrouping => "cycle_grouping",
+        }
+    }
+}
+
+pub const HELP: &[HelpEntry<Action>] = &[
+    (&[Action::OpenTaskInput], "new task"),
+    (&[Action::FocusTasks, Action::FocusDetail, Action::FocusLogs], "fragment"),
+    (&[Action::NextTask, Action::PrevTask], "move/scroll"),
+    (&[Action::SearchTasks], "find"),
+    (&[Action::CycleFilter], "status"),
+    (&[Action::CycleSort], "sort"),
+    (&[Action::CycleGrouping], "group"),
+    (&[Action::SearchLogs], "search logs"),
+    (&[Action::OpenHypotheses], "hypotheses"),
+    (&[Action::FocusInput], "command"),
+    (&[Action::ExportReport], "export"),
+    (&[Action::CancelTask], "cancel"),
+    (&[Action::Quit], "quit"),
+];
+
+/// Lines moved by PageUp/PageDown in the log pane.
+const LOG_PAGE: isize = 10;
+
+pub const KEY_BINDINGS: &[KeyBinding<Action>] = &[
     KeyBinding {
-        key: KeyCode::Char('q'),
+        key: KeyChord::plain(KeyCode::Char('q')),
         action: Action::Quit,
     },
     KeyBinding {
-        key: KeyCode::Char('n'),
+        key: KeyChord::plain(KeyCode::Char('n')),
         action: Action::OpenTaskInput,
     },
     KeyBinding {
-        key: KeyCode::Char('t'),
+        key: KeyChord::plain(KeyCode::Char('t')),
         action: Action::FocusTasks,
     },
     KeyBinding {
-        key: KeyCode::Char('d'),
+        key: KeyChord::plain(KeyCode::Char('d')),
         action: Action::FocusDetail,
     },
     KeyBinding {
-        key: KeyCode::Char('i'),
+        key: KeyChord::plain(KeyCode::Char('i')),
         action: Action::FocusInput,
     },
     KeyBinding {
-        key: KeyCode::Down,
+        key: KeyChord::plain(KeyCode::Char(':')),
+        action: Action::FocusInput,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('j')),
         action: Action::NextTask,
     },
     KeyBinding {
-        key: KeyCode::Up,
+        key: KeyChord::plain(KeyCode::Char('k')),
         action: Action::PrevTask,
     },
     KeyBinding {
-        key: KeyCode::Char('j'),
+        key: KeyChord::plain(KeyCode::Down),
         action: Action::NextTask,
     },
     KeyBinding {
-        key: KeyCode::Char('k'),
+        key: KeyChord::plain(KeyCode::Up),
         action: Action::PrevTask,
     },
     KeyBinding {
-        key: KeyCode::Char('c'),
+        key: KeyChord::plain(KeyCode::Char('c')),
         action: Action::CancelTask,
     },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('e')),
+        action: Action::ExportReport,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('l')),
+        action: Action::FocusLogs,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('/')),
+        action: Action::SearchLogs,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Esc),
+        action: Action::ClearLogSearch,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::PageUp),
+        action: Action::LogsPageUp,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::PageDown),
+        action: Action::LogsPageDown,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Home),
+        action: Action::LogsTop,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::End),
+        action: Action::LogsBottom,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('h')),
+        action: Action::OpenHypotheses,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('f')),
+        action: Action::SearchTasks,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Tab),
+        action: Action::CycleFilter,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('s')),
+        action: Action::CycleSort,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Char('g')),
+        action: Action::CycleGrouping,
+    },
 ];
 
 pub struct MainScreen;
@@ -71,62 +213,167 @@ impl Screen for MainScreen {
 
     fn handle_key(
         &self,
-        key: KeyCode,
+        key: KeyEvent,
         app: &mut AppState,
     ) -> std::io::Result<bool> {
         handle_key(key, app)
     }
+
+    fn handle_paste(&self, text: &str, app: &mut AppState) {
+        handle_paste(text, app);
+    }
 }
 
 pub fn handle_key(
-    key: KeyCode,
+    key: KeyEvent,
     app: &mut AppState,
 ) -> std::io::Result<bool> {
-    if let Some(binding) = KEY_BINDINGS.iter().find(|b| b.key == key) {
-        return handle_action(binding.action, app);
+    if app.log_view.searching {
+        return handle_search_key(key, app);
+    }
+    if app.task_search.searching {
+        return handle_task_search_key(key, app);
+    }
+    if app.fragment == FragmentId::MainInput {
+        return handle_command_key(key, app);
+    }
+    if let Some(action) = keymap::lookup(&app.keymaps.main, &key) {
+        app.enqueue_event(action_event(action));
     }
     Ok(false)
 }
 
-fn handle_action(
-    action: Action,
-    app: &mut AppState,
-) -> std::io::Result<bool> {
+/// Pastes go to a search prompt or the command line, whichever is open.
+pub fn handle_paste(text: &str, app: &mut AppState) {
+    let edit = TextEditEvent::Paste(text.to_string());
+    if app.log_view.searching {
+        app.enqueue_event(AppEvent::Main(MainScreenEvent::LogSearch(edit)));
+    } else if app.task_search.searching {
+        app.enqueue_event(AppEvent::Main(MainScreenEvent::TaskSearch(edit)));
+    } else if app.fragment == FragmentId::MainInput {
+        app.enqueue_event(AppEvent::Main(MainScreenEvent::CommandLine(
+            CommandLineEvent::Edit(edit),
+        )));
+    }
+}
+
+pub fn action_event(action: Action) -> AppEvent {
     match action {
-        Action::Quit => app.enqueue_event(AppEvent::Quit),
-        Action::OpenTaskInput => app.enqueue_event(AppEvent::Main(MainScreenEvent::OpenTaskInput)),
-        Action::FocusTasks => app.enqueue_event(AppEvent::Main(MainScreenEvent::FocusTasks)),
-        Action::FocusDetail => app.enqueue_event(AppEvent::Main(MainScreenEvent::FocusDetail)),
-        Action::FocusInput => app.enqueue_event(AppEvent::Main(MainScreenEvent::FocusInput)),
-        Action::NextTask => app.enqueue_event(AppEvent::Main(MainScreenEvent::SelectTaskNext)),
-        Action::PrevTask => app.enqueue_event(AppEvent::Main(MainScreenEvent::SelectTaskPrev)),
-        Action::CancelTask => {
-            app.enqueue_event(AppEvent::Main(MainScreenEvent::CancelSelectedTask))
-        }
+        Action::Quit => AppEvent::Main(MainScreenEvent::Quit),
+        Action::OpenTaskInput => AppEvent::Main(MainScreenEvent::OpenTaskInput),
+        Action::FocusTasks => AppEvent::Main(MainScreenEvent::FocusTasks),
+        Action::FocusDetail => AppEvent::Main(MainScreenEvent::FocusDetail),
+        Action::FocusInput => AppEvent::Main(MainScreenEvent::FocusInput),
+        Action::NextTask => AppEvent::Main(MainScreenEvent::SelectTaskNext),
+        Action::PrevTask => AppEvent::Main(MainScreenEvent::SelectTaskPrev),
+        Action::CancelTask => AppEvent::Main(MainScreenEvent::CancelSelectedTask),
+        Action::ExportReport => AppEvent::Main(MainScreenEvent::ExportSelectedTask),
+        Action::FocusLogs => AppEvent::Main(MainScreenEvent::FocusLogs),
+        Action::SearchLogs => AppEvent::Main(MainScreenEvent::StartLogSearch),
+        Action::ClearLogSearch => AppEvent::Main(MainScreenEvent::ClearLogSearch),
+        Action::LogsPageUp => AppEvent::Main(MainScreenEvent::ScrollLogs(LOG_PAGE)),
+        Action::LogsPageDown => AppEvent::Main(MainScreenEvent::ScrollLogs(-LOG_PAGE)),
+        Action::LogsTop => AppEvent::Main(MainScreenEvent::ScrollLogsTop),
+        Action::LogsBottom => AppEvent::Main(MainScreenEvent::ScrollLogsBottom),
+        Action::OpenHypotheses => AppEvent::Main(MainScreenEvent::OpenHypotheses),
+        Action::SearchTasks => AppEvent::Main(MainScreenEvent::StartTaskSearch),
+        Action::CycleFilter => AppEvent::Main(MainScreenEvent::CycleTaskFilter),
+        Action::CycleSort => AppEvent::Main(MainScreenEvent::CycleTaskSort),
+        Action::CycleGrouping => AppEvent::Main(MainScreenEvent::CycleTaskGrouping),
     }
+}
+
+/// While the log search prompt is open every key edits the query.
+fn handle_search_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let event = match key.code {
+        KeyCode::Enter => MainScreenEvent::EndLogSearch,
+        KeyCode::Esc => MainScreenEvent::ClearLogSearch,
+        _ => match keymap::text_edit_event(&key) {
+            Some(edit) => MainScreenEvent::LogSearch(edit),
+            None => return Ok(false),
+        },
+    };
+    app.enqueue_event(AppEvent::Main(event));
+    Ok(false)
+}
+
+/// While the task search prompt is open every key edits the name query.
+fn handle_task_search_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let event = match key.code {
+        KeyCode::Enter => MainScreenEvent::EndTaskSearch,
+        KeyCode::Esc => MainScreenEvent::ClearTaskSearch,
+        _ => match keymap::text_edit_event(&key) {
+            Some(edit) => MainScreenEvent::TaskSearch(edit),
+            None => return Ok(false),
+        },
+    };
+    app.enqueue_event(AppEvent::Main(event));
+    Ok(false)
+}
+
+/// While the command line is focused every key goes to it.
+fn handle_command_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let event = match key.code {
+        KeyCode::Enter => CommandLineEvent::Submit,
+        KeyCode::Esc => CommandLineEvent::Leave,
+        KeyCode::Tab => CommandLineEvent::Complete,
+        KeyCode::Up => CommandLineEvent::HistoryPrev,
+        KeyCode::Down => CommandLineEvent::HistoryNext,
+        _ => match keymap::text_edit_event(&key) {
+            Some(edit) => CommandLineEvent::Edit(edit),
+            None => return Ok(false),
+        },
+    };
+    app.enqueue_event(AppEvent::Main(MainScreenEvent::CommandLine(event)));
     Ok(false)
 }
 
 pub fn draw(frame: &mut Frame, app: &AppState) {
+    let area = frame.size();
+    let bar = bar_height(area);
     let root = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
+            Constraint::Length(bar),
             Constraint::Min(8),
             Constraint::Length(3),
-            Constraint::Length(3),
+            Constraint::Length(bar),
         ])
-        .split(frame.size());
+        .split(area);
 
-    fragments::header::draw(frame, root[0]);
+    fragments::header::draw(frame, root[0], app);
 
-    let main = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
-        .split(root[1]);
-    fragments::tasks::draw(frame, main[0], app);
-    fragments::detail::draw(frame, main[1], app);
+    // Narrow terminals stack the panes: tasks, then details, then logs.
+    let (tasks, logs, detail) = if is_narrow(area) {
+        let panes = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+                Constraint::Percentage(25),
+            ])
+            .split(root[1]);
+        (panes[0], panes[2], panes[1])
+    } else {
+        let main = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(root[1]);
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(main[0]);
+        (left[0], left[1], main[1])
+    };
+    fragments::tasks::draw(frame, tasks, app);
+    fragments::logs::draw(frame, logs, app);
+    fragments::detail::draw(frame, detail, app);
 
-    fragments::input::draw(frame, root[2], app);
-    fragments::help::draw(frame, root[3]);
+    let cursor = fragments::input::draw(frame, root[2], app);
+    if let Some((x, y)) = cursor
+        && app.cursor_visible
+    {
+        frame.set_cursor(x, y);
+    }
+    fragments::help::draw(frame, root[3], app);
 }
@@ -1,15 +1,22 @@
 pub mod common;
+pub mod dialog;
+pub mod hypotheses;
+pub mod keymap;
 pub mod main;
+pub mod mouse;
 pub mod task_input;
+pub mod theme;
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::Frame;
 use crate::app::AppState;
+use crate::screens::keymap::KeyChord;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ScreenId {
     Main,
     TaskInput,
+    Hypotheses,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -17,13 +24,15 @@ pub enum FragmentId {
     MainTasks,
     MainDetail,
     MainInput,
+    MainLogs,
     TaskDescription,
     TaskHypotheses,
+    HypothesisList,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct KeyBinding<A: Copy> {
-    pub key: KeyCode,
+    pub key: KeyChord,
     pub action: A,
 }
 
@@ -31,28 +40,60 @@ pub trait Screen {
     fn draw(&self, frame: &mut Frame, app: &AppState);
     fn handle_key(
         &self,
-        key: KeyCode,
+        key: KeyEvent,
         app: &mut AppState,
     ) -> std::io::Result<bool>;
+    /// Handles bracketed-paste text, which arrives as one event.
+    fn handle_paste(&self, text: &str, app: &mut AppState);
 }
 
 static MAIN_SCREEN: main::MainScreen = main::MainScreen;
 static TASK_INPUT_SCREEN: task_input::TaskInputScreen = task_input::TaskInputScreen;
+static HYPOTHESES_SCREEN: hypotheses::HypothesesScreen = hypotheses::HypothesesScreen;
 
 fn current_screen(app: &AppState) -> &'static dyn Screen {
     match app.screen {
         ScreenId::Main => &MAIN_SCREEN,
         ScreenId::TaskInput => &TASK_INPUT_SCREEN,
+        ScreenId::Hypotheses => &HYPOTHESES_SCREEN,
     }
 }
 
+/// Sends a key to the open dialog, or else to the current screen.
 pub fn dispatch_key(
-    key: KeyCode,
+    key: KeyEvent,
     app: &mut AppState,
 ) -> std::io::Result<bool> {
+    if !app.dialogs.is_empty() {
+        return dialog::handle_key(key, app);
+    }
     current_screen(app).handle_key(key, app)
 }
 
+pub fn dispatch_paste(text: &str, app: &mut AppState) {
+    if !app.dialogs.is_empty() {
+        dialog::handle_paste(text, app);
+        return;
+    }
+    current_screen(app).handle_paste(text, app);
+}
+
+/// Sends the events of the hotspot under the pointer, as drawn last frame.
+pub fn dispatch_mouse(event: MouseEvent, app: &mut AppState) {
+    for event in app.mouse.events(&event) {
+        app.enqueue_event(event);
+    }
+}
+
 pub fn draw(frame: &mut Frame, app: &AppState) {
-    current_screen(app).draw(frame, app);
+    app.mouse.clear();
+    let area = frame.size();
+    if area.width < common::MIN_WIDTH || area.height < common::MIN_HEIGHT {
+        common::draw_too_small(frame, area, app);
+    } else {
+        current_screen(app).draw(frame, app);
+    }
+    if let Some((top, waiting)) = app.dialogs.split_last() {
+        dialog::draw(frame, area, top, waiting.len(), app);
+    }
 }
@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+use crate::app::AppEvent;
+use crate::screens::keymap::HelpButton;
+
+/// Log lines scrolled per wheel notch.
+pub const WHEEL_LINES: isize = 3;
+
+/// A screen region and the events a left click or wheel turn over it sends.
+#[derive(Debug, Clone, Default)]
+pub struct Hotspot {
+    pub area: Rect,
+    pub click: Vec<AppEvent>,
+    pub scroll_up: Vec<AppEvent>,
+    pub scroll_down: Vec<AppEvent>,
+}
+
+/// Hotspots registered by the fragments while drawing the last frame. Later
+/// hotspots sit on top of earlier ones, and a popup clears the ones below it.
+#[derive(Debug, Default)]
+pub struct MouseMap {
+    hotspots: RefCell<Vec<Hotspot>>,
+}
+
+impl MouseMap {
+    pub fn clear(&self) {
+        self.hotspots.borrow_mut().clear();
+    }
+
+    pub fn add(&self, hotspot: Hotspot) {
+        self.hotspots.borrow_mut().push(hotspot);
+    }
+
+    pub fn on_click(&self, area: Rect, click: Vec<AppEvent>) {
+        self.add(Hotspot {
+            area,
+            click,
+            ..Hotspot::default()
+        });
+    }
+
+    pub fn on_scroll(&self, area: Rect, scroll_up: Vec<AppEvent>, scroll_down: Vec<AppEvent>) {
+        self.add(Hotspot {
+            area,
+            scroll_up,
+            scroll_down,
+            ..Hotspot::default()
+        });
+    }
+
+    /// Makes the buttons of a help bar drawn on the first line of `area`
+    /// clickable.
+    pub fn on_help_buttons<A: Copy>(
+        &self,
+        area: Rect,
+        buttons: &[HelpButton<A>],
+        event: impl Fn(A) -> AppEvent,
+    ) {
+        for (columns, action) in buttons {
+            let x = area.x.saturating_add(columns.start);
+            if x >= area.right() {
+                break;
+            }
+            let width = (columns.end - columns.start).min(area.right() - x);
+            self.on_click(Rect::new(x, area.y, width, 1), vec![event(*action)]);
+        }
+    }
+
+    /// The events of the topmost hotspot under the pointer that handles this
+    /// kind of mouse event.
+    pub fn events(&self, event: &MouseEvent) -> Vec<AppEvent> {
+        let pick: fn(&Hotspot) -> &Vec<AppEvent> = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => |hotspot| &hotspot.click,
+            MouseEventKind::ScrollUp => |hotspot| &hotspot.scroll_up,
+            MouseEventKind::ScrollDown => |hotspot| &hotspot.scroll_down,
+            _ => return Vec::new(),
+        };
+        self.hotspots
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|hotspot| contains(hotspot.area, event.column, event.row))
+            .map(pick)
+            .find(|events| !events.is_empty())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn contains(area: Rect, x: u16, y: u16) -> bool {
+    x >= area.x && x < area.right() && y >= area.y && y < area.bottom()
+}
+
+/// One-line rows of a bordered table or list, from its first data row, for
+/// the items `offset..len`.
+pub fn row_areas(area: Rect, header_rows: u16, offset: usize, len: usize) -> Vec<(usize, Rect)> {
+    let inner = Rect::new(
+        area.x + 1,
+        area.y + 1 + header_rows,
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2 + header_rows),
+    );
+    (offset..len)
+        .zip(inner.y..inner.bottom())
+        .map(|(index, y)| (index, Rect::new(inner.x, y, inner.width, 1)))
+        .collect()
+}
@@ -1,42 +1,75 @@
+use std::path::Path;
+
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{AppState, DraftField, HeuristicsFocus};
+use crate::app::{
+    AppEvent, AppState, DraftField, HeuristicsFocus, TaskDraft, TaskInputEvent, TextEditEvent,
+};
 use crate::screens::FragmentId;
-use crate::screens::common::selected_list_style;
-use crate::ui::{dashed_border_set, truncate};
+use crate::screens::common::{draw_image_preview, is_narrow};
+use crate::screens::mouse::row_areas;
+use crate::spec::SpecField;
+use crate::ui::truncate;
 
 pub fn draw(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
     let is_active = app.fragment == FragmentId::TaskDescription;
-    let title_style = if is_active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let (border_set, border_style) = if is_active {
-        (dashed_border_set(), Style::default().fg(Color::Green))
-    } else {
-        (ratatui::symbols::border::PLAIN, Style::default())
-    };
+    let title_style = app.theme.focus_title(is_active);
+    let (border_set, border_style) = app.theme.focus_border(is_active);
     let block = Block::default()
         .borders(Borders::ALL)
         .border_set(border_set)
         .border_style(border_style)
         .title(Span::styled("Task Description [F1]", title_style));
     frame.render_widget(block.clone(), area);
+    app.mouse.on_click(area, vec![AppEvent::TaskInput(TaskInputEvent::FocusDescription)]);
 
     let inner = block.inner(area);
-    let columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(32),
-            Constraint::Percentage(32),
-            Constraint::Percentage(36),
-        ])
+    let issues = issue_lines(app);
+    let issue_rows = issues.len() as u16;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(issue_rows)])
         .split(inner);
+    // Narrow terminals put Name and Dataset above Heuristics and Preview.
+    let columns: Vec<Rect> = if is_narrow(area) {
+        let stacked = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)])
+            .split(rows[0]);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(stacked[0]);
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(stacked[1]);
+        vec![top[0], top[1], bottom[0], bottom[1]]
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(24),
+                Constraint::Percentage(24),
+                Constraint::Percentage(34),
+                Constraint::Percentage(18),
+            ])
+            .split(rows[0])
+            .to_vec()
+    };
+    frame.render_widget(Paragraph::new(issues), rows[1]);
+    draw_image_preview(
+        frame,
+        columns[3],
+        "Preview",
+        app.selected_draft_image().filter(|image| !image.trim().is_empty()),
+        "Select a heuristic image to preview it.",
+        app,
+    );
 
     let name_cursor = draw_name_box(frame, columns[0], app);
     let dataset_cursor = draw_dataset_box(frame, columns[1], app);
@@ -44,15 +77,54 @@ pub fn draw(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)>
     name_cursor.or(dataset_cursor).or(heuristics_cursor)
 }
 
-fn draw_name_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
-    let active = app.fragment == FragmentId::TaskDescription && app.draft.field == DraftField::Name;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+/// Issue lines shown under the boxes; the last one summarizes the rest.
+const MAX_ISSUE_ROWS: usize = 4;
+
+/// Errors in red, then warnings with a hint when they are all that is left.
+fn issue_lines(app: &AppState) -> Vec<Line<'static>> {
+    let draft = &app.draft;
+    let (errors, warnings): (Vec<_>, Vec<_>) =
+        draft.issues.iter().partition(|issue| TaskDraft::can_edit(issue.field));
+    let mut lines = errors
+        .iter()
+        .map(|issue| Line::from(Span::styled(format!("✗ {}", issue.message), app.theme.error())))
+        .chain(warnings.iter().map(|issue| {
+            Line::from(Span::styled(format!("! {}", issue.message), app.theme.warning()))
+        }))
+        .collect::<Vec<_>>();
+    let hint = errors.is_empty() && !warnings.is_empty();
+    let room = MAX_ISSUE_ROWS - usize::from(hint);
+    if lines.len() > room {
+        let hidden = lines.len() - (room - 1);
+        lines.truncate(room - 1);
+        let style = if errors.is_empty() { app.theme.warning() } else { app.theme.error() };
+        lines.push(Line::from(Span::styled(format!("  … and {hidden} more"), style)));
+    }
+    if hint {
+        lines.push(Line::from(Span::styled(
+            "  Submit again to run anyway.",
+            app.theme.warning(),
+        )));
+    }
+    lines
+}
+
+/// Active boxes stay green; boxes with validation issues get a red title.
+fn box_title_style(active: bool, app: &AppState, matches: impl Fn(SpecField) -> bool) -> Style {
+    if active {
+        app.theme.focus_title(true)
+    } else if app.draft.issues.iter().any(|issue| matches(issue.field)) {
+        app.theme.error()
     } else {
         Style::default()
-    };
+    }
+}
+
+fn draw_name_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
+    let active = app.fragment == FragmentId::TaskDescription && app.draft.field == DraftField::Name;
+    let title_style = box_title_style(active, app, |field| field == SpecField::Name);
     let value = if app.draft.field == DraftField::Name {
-        app.input.clone()
+        app.input.as_str().to_string()
     } else {
         app.draft.name.clone()
     };
@@ -60,8 +132,9 @@ fn draw_name_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16,
         .borders(Borders::ALL)
         .title(Span::styled("Name", title_style));
     frame.render_widget(Paragraph::new(value).block(block), area);
+    app.mouse.on_click(area, vec![focus_field(DraftField::Name)]);
     if active {
-        let x = area.x + 1 + app.cursor_pos.min(area.width.saturating_sub(2) as usize) as u16;
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
         let y = area.y + 1;
         return Some((x, y));
     }
@@ -71,22 +144,24 @@ fn draw_name_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16,
 fn draw_dataset_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
     let active =
         app.fragment == FragmentId::TaskDescription && app.draft.field == DraftField::DatasetFolder;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let value = if app.draft.field == DraftField::DatasetFolder {
-        app.input.clone()
+    let title_style = box_title_style(active, app, |field| {
+        matches!(field, SpecField::DatasetFolder | SpecField::Labels)
+    });
+    let value = app.draft_dataset_folder().to_string();
+    let (title, value_style) = if !app.dataset_folder_valid(&value) {
+        ("Dataset Folder (missing)", app.theme.error())
+    } else if !Path::new(&value).is_dir() {
+        ("Dataset Folder (mock dataset)", app.theme.muted())
     } else {
-        app.draft.dataset_folder.clone()
+        ("Dataset Folder [F6]", Style::default())
     };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled("Dataset Folder", title_style));
-    frame.render_widget(Paragraph::new(value).block(block), area);
+        .title(Span::styled(title, title_style));
+    frame.render_widget(Paragraph::new(value).style(value_style).block(block), area);
+    app.mouse.on_click(area, vec![focus_field(DraftField::DatasetFolder)]);
     if active {
-        let x = area.x + 1 + app.cursor_pos.min(area.width.saturating_sub(2) as usize) as u16;
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
         let y = area.y + 1;
         return Some((x, y));
     }
@@ -96,15 +171,14 @@ fn draw_dataset_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u1
 fn draw_heuristics_box(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
     let active =
         app.fragment == FragmentId::TaskDescription && app.draft.field == DraftField::Heuristics;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    let title_style = box_title_style(active, app, |field| {
+        matches!(field, SpecField::Heuristic(_) | SpecField::Image { .. })
+    });
     let block = Block::default()
         .borders(Borders::ALL)
         .title(Span::styled("Heuristics [+ to add]", title_style));
     frame.render_widget(block.clone(), area);
+    app.mouse.on_click(area, vec![focus_field(DraftField::Heuristics)]);
 
     let inner = block.inner(area);
     let columns = Layout::default()
@@ -121,27 +195,23 @@ fn draw_heuristic_titles(frame: &mut Frame, area: Rect, app: &AppState) -> Optio
     let active = app.fragment == FragmentId::TaskDescription
         && app.draft.field == DraftField::Heuristics
         && app.draft.heuristics_focus == HeuristicsFocus::Titles;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    let title_style = app.theme.focus_title(active);
     let mut items = Vec::new();
     if app.draft.heuristics.is_empty() {
         items.push(ListItem::new(Span::styled(
             "[Empty List]",
-            Style::default().fg(Color::DarkGray),
+            app.theme.muted(),
         )));
     } else {
         for (idx, heuristic) in app.draft.heuristics.iter().enumerate() {
             let selected = idx == app.draft.selected_heuristic;
             let style = if active && selected {
-                selected_list_style()
+                app.theme.selected()
             } else {
                 Style::default()
             };
             items.push(ListItem::new(Span::styled(
-                truncate(&heuristic.title, 24),
+                truncate(heuristic.title.as_str(), 24),
                 style,
             )));
         }
@@ -150,8 +220,9 @@ fn draw_heuristic_titles(frame: &mut Frame, area: Rect, app: &AppState) -> Optio
         .borders(Borders::ALL)
         .title(Span::styled("Titles", title_style));
     frame.render_widget(List::new(items).block(block), area);
+    list_hotspots(app, area, HeuristicsFocus::Titles, app.draft.heuristics.len());
     if active && !app.draft.heuristics.is_empty() {
-        let x = area.x + 1 + app.cursor_pos.min(area.width.saturating_sub(2) as usize) as u16;
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
         let y = area.y + 1 + app.draft.selected_heuristic as u16;
         return Some((x, y));
     }
@@ -162,11 +233,7 @@ fn draw_heuristic_images(frame: &mut Frame, area: Rect, app: &AppState) -> Optio
     let active = app.fragment == FragmentId::TaskDescription
         && app.draft.field == DraftField::Heuristics
         && app.draft.heuristics_focus == HeuristicsFocus::Images;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+    let title_style = app.theme.focus_title(active);
     let images = app
         .draft
         .heuristics
@@ -177,27 +244,47 @@ fn draw_heuristic_images(frame: &mut Frame, area: Rect, app: &AppState) -> Optio
     if images.is_empty() {
         items.push(ListItem::new(Span::styled(
             "[Empty List]",
-            Style::default().fg(Color::DarkGray),
+            app.theme.muted(),
         )));
     } else {
         for (idx, image) in images.iter().enumerate() {
             let selected = idx == app.draft.selected_image;
-            let style = if active && selected {
-                selected_list_style()
-            } else {
-                Style::default()
+            let valid = AppState::image_path_valid(image.as_str());
+            let style = match (active && selected, valid) {
+                (true, true) => app.theme.selected(),
+                (true, false) => app.theme.selected_error(),
+                (false, true) => Style::default(),
+                (false, false) => app.theme.error(),
             };
-            items.push(ListItem::new(Span::styled(truncate(image, 24), style)));
+            items.push(ListItem::new(Span::styled(truncate(image.as_str(), 24), style)));
         }
     }
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(Span::styled("Images (Right arrow)", title_style));
+        .title(Span::styled("Images (Right arrow, F6 browse)", title_style));
     frame.render_widget(List::new(items).block(block), area);
+    list_hotspots(app, area, HeuristicsFocus::Images, images.len());
     if active && !images.is_empty() {
-        let x = area.x + 1 + app.cursor_pos.min(area.width.saturating_sub(2) as usize) as u16;
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
         let y = area.y + 1 + app.draft.selected_image as u16;
         return Some((x, y));
     }
     None
 }
+
+fn focus_field(field: DraftField) -> AppEvent {
+    AppEvent::TaskInput(TaskInputEvent::FocusField(field))
+}
+
+/// Clicking a heuristic title or image selects it; the wheel moves through
+/// the list.
+fn list_hotspots(app: &AppState, area: Rect, list: HeuristicsFocus, len: usize) {
+    let focus = AppEvent::TaskInput(TaskInputEvent::FocusHeuristicList(list));
+    let step = |edit| vec![focus.clone(), AppEvent::TaskInput(TaskInputEvent::Edit(edit))];
+    app.mouse.on_click(area, vec![focus.clone()]);
+    app.mouse.on_scroll(area, step(TextEditEvent::MoveUp), step(TextEditEvent::MoveDown));
+    for (index, row) in row_areas(area, 0, 0, len) {
+        let select = AppEvent::TaskInput(TaskInputEvent::SelectItem(index));
+        app.mouse.on_click(row, vec![focus.clone(), select]);
+    }
+}
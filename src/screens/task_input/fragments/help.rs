@@ -1,25 +1,14 @@
-use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect) {
-    let help = Line::from(vec![
-        Span::styled("F1/F2", Style::default().fg(Color::Yellow)),
-        Span::raw(" switch fragment  "),
-        Span::styled("Tab", Style::default().fg(Color::Yellow)),
-        Span::raw(" switch field  "),
-        Span::styled("+", Style::default().fg(Color::Yellow)),
-        Span::raw(" add heuristic  "),
-        Span::styled("Right/Left", Style::default().fg(Color::Yellow)),
-        Span::raw(" images/titles  "),
-        Span::styled("Up/Down", Style::default().fg(Color::Yellow)),
-        Span::raw(" move lists  "),
-        Span::styled("Enter", Style::default().fg(Color::Yellow)),
-        Span::raw(" add image (images) / submit (form)  "),
-        Span::styled("Esc", Style::default().fg(Color::Yellow)),
-        Span::raw(" cancel"),
-    ]);
-    let block = Block::default().borders(Borders::ALL).title("Task Input Controls");
+use crate::app::AppState;
+use crate::screens::common::bar_block;
+use crate::screens::keymap::help_bar;
+use crate::screens::task_input::{action_event, HELP};
+
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+    let (help, buttons) = help_bar(&app.keymaps.task_input, HELP, app.theme.key());
+    let block = bar_block(area, "Task Input Controls");
+    app.mouse.on_help_buttons(block.inner(area), &buttons, action_event);
     frame.render_widget(Paragraph::new(help).block(block), area);
 }
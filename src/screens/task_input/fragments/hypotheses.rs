@@ -1,26 +1,19 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders, List, ListItem};
 use ratatui::Frame;
 
-use crate::app::AppState;
-use crate::screens::common::selected_list_style;
+use crate::app::{AppEvent, AppState, TaskInputEvent, TextEditEvent};
+use crate::screens::mouse::row_areas;
 use crate::screens::FragmentId;
 use crate::ui::truncate;
 
-pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
+/// Seed hypotheses of the draft; returns the cursor position while focused.
+pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) -> Option<(u16, u16)> {
     let active = app.fragment == FragmentId::TaskHypotheses;
-    let title_style = if active {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
-    let (border_set, border_style) = if active {
-        (crate::ui::dashed_border_set(), Style::default().fg(Color::Green))
-    } else {
-        (ratatui::symbols::border::PLAIN, Style::default())
-    };
-    let hypothesis_items = app
+    let title_style = app.theme.focus_title(active);
+    let (border_set, border_style) = app.theme.focus_border(active);
+    let mut hypothesis_items = app
         .draft
         .hypotheses
         .iter()
@@ -28,21 +21,41 @@ pub fn draw(frame: &mut Frame, area: ratatui::layout::Rect, app: &AppState) {
         .map(|(idx, h)| {
             let selected = idx == app.draft.selected_hypothesis;
             let style = if active && selected {
-                selected_list_style()
+                app.theme.selected()
             } else {
                 Style::default()
             };
-            ListItem::new(Span::styled(truncate(&h.title, 60), style))
+            ListItem::new(Span::styled(truncate(h.title.as_str(), 60), style))
         })
         .collect::<Vec<_>>();
+    if hypothesis_items.is_empty() {
+        hypothesis_items.push(ListItem::new(Span::styled(
+            "[Empty List]",
+            app.theme.muted(),
+        )));
+    }
 
     let hypothesis_list = List::new(hypothesis_items).block(
         Block::default()
             .borders(Borders::ALL)
             .border_set(border_set)
             .border_style(border_style)
-            .title(Span::styled("Hypotheses [F2]", title_style)),
+            .title(Span::styled("Seed Hypotheses [F2] [+ to add]", title_style)),
     );
 
     frame.render_widget(hypothesis_list, area);
+    let focus = AppEvent::TaskInput(TaskInputEvent::FocusHypotheses);
+    let step = |edit| vec![focus.clone(), AppEvent::TaskInput(TaskInputEvent::Edit(edit))];
+    app.mouse.on_click(area, vec![focus.clone()]);
+    app.mouse.on_scroll(area, step(TextEditEvent::MoveUp), step(TextEditEvent::MoveDown));
+    for (index, row) in row_areas(area, 0, 0, app.draft.hypotheses.len()) {
+        let select = AppEvent::TaskInput(TaskInputEvent::SelectItem(index));
+        app.mouse.on_click(row, vec![focus.clone(), select]);
+    }
+    if active && !app.draft.hypotheses.is_empty() {
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
+        let y = area.y + 1 + app.draft.selected_hypothesis as u16;
+        return Some((x, y));
+    }
+    None
 }
@@ -2,3 +2,5 @@ pub mod description;
 pub mod header;
 pub mod help;
 pub mod hypotheses;
+pub mod path_picker;
+pub mod spec;
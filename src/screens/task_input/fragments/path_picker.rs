@@ -0,0 +1,90 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use unicode_width::UnicodeWidthStr;
+
+use crate::path_picker::{EntryKind, PathPicker, PathTarget};
+use crate::screens::theme::Theme;
+use crate::ui::dashed_border_set;
+
+/// Popup over `area`; returns the cursor position in the filter line.
+pub fn draw(frame: &mut Frame, area: Rect, picker: &PathPicker, theme: &Theme) -> (u16, u16) {
+    let popup = centered(area, 70, 70);
+    frame.render_widget(Clear, popup);
+    let what = match picker.target {
+        PathTarget::DatasetFolder => "dataset folder",
+        PathTarget::Image => "image",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(dashed_border_set())
+        .border_style(theme.popup())
+        .title(Span::styled(
+            format!("Choose {what} [Enter open/choose  Tab complete  Esc close]"),
+            theme.popup_title(),
+        ));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            picker.dir.to_string_lossy().to_string(),
+            theme.accent(),
+        )),
+        rows[0],
+    );
+    let filter = Line::from(vec![
+        Span::styled("Filter: ", theme.muted()),
+        Span::raw(picker.filter.clone()),
+    ]);
+    frame.render_widget(Paragraph::new(filter), rows[1]);
+
+    let mut items = picker
+        .visible()
+        .into_iter()
+        .map(|entry| {
+            let (label, style) = match entry.kind {
+                EntryKind::Current => ("[select this folder]".to_string(), theme.warning()),
+                EntryKind::Parent => ("../".to_string(), theme.accent()),
+                EntryKind::Dir => (format!("{}/", entry.name), theme.accent()),
+                EntryKind::File if entry.is_image => (entry.name.clone(), theme.success()),
+                EntryKind::File => (entry.name.clone(), theme.muted()),
+            };
+            ListItem::new(Span::styled(label, style))
+        })
+        .collect::<Vec<_>>();
+    if let Some(error) = &picker.error {
+        items.push(ListItem::new(Span::styled(error.clone(), theme.error())));
+    } else if items.is_empty() {
+        items.push(ListItem::new(Span::styled(
+            "[No matches]",
+            theme.muted(),
+        )));
+    }
+    let mut state = ListState::default();
+    state.select(Some(picker.selected));
+    frame.render_stateful_widget(
+        List::new(items).highlight_style(theme.selected()),
+        rows[2],
+        &mut state,
+    );
+
+    let cursor_x = rows[1].x + "Filter: ".len() as u16 + picker.filter.width() as u16;
+    (cursor_x.min(rows[1].right().saturating_sub(1)), rows[1].y)
+}
+
+fn centered(area: Rect, width_percent: u16, height_percent: u16) -> Rect {
+    let width = area.width * width_percent / 100;
+    let height = area.height * height_percent / 100;
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
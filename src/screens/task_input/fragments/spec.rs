@@ -0,0 +1,36 @@
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{AppEvent, AppState, DraftField, TaskInputEvent};
+use crate::screens::FragmentId;
+
+pub fn draw(frame: &mut Frame, area: Rect, app: &AppState) -> Option<(u16, u16)> {
+    let active = app.fragment == FragmentId::TaskDescription && app.draft.field == DraftField::SpecPath;
+    let title_style = app.theme.focus_title(active);
+    let path = if active {
+        app.input.as_str().to_string()
+    } else {
+        app.draft.spec_path.clone()
+    };
+    let mut spans = vec![Span::raw(path)];
+    if let Some(status) = &app.draft.spec_status {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(status.clone(), app.theme.muted()));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled("Spec File [F3 open / F4 save]", title_style));
+    frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    app.mouse.on_click(
+        area,
+        vec![AppEvent::TaskInput(TaskInputEvent::FocusField(DraftField::SpecPath))],
+    );
+    if active {
+        let x = area.x + 1 + app.cursor_column().min(area.width.saturating_sub(2) as usize) as u16;
+        let y = area.y + 1;
+        return Some((x, y));
+    }
+    None
+}
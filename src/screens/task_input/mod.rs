@@ -1,70 +1,167 @@
 pub mod fragments;
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Frame;
 
-use crate::app::{AppState, TaskInputEvent, TextEditEvent};
+use crate::app::{AppEvent, AppState, PathPickerEvent, TaskInputEvent, TextEditEvent};
+use crate::screens::keymap::{self, BindableAction, HelpEntry, KeyChord};
+use crate::screens::common::{bar_height, is_narrow};
 use crate::screens::{KeyBinding, Screen};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Close,
     SwitchField,
     FocusDescription,
     FocusHypotheses,
+    OpenSpec,
+    SaveSpec,
+    OpenPathPicker,
     CursorLeft,
     CursorRight,
     MoveUp,
     MoveDown,
     AddHeuristic,
+    DeleteItem,
+    DuplicateItem,
+    MoveItemUp,
+    MoveItemDown,
     Submit,
     Backspace,
 }
 
-const KEY_BINDINGS: &[KeyBinding<Action>] = &[
+impl BindableAction for Action {
+    const ALL: &'static [Self] = &[
+        Action::Close,
+        Action::SwitchField,
+        Action::FocusDescription,
+        Action::FocusHypotheses,
+        Action::OpenSpec,
+        Action::SaveSpec,
+        Action::OpenPathPicker,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::AddHeuristic,
+        Action::DeleteItem,
+        Action::DuplicateItem,
+        Action::MoveItemUp,
+        Action::MoveItemDown,
+        Action::Submit,
+        Action::Backspace,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Action::Close => "close",
+            Action::SwitchField => "switch_field",
+            Action::FocusDescription => "focus_description",
+            Action::FocusHypotheses => "focus_hypotheses",
+            Action::OpenSpec => "open_spec",
+            Action::SaveSpec => "save_spec",
+            Action::OpenPathPicker => "open_path_picker",
+            Action::CursorLeft => "cursor_left",
+            Action::CursorRight => "cursor_right",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::AddHeuristic => "add_item",
+            Action::DeleteItem => "delete_item",
+            Action::DuplicateItem => "duplicate_item",
+            Action::MoveItemUp => "move_item_up",
+            Action::MoveItemDown => "move_item_down",
+            Action::Submit => "submit",
+            Action::Backspace => "backspace",
+        }
+    }
+}
+
+pub const HELP: &[HelpEntry<Action>] = &[
+    (&[Action::FocusDescription, Action::FocusHypotheses], "switch fragment"),
+    (&[Action::SwitchField], "switch field"),
+    (&[Action::OpenSpec, Action::SaveSpec], "open/save spec"),
+    (&[Action::OpenPathPicker], "browse path"),
+    (&[Action::AddHeuristic], "add heuristic/seed"),
+    (&[Action::DeleteItem, Action::DuplicateItem], "delete/duplicate"),
+    (&[Action::MoveItemUp, Action::MoveItemDown], "move item"),
+    (&[Action::CursorRight, Action::CursorLeft], "images/titles"),
+    (&[Action::MoveUp, Action::MoveDown], "move lists"),
+    (&[Action::Submit], "add image (images) / submit (form)"),
+    (&[Action::Close], "cancel"),
+];
+
+pub const KEY_BINDINGS: &[KeyBinding<Action>] = &[
     KeyBinding {
-        key: KeyCode::Esc,
+        key: KeyChord::plain(KeyCode::Esc),
         action: Action::Close,
     },
     KeyBinding {
-        key: KeyCode::Tab,
+        key: KeyChord::plain(KeyCode::Tab),
         action: Action::SwitchField,
     },
     KeyBinding {
-        key: KeyCode::F(1),
+        key: KeyChord::plain(KeyCode::F(1)),
         action: Action::FocusDescription,
     },
     KeyBinding {
-        key: KeyCode::F(2),
+        key: KeyChord::plain(KeyCode::F(2)),
         action: Action::FocusHypotheses,
     },
     KeyBinding {
-        key: KeyCode::Left,
+        key: KeyChord::plain(KeyCode::F(3)),
+        action: Action::OpenSpec,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::F(4)),
+        action: Action::SaveSpec,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::F(6)),
+        action: Action::OpenPathPicker,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Left),
         action: Action::CursorLeft,
     },
     KeyBinding {
-        key: KeyCode::Right,
+        key: KeyChord::plain(KeyCode::Right),
         action: Action::CursorRight,
     },
     KeyBinding {
-        key: KeyCode::Up,
+        key: KeyChord::plain(KeyCode::Up),
         action: Action::MoveUp,
     },
     KeyBinding {
-        key: KeyCode::Down,
+        key: KeyChord::plain(KeyCode::Down),
         action: Action::MoveDown,
     },
     KeyBinding {
-        key: KeyCode::Char('+'),
+        key: KeyChord::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
         action: Action::AddHeuristic,
     },
     KeyBinding {
-        key: KeyCode::Enter,
+        key: KeyChord::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+        action: Action::DeleteItem,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::F(5)),
+        action: Action::DuplicateItem,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::PageUp),
+        action: Action::MoveItemUp,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::PageDown),
+        action: Action::MoveItemDown,
+    },
+    KeyBinding {
+        key: KeyChord::plain(KeyCode::Enter),
         action: Action::Submit,
     },
     KeyBinding {
-        key: KeyCode::Backspace,
+        key: KeyChord::plain(KeyCode::Backspace),
         action: Action::Backspace,
     },
 ];
@@ -78,67 +175,128 @@ impl Screen for TaskInputScreen {
 
     fn handle_key(
         &self,
-        key: KeyCode,
+        key: KeyEvent,
         app: &mut AppState,
     ) -> std::io::Result<bool> {
         handle_key(key, app)
     }
+
+    fn handle_paste(&self, text: &str, app: &mut AppState) {
+        handle_paste(text, app);
+    }
 }
 
 pub fn handle_key(
-    key: KeyCode,
+    key: KeyEvent,
     app: &mut AppState,
 ) -> std::io::Result<bool> {
-    if let Some(binding) = KEY_BINDINGS.iter().find(|b| b.key == key) {
-        return handle_action(binding.action, app);
+    if app.path_picker.is_some() {
+        return handle_picker_key(key, app);
+    }
+    // Every field is text, so typed characters win over bindings.
+    if let Some(edit @ TextEditEvent::InsertChar(_)) = keymap::text_edit_event(&key) {
+        app.enqueue_event(AppEvent::TaskInput(TaskInputEvent::Edit(edit)));
+        return Ok(false);
     }
-    if let KeyCode::Char(ch) = key {
-        app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(
-            TextEditEvent::InsertChar(ch),
-        )));
+    if let Some(action) = keymap::lookup(&app.keymaps.task_input, &key) {
+        app.enqueue_event(action_event(action));
         return Ok(false);
     }
+    if let Some(edit) = keymap::text_edit_event(&key) {
+        app.enqueue_event(AppEvent::TaskInput(TaskInputEvent::Edit(edit)));
+    }
     Ok(false)
 }
 
-fn handle_action(
-    action: Action,
-    app: &mut AppState,
-) -> std::io::Result<bool> {
+pub fn handle_paste(text: &str, app: &mut AppState) {
+    let edit = TextEditEvent::Paste(text.to_string());
+    let event = match app.path_picker {
+        Some(_) => TaskInputEvent::PathPicker(PathPickerEvent::Filter(edit)),
+        None => TaskInputEvent::Edit(edit),
+    };
+    app.enqueue_event(AppEvent::TaskInput(event));
+}
+
+pub fn action_event(action: Action) -> AppEvent {
     match action {
-        Action::Close => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Close)),
-        Action::SwitchField => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::SwitchField)),
-        Action::FocusDescription => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::FocusDescription)),
-        Action::FocusHypotheses => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::FocusHypotheses)),
-        Action::CursorLeft => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorLeft))),
-        Action::CursorRight => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorRight))),
-        Action::MoveUp => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::MoveUp))),
-        Action::MoveDown => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::MoveDown))),
-        Action::AddHeuristic => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::AddHeuristic)),
-        Action::Submit => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Submit)),
-        Action::Backspace => app.enqueue_event(crate::app::AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::Backspace))),
+        Action::Close => AppEvent::TaskInput(TaskInputEvent::Close),
+        Action::SwitchField => AppEvent::TaskInput(TaskInputEvent::SwitchField),
+        Action::FocusDescription => AppEvent::TaskInput(TaskInputEvent::FocusDescription),
+        Action::FocusHypotheses => AppEvent::TaskInput(TaskInputEvent::FocusHypotheses),
+        Action::OpenSpec => AppEvent::TaskInput(TaskInputEvent::OpenSpec),
+        Action::SaveSpec => AppEvent::TaskInput(TaskInputEvent::SaveSpec),
+        Action::OpenPathPicker => AppEvent::TaskInput(TaskInputEvent::OpenPathPicker),
+        Action::CursorLeft => AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorLeft)),
+        Action::CursorRight => AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorRight)),
+        Action::MoveUp => AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::MoveUp)),
+        Action::MoveDown => AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::MoveDown)),
+        Action::AddHeuristic => AppEvent::TaskInput(TaskInputEvent::AddHeuristic),
+        Action::DeleteItem => AppEvent::TaskInput(TaskInputEvent::DeleteItem),
+        Action::DuplicateItem => AppEvent::TaskInput(TaskInputEvent::DuplicateItem),
+        Action::MoveItemUp => AppEvent::TaskInput(TaskInputEvent::MoveItemUp),
+        Action::MoveItemDown => AppEvent::TaskInput(TaskInputEvent::MoveItemDown),
+        Action::Submit => AppEvent::TaskInput(TaskInputEvent::Submit),
+        Action::Backspace => AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::Backspace)),
     }
+}
+
+/// While the path picker is open every key goes to it.
+fn handle_picker_key(key: KeyEvent, app: &mut AppState) -> std::io::Result<bool> {
+    let event = match key.code {
+        KeyCode::Esc => PathPickerEvent::Close,
+        KeyCode::Enter => PathPickerEvent::Confirm,
+        KeyCode::Tab => PathPickerEvent::Complete,
+        KeyCode::Up => PathPickerEvent::SelectPrev,
+        KeyCode::Down => PathPickerEvent::SelectNext,
+        _ => match keymap::text_edit_event(&key) {
+            Some(edit) => PathPickerEvent::Filter(edit),
+            None => return Ok(false),
+        },
+    };
+    app.enqueue_event(AppEvent::TaskInput(TaskInputEvent::PathPicker(event)));
     Ok(false)
 }
 
 pub fn draw(frame: &mut Frame, app: &AppState) {
+    let area = frame.size();
+    let bar = bar_height(area);
+    // Stacked description boxes need the room of one extra box row.
+    let description = if bar < 3 { 10 } else { 12 } + if is_narrow(area) { 3 } else { 0 };
     let root = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(bar),
             Constraint::Length(3),
-            Constraint::Length(12),
-            Constraint::Min(6),
-            Constraint::Length(3),
+            Constraint::Length(description),
+            Constraint::Min(4),
+            Constraint::Length(bar),
         ])
-        .split(frame.size());
+        .split(area);
 
-    fragments::header::draw(frame, root[0]);
+    fragments::header::draw(frame, root[0], app);
 
-    if let Some(cursor) = fragments::description::draw(frame, root[1], app) {
-        if app.cursor_visible {
-            frame.set_cursor(cursor.0, cursor.1);
-        }
+    let spec_cursor = fragments::spec::draw(frame, root[1], app);
+    let description_cursor = fragments::description::draw(frame, root[2], app);
+    let hypotheses_cursor = fragments::hypotheses::draw(frame, root[3], app);
+    fragments::help::draw(frame, root[4], app);
+    let picker_cursor = app.path_picker.as_ref().map(|picker| {
+        // The picker is modal: only its list scrolls.
+        app.mouse.clear();
+        let step = |event| vec![AppEvent::TaskInput(TaskInputEvent::PathPicker(event))];
+        app.mouse.on_scroll(
+            frame.size(),
+            step(PathPickerEvent::SelectPrev),
+            step(PathPickerEvent::SelectNext),
+        );
+        fragments::path_picker::draw(frame, frame.size(), picker, &app.theme)
+    });
+    let cursor = match picker_cursor {
+        Some(cursor) => Some(cursor),
+        None => spec_cursor.or(description_cursor).or(hypotheses_cursor),
+    };
+    if let Some(cursor) = cursor
+        && app.cursor_visible
+    {
+        frame.set_cursor(cursor.0, cursor.1);
     }
-    fragments::hypotheses::draw(frame, root[2], app);
-    fragments::help::draw(frame, root[3]);
 }
@@ -0,0 +1,289 @@
+use std::env;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::border;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ThemeConfig;
+use crate::engine::scheduler::LogLevel;
+use crate::ui::dashed_border_set;
+
+/// A built-in palette, picked with `[theme] name`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    /// Brighter blue/orange/yellow colors that stay apart for red-green
+    /// colorblindness; needs a truecolor terminal.
+    HighContrast,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    /// The app name in the header.
+    pub title: Color,
+    /// Borders and titles of the focused fragment.
+    pub focus: Color,
+    /// Table headers, key names in the controls bar and popup borders.
+    pub heading: Color,
+    /// Folders, pinned hypotheses and other secondary highlights.
+    pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Placeholders, hints and info logs.
+    pub muted: Color,
+    pub axis: Color,
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    /// Log search matches.
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+}
+
+/// Role names accepted in `[theme.colors]`.
+pub const ROLES: &[&str] = &[
+    "title",
+    "focus",
+    "heading",
+    "accent",
+    "success",
+    "warning",
+    "error",
+    "muted",
+    "axis",
+    "selected_fg",
+    "selected_bg",
+    "highlight_fg",
+    "highlight_bg",
+];
+
+impl Palette {
+    pub fn builtin(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self {
+                title: Color::Cyan,
+                focus: Color::Green,
+                heading: Color::Yellow,
+                accent: Color::Cyan,
+                success: Color::Green,
+                warning: Color::Yellow,
+                error: Color::Red,
+                muted: Color::DarkGray,
+                axis: Color::Gray,
+                selected_fg: Color::Black,
+                selected_bg: Color::Green,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::Yellow,
+            },
+            ThemeName::Light => Self {
+                title: Color::Blue,
+                focus: Color::Blue,
+                heading: Color::Magenta,
+                accent: Color::Blue,
+                success: Color::Green,
+                warning: Color::Rgb(175, 95, 0),
+                error: Color::Red,
+                muted: Color::DarkGray,
+                axis: Color::DarkGray,
+                selected_fg: Color::White,
+                selected_bg: Color::Blue,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::Yellow,
+            },
+            // Okabe-Ito colors: sky blue, blue, orange and yellow.
+            ThemeName::HighContrast => Self {
+                title: Color::White,
+                focus: Color::Rgb(86, 180, 233),
+                heading: Color::Rgb(240, 228, 66),
+                accent: Color::Rgb(86, 180, 233),
+                success: Color::Rgb(0, 114, 178),
+                warning: Color::Rgb(240, 228, 66),
+                error: Color::Rgb(230, 159, 0),
+                muted: Color::Gray,
+                axis: Color::White,
+                selected_fg: Color::Black,
+                selected_bg: Color::Rgb(240, 228, 66),
+                highlight_fg: Color::Black,
+                highlight_bg: Color::Rgb(86, 180, 233),
+            },
+        }
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "title" => &mut self.title,
+            "focus" => &mut self.focus,
+            "heading" => &mut self.heading,
+            "accent" => &mut self.accent,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "muted" => &mut self.muted,
+            "axis" => &mut self.axis,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "highlight_fg" => &mut self.highlight_fg,
+            "highlight_bg" => &mut self.highlight_bg,
+            _ => return None,
+        })
+    }
+}
+
+/// The palette plus whether to use it at all. Fragments take every style
+/// from here, so `NO_COLOR` falls back to bold and reversed text everywhere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub palette: Palette,
+    pub color: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            palette: Palette::builtin(ThemeName::default()),
+            color: true,
+        }
+    }
+}
+
+impl Theme {
+    /// Builds the configured theme; a non-empty `NO_COLOR` variable turns
+    /// colors off (<https://no-color.org>).
+    pub fn from_config(config: &ThemeConfig) -> Result<Self, String> {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::build(config, no_color)
+    }
+
+    pub fn build(config: &ThemeConfig, no_color: bool) -> Result<Self, String> {
+        let mut palette = Palette::builtin(config.name);
+        for (role, value) in &config.colors {
+            let slot = palette.role_mut(role).ok_or_else(|| {
+                format!("theme.colors.{role}: unknown role (expected one of {})", ROLES.join(", "))
+            })?;
+            *slot = value
+                .parse()
+                .map_err(|_| format!("theme.colors.{role}: invalid color '{value}'"))?;
+        }
+        Ok(Self {
+            palette,
+            color: !(config.no_color || no_color),
+        })
+    }
+
+    /// Foreground `color`, or the terminal default without colors.
+    pub fn fg(&self, color: Color) -> Style {
+        if self.color {
+            Style::default().fg(color)
+        } else {
+            Style::default()
+        }
+    }
+
+    pub fn header(&self) -> Style {
+        self.fg(self.palette.title).add_modifier(Modifier::BOLD)
+    }
+
+    /// Title of a fragment box, bold and colored when it has focus.
+    pub fn focus_title(&self, active: bool) -> Style {
+        if active {
+            self.fg(self.palette.focus).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Border symbols and style of a fragment box; focus is dashed.
+    pub fn focus_border(&self, active: bool) -> (border::Set, Style) {
+        if active {
+            (dashed_border_set(), self.fg(self.palette.focus))
+        } else {
+            (border::PLAIN, Style::default())
+        }
+    }
+
+    pub fn table_header(&self) -> Style {
+        self.fg(self.palette.heading).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn popup(&self) -> Style {
+        self.fg(self.palette.heading)
+    }
+
+    pub fn popup_title(&self) -> Style {
+        self.popup().add_modifier(Modifier::BOLD)
+    }
+
+    /// Key names in the controls bar.
+    pub fn key(&self) -> Style {
+        if self.color {
+            self.fg(self.palette.heading)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        }
+    }
+
+    pub fn selected(&self) -> Style {
+        if self.color {
+            Style::default()
+                .bg(self.palette.selected_bg)
+                .fg(self.palette.selected_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        }
+    }
+
+    /// A selected row holding an invalid value.
+    pub fn selected_error(&self) -> Style {
+        if self.color {
+            self.selected().bg(self.palette.error)
+        } else {
+            self.selected().add_modifier(Modifier::CROSSED_OUT)
+        }
+    }
+
+    pub fn highlight(&self) -> Style {
+        if self.color {
+            Style::default()
+                .fg(self.palette.highlight_fg)
+                .bg(self.palette.highlight_bg)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED)
+        }
+    }
+
+    pub fn accent(&self) -> Style {
+        self.fg(self.palette.accent)
+    }
+
+    pub fn success(&self) -> Style {
+        self.fg(self.palette.success)
+    }
+
+    pub fn warning(&self) -> Style {
+        self.fg(self.palette.warning)
+    }
+
+    pub fn error(&self) -> Style {
+        self.fg(self.palette.error)
+    }
+
+    pub fn muted(&self) -> Style {
+        self.fg(self.palette.muted)
+    }
+
+    pub fn log_level(&self, level: LogLevel) -> Style {
+        match level {
+            LogLevel::Info => self.muted(),
+            LogLevel::Warn => self.warning(),
+            LogLevel::Error => self.error(),
+        }
+    }
+
+    pub fn axis(&self) -> Style {
+        self.fg(self.palette.axis)
+    }
+}
@@ -0,0 +1,307 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::merge_values;
+use crate::task::{dataset_error, is_image_file, scan_dataset};
+
+/// Serializable description of a task, stored as TOML or JSON.
+///
+/// Every field has a default so spec files only need to list what they change.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TaskSpec {
+    pub name: String,
+    /// Empty runs the task on the built-in mock dataset.
+    pub dataset_folder: String,
+    pub labels: Vec<String>,
+    pub hypotheses: Vec<String>,
+    pub budget: BudgetSpec,
+    pub llm: LlmSpec,
+    pub report: ReportSpec,
+    pub heuristics: Vec<HeuristicSpec>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeuristicSpec {
+    pub title: String,
+    pub images: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetSpec {
+    pub max_iters: usize,
+    pub patience: usize,
+    pub verify_threshold: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmSpec {
+    pub model: String,
+    pub temperature: f64,
+}
+
+/// Per-task report settings; unset fields use the `[reports]` config section.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecField {
+    Name,
+    DatasetFolder,
+    Labels,
+    Heuristic(usize),
+    Image { heuristic: usize, image: usize },
+    Budget,
+    Llm,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecIssue {
+    pub field: SpecField,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecFormat {
+    Toml,
+    Json,
+}
+
+impl Default for BudgetSpec {
+    fn default() -> Self {
+        Self {
+            max_iters: 6,
+            patience: 2,
+            verify_threshold: 0.6,
+        }
+    }
+}
+
+impl Default for LlmSpec {
+    fn default() -> Self {
+        Self {
+            model: String::from("gpt-4o-mini"),
+            temperature: 0.7,
+        }
+    }
+}
+
+impl SpecFormat {
+    /// `.json` files are JSON, everything else is treated as TOML.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => SpecFormat::Json,
+            _ => SpecFormat::Toml,
+        }
+    }
+}
+
+impl TaskSpec {
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn parse(source: &str, format: SpecFormat) -> Result<Self, String> {
+        match format {
+            SpecFormat::Toml => toml::from_str(source).map_err(|e| e.to_string()),
+            SpecFormat::Json => serde_json::from_str(source).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn uses_mock_dataset(&self) -> bool {
+        self.dataset_folder.trim().is_empty()
+    }
+
+    /// Checks that the spec describes a runnable task and returns every problem
+    /// found.
+    pub fn validate(&self) -> Vec<SpecIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |field, message: String| issues.push(SpecIssue { field, message });
+        if self.name.trim().is_empty() {
+            issue(SpecField::Name, "name must not be empty".to_string());
+        }
+
+        if !self.uses_mock_dataset() {
+            let dataset = scan_dataset(Path::new(&self.dataset_folder));
+            if let Some(err) = dataset_error(&self.dataset_folder, &dataset) {
+                issue(SpecField::DatasetFolder, err);
+            } else if self.labels.is_empty() && dataset.iter().all(|image| image.label.is_none()) {
+                issue(
+                    SpecField::Labels,
+                    "labels are missing: list them or sort images into one subfolder per label"
+                        .to_string(),
+                );
+            }
+        }
+        for (idx, label) in self.labels.iter().enumerate() {
+            if label.trim().is_empty() {
+                issue(SpecField::Labels, "labels must not be blank".to_string());
+            } else if self.labels[..idx].contains(label) {
+                issue(SpecField::Labels, format!("label {label} is listed twice"));
+            }
+        }
+
+        for (h, heuristic) in self.heuristics.iter().enumerate() {
+            if heuristic.title.trim().is_empty() {
+                issue(SpecField::Heuristic(h), format!("heuristic {} needs a title", h + 1));
+            }
+            for (i, image) in heuristic.images.iter().enumerate() {
+                let field = SpecField::Image {
+                    heuristic: h,
+                    image: i,
+                };
+                let path = Path::new(image);
+                if image.trim().is_empty() {
+                    issue(field, format!("heuristic {} has an empty image path", h + 1));
+                } else if !is_image_file(path) {
+                    issue(field, format!("{image} is not an image file"));
+                } else if let Err(err) = fs::File::open(path) {
+                    issue(field, format!("cannot read {image}: {err}"));
+                }
+            }
+        }
+
+        if self.budget.max_iters == 0 {
+            issue(SpecField::Budget, "budget.max_iters must be greater than 0".to_string());
+        }
+        if self.budget.patience == 0 {
+            issue(SpecField::Budget, "budget.patience must be greater than 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.budget.verify_threshold) {
+            issue(
+                SpecField::Budget,
+                "budget.verify_threshold must be between 0 and 1".to_string(),
+            );
+        }
+        if self.llm.model.trim().is_empty() {
+            issue(SpecField::Llm, "llm.model must not be empty".to_string());
+        }
+        if !(0.0..=2.0).contains(&self.llm.temperature) {
+            issue(SpecField::Llm, "llm.temperature must be between 0 and 2".to_string());
+        }
+        issues
+    }
+
+    pub fn render(&self, format: SpecFormat) -> Result<String, String> {
+        match format {
+            SpecFormat::Toml => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+            SpecFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Parses `source` with the fields it leaves out taken from `base`.
+    pub fn parse_over(source: &str, format: SpecFormat, base: &TaskSpec) -> Result<Self, String> {
+        Self::merge_over(parse_value(source, format)?, base)
+    }
+
+    fn merge_over(overlay: Value, base: &TaskSpec) -> Result<Self, String> {
+        let mut merged = serde_json::to_value(base).map_err(|e| e.to_string())?;
+        merge_values(&mut merged, overlay);
+        serde_json::from_value(merged).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::load_over(path, &Self::default())
+    }
+
+    /// Loads the spec at `path` over `base`. Relative paths in the file are
+    /// relative to the file's directory; those taken from `base` are kept.
+    pub fn load_over(path: impl AsRef<Path>, base: &TaskSpec) -> Result<Self, String> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        parse_value(&source, SpecFormat::from_path(path))
+            .and_then(|mut overlay| {
+                for file in spec_paths(&mut overlay) {
+                    if Path::new(file.as_str()).is_relative() {
+                        *file = dir.join(file.as_str()).to_string_lossy().into_owned();
+                    }
+                }
+                Self::merge_over(overlay, base)
+            })
+            .map_err(|e| format!("invalid spec {}: {e}", path.display()))
+    }
+
+    /// Saves the spec to `path`, rewriting relative paths so `load_over` reads
+    /// the same files back: relative to the spec's directory when inside it,
+    /// absolute otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut spec = self.clone();
+        if !dir.as_os_str().is_empty() {
+            let mut value = serde_json::to_value(&spec).map_err(|e| e.to_string())?;
+            for file in spec_paths(&mut value) {
+                let relative = Path::new(file.as_str());
+                if let Ok(inside) = relative.strip_prefix(dir) {
+                    *file = inside.to_string_lossy().into_owned();
+                } else if relative.is_relative()
+                    && let Ok(absolute) = std::path::absolute(relative)
+                {
+                    *file = absolute.to_string_lossy().into_owned();
+                }
+            }
+            spec = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        }
+        let rendered = spec.render(SpecFormat::from_path(path))?;
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, rendered).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    }
+}
+
+fn parse_value(source: &str, format: SpecFormat) -> Result<Value, String> {
+    match format {
+        SpecFormat::Toml => toml::from_str(source).map_err(|e| e.to_string()),
+        SpecFormat::Json => serde_json::from_str(source).map_err(|e| e.to_string()),
+    }
+}
+
+/// Every non-empty file path in a spec value: the dataset folder, heuristic
+/// images and the report directory.
+fn spec_paths(spec: &mut Value) -> Vec<&mut String> {
+    let mut paths = Vec::new();
+    if let Value::Object(fields) = spec {
+        for (key, value) in fields.iter_mut() {
+            match (key.as_str(), value) {
+                ("dataset_folder", Value::String(folder)) => paths.push(folder),
+                ("report", Value::Object(report)) => {
+                    if let Some(Value::String(dir)) = report.get_mut("dir") {
+                        paths.push(dir);
+                    }
+                }
+                ("heuristics", Value::Array(heuristics)) => paths.extend(
+                    heuristics
+                        .iter_mut()
+                        .filter_map(|heuristic| heuristic.get_mut("images"))
+                        .filter_map(Value::as_array_mut)
+                        .flatten()
+                        .filter_map(|image| match image {
+                            Value::String(image) => Some(image),
+                            _ => None,
+                        }),
+                ),
+                _ => {}
+            }
+        }
+    }
+    paths.retain(|path| !path.trim().is_empty());
+    paths
+}
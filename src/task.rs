@@ -1,11 +1,19 @@
+use std::fs;
+use std::path::Path;
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
+use crate::spec::{HeuristicSpec, LlmSpec, ReportSpec, TaskSpec};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "webp"];
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ImageItem {
     pub id: usize,
     pub name: String,
+    pub path: Option<String>,
+    pub label: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -19,17 +27,62 @@ pub struct Heuristics {
 pub struct TaskDefinition {
     pub id: usize,
     pub name: String,
+    pub dataset_folder: String,
     pub dataset: Vec<ImageItem>,
+    pub labels: Vec<String>,
     pub heuristics: Heuristics,
+    pub hints: Vec<HeuristicSpec>,
+    pub seed_hypotheses: Vec<String>,
     pub max_iters: usize,
+    pub patience: usize,
+    pub verify_threshold: f32,
+    pub llm: LlmSpec,
+    pub report: ReportSpec,
     pub created_at: SystemTime,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Hypothesis {
     pub id: usize,
+    pub iteration: usize,
     pub description: String,
+    pub rationale: String,
     pub score: f32,
+    pub results: Vec<ImageResult>,
+}
+
+/// Whether a hypothesis held on one dataset image during evaluation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageResult {
+    pub image_id: usize,
+    pub image: String,
+    /// File of the image; mock dataset images have none.
+    #[serde(default)]
+    pub path: Option<String>,
+    pub passed: bool,
+}
+
+/// Label predicted for one dataset image by a synthesized program.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Prediction {
+    pub image_id: usize,
+    pub image: String,
+    pub predicted: String,
+    pub expected: Option<String>,
+}
+
+/// What one search iteration did, kept by the engine for the whole run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IterationRecord {
+    pub iteration: usize,
+    pub generated: usize,
+    pub verified: usize,
+    pub best_score: f32,
+    pub last_score: f32,
+    /// Time from the start of the iteration until its program was synthesized.
+    pub elapsed_ms: u64,
+    /// Iteration whose program was the best after this one, if any was.
+    pub program_version: Option<usize>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -68,17 +121,32 @@ pub struct TaskSnapshot {
     pub discarded: Vec<Hypothesis>,
     pub best_score: f32,
     pub last_score: f32,
+    pub verify_threshold: f32,
+    pub history: Vec<IterationRecord>,
     pub report_path: Option<String>,
 }
 
 impl TaskDefinition {
     pub fn mock(id: usize, name: String) -> Self {
-        let dataset = (0..16)
-            .map(|i| ImageItem {
-                id: i,
-                name: format!("image_{id}_{i:02}.png"),
-            })
-            .collect::<Vec<_>>();
+        let mut definition = Self::from_spec(id, &TaskSpec::named(name));
+        definition.dataset = mock_dataset(id);
+        definition
+    }
+
+    /// Builds a definition from a spec, reading image files from the dataset folder,
+    /// or generating the mock dataset when the spec leaves the folder empty.
+    pub fn from_spec(id: usize, spec: &TaskSpec) -> Self {
+        let dataset = if spec.uses_mock_dataset() {
+            let mut dataset = mock_dataset(id);
+            if !spec.labels.is_empty() {
+                for image in &mut dataset {
+                    image.label = Some(spec.labels[image.id % spec.labels.len()].clone());
+                }
+            }
+            dataset
+        } else {
+            scan_dataset(Path::new(&spec.dataset_folder))
+        };
 
         let heuristics = Heuristics {
             edge_threshold: 0.42,
@@ -88,13 +156,126 @@ impl TaskDefinition {
 
         Self {
             id,
-            name,
+            name: spec.name.clone(),
+            dataset_folder: spec.dataset_folder.clone(),
             dataset,
+            labels: spec.labels.clone(),
             heuristics,
-            max_iters: 6,
+            hints: spec.heuristics.clone(),
+            seed_hypotheses: spec.hypotheses.clone(),
+            max_iters: spec.budget.max_iters,
+            patience: spec.budget.patience,
+            verify_threshold: spec.budget.verify_threshold,
+            llm: spec.llm.clone(),
+            report: spec.report.clone(),
             created_at: SystemTime::now(),
         }
     }
+
+    pub fn uses_mock_dataset(&self) -> bool {
+        self.dataset_folder.trim().is_empty()
+    }
+
+    /// Labels a program can predict: the declared labels, else those found in
+    /// the dataset folders, else a binary match/no-match.
+    pub fn label_set(&self) -> Vec<String> {
+        if !self.labels.is_empty() {
+            return self.labels.clone();
+        }
+        let mut found = self
+            .dataset
+            .iter()
+            .filter_map(|image| image.label.clone())
+            .collect::<Vec<_>>();
+        found.sort();
+        found.dedup();
+        if found.is_empty() {
+            vec!["match".to_string(), "no match".to_string()]
+        } else {
+            found
+        }
+    }
+}
+
+impl Prediction {
+    pub fn is_correct(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| *expected == self.predicted)
+    }
+}
+
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+        .unwrap_or(false)
+}
+
+/// Why the images scanned from `folder` cannot be used as a dataset.
+pub fn dataset_error(folder: &str, dataset: &[ImageItem]) -> Option<String> {
+    if !dataset.is_empty() {
+        None
+    } else if Path::new(folder).is_dir() {
+        Some(format!("dataset folder {folder} contains no images"))
+    } else {
+        Some(format!("dataset folder {folder} does not exist"))
+    }
+}
+
+/// Lists image files in `folder` and its immediate subfolders, sorted by path.
+/// Images inside a subfolder are labelled with the subfolder name.
+pub fn scan_dataset(folder: &Path) -> Vec<ImageItem> {
+    let mut paths = Vec::new();
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let label = entry.file_name().to_string_lossy().to_string();
+            if let Ok(children) = fs::read_dir(&path) {
+                paths.extend(
+                    children
+                        .flatten()
+                        .map(|child| child.path())
+                        .filter(|child| is_image_file(child))
+                        .map(|child| (child, Some(label.clone()))),
+                );
+            }
+        } else if is_image_file(&path) {
+            paths.push((path, None));
+        }
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .enumerate()
+        .map(|(id, (path, label))| ImageItem {
+            id,
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: Some(path.to_string_lossy().to_string()),
+            label,
+        })
+        .collect()
+}
+
+fn mock_dataset(id: usize) -> Vec<ImageItem> {
+    (0..16)
+        .map(|i| ImageItem {
+            id: i,
+            name: format!("image_{id}_{i:02}.png"),
+            path: None,
+            label: None,
+        })
+        .collect()
 }
 
 impl TaskSnapshot {
@@ -113,6 +294,8 @@ impl TaskSnapshot {
             discarded: Vec::new(),
             best_score: 0.0,
             last_score: 0.0,
+            verify_threshold: definition.verify_threshold,
+            history: Vec::new(),
             report_path: None,
         }
     }
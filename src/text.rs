@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::TextEditEvent;
+
+/// A single line of editable text. The cursor is a byte offset that always
+/// sits on a grapheme boundary, so accents, emoji and other multi-byte
+/// characters move and delete as one unit. Serialized as its text alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct TextBuffer {
+    text: String,
+    cursor: usize,
+}
+
+impl TextBuffer {
+    /// A buffer with the cursor at the end of `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let cursor = text.len();
+        Self { text, cursor }
+    }
+
+    /// A buffer with the cursor moved back to the nearest grapheme boundary
+    /// at or before `cursor`.
+    pub fn with_cursor(text: impl Into<String>, cursor: usize) -> Self {
+        let mut buffer = Self::new(text);
+        buffer.cursor = buffer.floor_boundary(cursor);
+        buffer
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Terminal columns between the start of the text and the cursor.
+    pub fn cursor_column(&self) -> usize {
+        self.text[..self.cursor].width()
+    }
+
+    /// The text from where a `width` columns wide box scrolls it to keep the
+    /// cursor in view, and the cursor's column within that box.
+    pub fn scrolled(&self, width: usize) -> (&str, usize) {
+        let mut start = self.cursor;
+        let mut column = 0;
+        for (i, grapheme) in self.text[..self.cursor].grapheme_indices(true).rev() {
+            if column + grapheme.width() >= width {
+                break;
+            }
+            column += grapheme.width();
+            start = i;
+        }
+        (&self.text[start..], column)
+    }
+
+    /// Applies a line edit; list movement (`MoveUp`/`MoveDown`) is ignored.
+    pub fn apply(&mut self, event: &TextEditEvent) {
+        match event {
+            TextEditEvent::CursorLeft => self.cursor = self.prev_boundary(),
+            TextEditEvent::CursorRight => self.cursor = self.next_boundary(),
+            TextEditEvent::Home => self.cursor = 0,
+            TextEditEvent::End => self.cursor = self.text.len(),
+            TextEditEvent::WordLeft => self.cursor = self.word_start(),
+            TextEditEvent::WordRight => self.cursor = self.word_end(),
+            TextEditEvent::InsertChar(ch) => self.insert(ch.encode_utf8(&mut [0; 4])),
+            TextEditEvent::Paste(pasted) => {
+                let pasted = pasted
+                    .lines()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .replace(|ch: char| ch.is_control(), " ");
+                self.insert(&pasted);
+            }
+            TextEditEvent::Backspace => self.delete_back_to(self.prev_boundary()),
+            TextEditEvent::Delete => {
+                let end = self.next_boundary();
+                self.text.replace_range(self.cursor..end, "");
+            }
+            TextEditEvent::DeleteWordBack => self.delete_back_to(self.word_start()),
+            TextEditEvent::DeleteToStart => self.delete_back_to(0),
+            TextEditEvent::DeleteToEnd => self.text.truncate(self.cursor),
+            TextEditEvent::MoveUp | TextEditEvent::MoveDown => {}
+        }
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.text.insert_str(self.cursor, text);
+        // A combining mark joins the grapheme before it, so re-snap.
+        self.cursor = self.floor_boundary(self.cursor + text.len());
+    }
+
+    fn delete_back_to(&mut self, start: usize) {
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    fn boundaries(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.text
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(self.text.len()))
+    }
+
+    fn floor_boundary(&self, cursor: usize) -> usize {
+        self.boundaries().rev().find(|&i| i <= cursor).unwrap_or(0)
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.boundaries().rev().find(|&i| i < self.cursor).unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.boundaries()
+            .find(|&i| i > self.cursor)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first.
+    fn word_start(&self) -> usize {
+        let before = self.text[..self.cursor].trim_end();
+        before
+            .grapheme_indices(true)
+            .rev()
+            .find(|(_, grapheme)| grapheme.trim().is_empty())
+            .map_or(0, |(i, grapheme)| i + grapheme.len())
+    }
+
+    /// End of the word after the cursor, skipping whitespace first.
+    fn word_end(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let start = after.len() - after.trim_start().len();
+        after[start..]
+            .grapheme_indices(true)
+            .find(|(_, grapheme)| grapheme.trim().is_empty())
+            .map_or(self.text.len(), |(i, _)| self.cursor + start + i)
+    }
+}
+
+impl From<String> for TextBuffer {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<TextBuffer> for String {
+    fn from(buffer: TextBuffer) -> Self {
+        buffer.text
+    }
+}
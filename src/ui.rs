@@ -3,6 +3,8 @@ use ratatui::symbols::border;
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::task::{TaskPhase, TaskStatus};
 
@@ -49,15 +51,22 @@ pub fn format_phase(phase: &TaskPhase) -> String {
     }
 }
 
+/// Cuts `value` to at most `max` terminal columns, ending in `...` when cut.
 pub fn truncate(value: &str, max: usize) -> String {
-    if value.len() <= max {
+    if value.width() <= max {
         return value.to_string();
     }
-    if max <= 3 {
-        return value.chars().take(max).collect();
+    let (budget, ellipsis) = if max <= 3 { (max, "") } else { (max - 3, "...") };
+    let mut out = String::new();
+    let mut width = 0;
+    for grapheme in value.graphemes(true) {
+        width += grapheme.width();
+        if width > budget {
+            break;
+        }
+        out.push_str(grapheme);
     }
-    let mut out = value.chars().take(max - 3).collect::<String>();
-    out.push_str("...");
+    out.push_str(ellipsis);
     out
 }
 
@@ -0,0 +1,188 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use revolver::cli::{exit_code, parse_args, run_headless, CliCommand};
+use revolver::config::AppConfig;
+use revolver::report::{list_reports, report_dirs, ReportFormat};
+use revolver::spec::TaskSpec;
+use revolver::task::TaskStatus;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn parse_args_defaults_to_tui() {
+    assert_eq!(parse_args(args(&[])), Ok(CliCommand::Tui));
+}
+
+#[test]
+fn parse_args_reads_subcommands() {
+    assert_eq!(
+        parse_args(args(&["run", "--name", "sweep", "--json"])),
+        Ok(CliCommand::Run {
+            spec: None,
+            name: Some("sweep".to_string()),
+            json: true,
+        })
+    );
+    assert_eq!(
+        parse_args(args(&["run", "--spec", "specs/example.toml"])),
+        Ok(CliCommand::Run {
+            spec: Some(PathBuf::from("specs/example.toml")),
+            name: None,
+            json: false,
+        })
+    );
+    assert_eq!(parse_args(args(&["list"])), Ok(CliCommand::List));
+    assert_eq!(
+        parse_args(args(&["report", "7"])),
+        Ok(CliCommand::Report { id: 7, run: None })
+    );
+    assert_eq!(
+        parse_args(args(&["report", "7", "1760880000123"])),
+        Ok(CliCommand::Report {
+            id: 7,
+            run: Some(1_760_880_000_123)
+        })
+    );
+}
+
+#[test]
+fn parse_args_rejects_bad_input() {
+    assert!(parse_args(args(&["run"])).is_err());
+    assert!(parse_args(args(&["report", "seven"])).is_err());
+    assert!(parse_args(args(&["report", "7", "latest"])).is_err());
+    assert!(parse_args(args(&["launch"])).is_err());
+}
+
+#[test]
+fn exit_code_reflects_task_status() {
+    assert_eq!(exit_code(&TaskStatus::Done), ExitCode::SUCCESS);
+    assert_eq!(exit_code(&TaskStatus::Failed("io".to_string())), ExitCode::from(1));
+    assert_eq!(exit_code(&TaskStatus::Cancelled), ExitCode::from(2));
+}
+
+#[tokio::test]
+async fn run_headless_streams_logs_and_writes_report() {
+    let reports = env::temp_dir().join(format!("revolver_cli_reports_{}", std::process::id()));
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 0;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    config.reports.dir = reports.to_string_lossy().to_string();
+
+    let mut out = Vec::new();
+    let snapshot = run_headless(TaskSpec::named("headless"), false, &config, &mut out)
+        .await
+        .expect("headless run failed");
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(matches!(snapshot.status, TaskStatus::Done));
+    assert_eq!(snapshot.history.len(), snapshot.iteration);
+    assert!(snapshot
+        .history
+        .iter()
+        .all(|record| record.best_score <= snapshot.best_score));
+    assert!(out.starts_with("[task 1] Task started"), "{out}");
+    assert!(out.trim_end().ends_with(&format!(
+        "report {}",
+        snapshot.report_path.clone().unwrap()
+    )));
+    let markdown = PathBuf::from(snapshot.report_path.unwrap());
+    let name = markdown.file_name().unwrap().to_string_lossy().to_string();
+    let run = name
+        .strip_prefix("task_1_run-")
+        .and_then(|rest| rest.strip_suffix("_headless.md"))
+        .expect("report name carries the run");
+    assert!(run.parse::<u64>().is_ok(), "{name}");
+    assert!(markdown.with_extension("html").exists());
+    assert!(markdown.with_extension("json").exists());
+    let _ = fs::remove_dir_all(reports);
+}
+
+#[tokio::test]
+async fn missing_dataset_folders_fail_instead_of_using_the_mock_dataset() {
+    let missing = env::temp_dir().join(format!("revolver_cli_missing_{}", std::process::id()));
+    let spec_path = env::temp_dir().join(format!("revolver_cli_spec_{}.toml", std::process::id()));
+    fs::write(
+        &spec_path,
+        format!("name = \"typo\"\ndataset_folder = \"{}\"\n", missing.display()),
+    )
+    .unwrap();
+    let mut config = AppConfig::default();
+    config.reports.dir = missing.join("reports").to_string_lossy().to_string();
+    let command = CliCommand::Run {
+        spec: Some(spec_path.clone()),
+        name: None,
+        json: false,
+    };
+    assert_eq!(revolver::cli::run(command, &config).await, ExitCode::from(64));
+    let _ = fs::remove_file(spec_path);
+
+    // Specs that skip validation still never run on made-up images.
+    let mut spec = TaskSpec::named("typo");
+    spec.dataset_folder = missing.to_string_lossy().to_string();
+    let snapshot = run_headless(spec, false, &config, &mut Vec::new()).await.unwrap();
+    assert!(
+        matches!(&snapshot.status, TaskStatus::Failed(err) if err.contains("does not exist")),
+        "{:?}",
+        snapshot.status
+    );
+    assert!(snapshot.report_path.is_none());
+    assert!(!missing.exists());
+}
+
+#[tokio::test]
+async fn reports_of_every_run_and_directory_are_listed() {
+    let root = env::temp_dir().join(format!("revolver_cli_runs_{}", std::process::id()));
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 0;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    config.reports.formats = vec![ReportFormat::Markdown];
+    config.reports.dir = root.join("reports").to_string_lossy().to_string();
+    let nightly = root.join("nightly");
+
+    // Two processes, as from cron: both number their task 1.
+    for _ in 0..2 {
+        let mut spec = TaskSpec::named("cron");
+        spec.report.dir = Some(nightly.to_string_lossy().to_string());
+        run_headless(spec, false, &config, &mut Vec::new()).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+
+    let dirs = report_dirs(Path::new(&config.reports.dir)).unwrap();
+    assert_eq!(dirs.len(), 2, "{dirs:?}");
+    assert_eq!(dirs[1], nightly.canonicalize().unwrap());
+    let runs = list_reports(&dirs[1]).unwrap();
+    assert_eq!(runs.len(), 2, "the second run overwrote the first");
+    assert!(runs.iter().all(|entry| entry.task_id == 1));
+    assert!(runs[0].run < runs[1].run);
+
+    let report = |run| CliCommand::Report { id: 1, run };
+    assert_eq!(revolver::cli::run(report(None), &config).await, ExitCode::SUCCESS);
+    assert_eq!(revolver::cli::run(report(Some(runs[0].run)), &config).await, ExitCode::SUCCESS);
+    assert_eq!(revolver::cli::run(report(Some(7)), &config).await, ExitCode::FAILURE);
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn report_names_without_a_run_marker_are_legacy() {
+    let dir = env::temp_dir().join(format!("revolver_cli_legacy_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    // Written before runs were named: task 3 "2024 summary" and task 5 "notes".
+    for name in ["task_3_2024_summary.md", "task_4_run-17_nightly.md", "task_5_notes.md"] {
+        fs::write(dir.join(name), "# report\n").unwrap();
+    }
+    fs::write(dir.join("task_6_run-17_nightly.json"), "{}").unwrap();
+
+    let runs = list_reports(&dir).unwrap();
+    let found = runs.iter().map(|entry| (entry.task_id, entry.run)).collect::<Vec<_>>();
+    assert_eq!(found, vec![(3, 0), (4, 17), (5, 0)]);
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use revolver::app::{TaskFilter, TaskGrouping, TaskSort};
+use revolver::command::{complete, Command};
+use revolver::config::AppConfig;
+use revolver::engine::scheduler::{run_scheduler_with_config, LogLevel, TaskUpdate};
+use revolver::gateway::Gateway;
+use revolver::protocol::{EngineToUi, UiToEngine};
+use revolver::report::ReportFormat;
+use revolver::spec::TaskSpec;
+use revolver::task::TaskStatus;
+
+#[test]
+fn parses_commands_and_reports_errors() {
+    assert_eq!(Command::parse(":cancel 3"), Ok(Command::Cancel(3)));
+    assert_eq!(Command::parse("retry 2"), Ok(Command::Retry(2)));
+    assert_eq!(
+        Command::parse("export 4 html"),
+        Ok(Command::Export {
+            id: 4,
+            format: ReportFormat::Html
+        })
+    );
+    assert_eq!(
+        Command::parse("export 4 md"),
+        Ok(Command::Export {
+            id: 4,
+            format: ReportFormat::Markdown
+        })
+    );
+    assert_eq!(
+        Command::parse("filter running"),
+        Ok(Command::Filter(TaskFilter::Running))
+    );
+    assert_eq!(Command::parse("filter"), Ok(Command::Filter(TaskFilter::All)));
+    assert_eq!(Command::parse("sort score"), Ok(Command::Sort(TaskSort::Score)));
+    assert_eq!(Command::parse("sort"), Ok(Command::Sort(TaskSort::Created)));
+    assert_eq!(
+        Command::parse("group status"),
+        Ok(Command::Group(TaskGrouping::Status))
+    );
+    assert_eq!(
+        Command::parse("group owner"),
+        Err("Unknown grouping 'owner'.".to_string())
+    );
+
+    assert_eq!(Command::parse("cancel"), Err("Missing task id.".to_string()));
+    assert_eq!(
+        Command::parse("cancel x"),
+        Err("Invalid task id 'x'.".to_string())
+    );
+    assert_eq!(
+        Command::parse("export 4 pdf"),
+        Err("Unknown report format 'pdf'.".to_string())
+    );
+    assert_eq!(
+        Command::parse("launch 1"),
+        Err("Unknown command 'launch'.".to_string())
+    );
+    assert_eq!(
+        Command::parse("retry 1 2"),
+        Err("Too many arguments for 'retry'.".to_string())
+    );
+}
+
+#[test]
+fn completes_command_names_task_ids_and_arguments() {
+    let ids = [3, 12, 14];
+
+    let completion = complete("ca", &ids);
+    assert_eq!(completion.line, "cancel ");
+    assert!(completion.candidates.is_empty());
+
+    let completion = complete("", &ids);
+    assert_eq!(completion.line, "");
+    assert_eq!(
+        completion.candidates,
+        vec!["cancel", "retry", "export", "filter", "sort", "group"]
+    );
+
+    let completion = complete("retry 1", &ids);
+    assert_eq!(completion.line, "retry 1");
+    assert_eq!(completion.candidates, vec!["12", "14"]);
+
+    assert_eq!(complete("retry 3", &ids).line, "retry 3 ");
+    assert_eq!(complete("export 12 h", &ids).line, "export 12 html ");
+    assert_eq!(complete("filter ru", &ids).line, "filter running ");
+    assert_eq!(complete("filter running ", &ids).line, "filter running ");
+    assert_eq!(complete("sort p", &ids).line, "sort progress ");
+    assert_eq!(complete("group s", &ids).line, "group status ");
+}
+
+#[tokio::test]
+async fn retry_and_export_reuse_a_finished_task() {
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 0;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    config.reports.formats = vec![ReportFormat::Markdown];
+    let dir = std::env::temp_dir().join(format!("revolver_command_{}", std::process::id()));
+    let mut spec = TaskSpec::named("exported");
+    spec.budget.max_iters = 2;
+    spec.report.dir = Some(dir.to_string_lossy().to_string());
+    config.reports.dir = spec.report.dir.clone().unwrap();
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    gateway.send(UiToEngine::AddTaskFromSpec { spec }).await;
+
+    let mut retried = None;
+    let mut exported = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        match update {
+            TaskUpdate::Upsert(snapshot) if snapshot.id == 1 => {
+                if matches!(snapshot.status, TaskStatus::Done) {
+                    gateway
+                        .send(UiToEngine::ExportReport {
+                            id: 1,
+                            format: ReportFormat::Json,
+                        })
+                        .await;
+                    gateway.send(UiToEngine::RetryTask { id: 1 }).await;
+                }
+            }
+            TaskUpdate::Upsert(snapshot) => retried = Some(snapshot),
+            TaskUpdate::Log { id: 1, level, message } if message.starts_with("Exported") => {
+                exported = Some((level, message));
+            }
+            _ => {}
+        }
+        if retried.is_some() && exported.is_some() {
+            break;
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    let json_exists = exported
+        .as_ref()
+        .and_then(|(_, message)| message.strip_prefix("Exported report to "))
+        .and_then(|path| path.strip_suffix('.'))
+        .is_some_and(|path| std::path::Path::new(path).exists());
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let (level, message) = exported.expect("no export log");
+    assert_eq!(level, LogLevel::Info);
+    assert!(message.starts_with(&format!("Exported report to {}", dir.display())), "{message}");
+    assert!(message.ends_with("_exported.json."), "{message}");
+    assert!(json_exists);
+    let retried = retried.expect("retry did not start a new task");
+    assert_eq!((retried.id, retried.name.as_str()), (2, "exported"));
+}
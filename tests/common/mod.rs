@@ -0,0 +1,128 @@
+//! Fixtures shared by the integration tests; each test crate uses a subset.
+#![allow(dead_code)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::{Frame, Terminal};
+
+use revolver::app::AppState;
+use revolver::engine::scheduler::TaskUpdate;
+use revolver::protocol::UiToEngine;
+use revolver::screens::{dispatch_key, dispatch_mouse};
+use revolver::task::{Hypothesis, TaskDefinition, TaskSnapshot, TaskStatus};
+
+/// Terminal size `draw` renders at.
+pub const WIDTH: u16 = 120;
+pub const HEIGHT: u16 = 32;
+
+/// A mock task named "task {id}" in `status`.
+pub fn snapshot(id: usize, status: TaskStatus) -> TaskSnapshot {
+    let definition = TaskDefinition::mock(id, format!("task {id}"));
+    let mut snapshot = TaskSnapshot::from_definition(&definition);
+    snapshot.status = status;
+    snapshot
+}
+
+/// An app holding task 1 in `status`.
+pub fn app_with(status: TaskStatus) -> AppState {
+    let mut app = AppState::new();
+    app.apply_update(TaskUpdate::Upsert(snapshot(1, status)));
+    app
+}
+
+pub fn hypothesis(id: usize, score: f32) -> Hypothesis {
+    Hypothesis {
+        id,
+        iteration: 1,
+        description: format!("idea {id}"),
+        rationale: String::new(),
+        score,
+        results: Vec::new(),
+    }
+}
+
+/// Runs `draw` on a `width`×`height` test terminal and returns the frame.
+pub fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Draws a frame of every screen layer, which also records the hotspots.
+pub fn screen(app: &AppState, width: u16, height: u16) -> Buffer {
+    render(width, height, |frame| revolver::screens::draw(frame, app))
+}
+
+pub fn rows(buffer: &Buffer) -> Vec<String> {
+    (0..buffer.area.height)
+        .map(|y| (0..buffer.area.width).map(|x| buffer.get(x, y).symbol()).collect())
+        .collect()
+}
+
+/// Text rows of a frame drawn at `width`×`height`.
+pub fn lines(app: &AppState, width: u16, height: u16) -> Vec<String> {
+    rows(&screen(app, width, height))
+}
+
+pub fn draw(app: &AppState) -> Vec<String> {
+    lines(app, WIDTH, HEIGHT)
+}
+
+/// Column and row of the first occurrence of `text` in `lines`.
+pub fn position(lines: &[String], text: &str) -> (u16, u16) {
+    lines
+        .iter()
+        .enumerate()
+        .find_map(|(y, line)| {
+            line.find(text)
+                .map(|byte| (line[..byte].chars().count() as u16, y as u16))
+        })
+        .unwrap_or_else(|| panic!("'{text}' is not on screen:\n{}", lines.join("\n")))
+}
+
+pub fn find(app: &AppState, text: &str) -> (u16, u16) {
+    position(&draw(app), text)
+}
+
+/// Applies the queued events; returns the engine commands sent and whether
+/// the app quit.
+pub fn apply(app: &mut AppState) -> (Vec<UiToEngine>, bool) {
+    let (mut commands, mut quit) = (Vec::new(), false);
+    while let Some(event) = app.pop_event() {
+        let result = app.apply_event(event);
+        commands.extend(result.cmd);
+        quit |= result.quit;
+    }
+    (commands, quit)
+}
+
+/// Presses `keys` and returns the engine commands sent and whether the app quit.
+pub fn press(app: &mut AppState, keys: &[KeyCode]) -> (Vec<UiToEngine>, bool) {
+    let (mut commands, mut quit) = (Vec::new(), false);
+    for key in keys {
+        dispatch_key(KeyEvent::from(*key), app).unwrap();
+        let (sent, quits) = apply(app);
+        commands.extend(sent);
+        quit |= quits;
+    }
+    (commands, quit)
+}
+
+/// Draws a frame to record the hotspots, then sends a mouse event at `(column, row)`.
+pub fn mouse(app: &mut AppState, kind: MouseEventKind, (column, row): (u16, u16)) {
+    draw(app);
+    let event = MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    dispatch_mouse(event, app);
+    apply(app);
+}
+
+pub fn click(app: &mut AppState, text: &str) {
+    let at = find(app, text);
+    mouse(app, MouseEventKind::Down(MouseButton::Left), at);
+}
@@ -0,0 +1,117 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
+
+use revolver::config::AppConfig;
+use revolver::report::ReportFormat;
+use revolver::spec::{SpecFormat, TaskSpec};
+
+fn write_layer(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("revolver_{}_{name}.toml", std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn vars(list: &[(&str, &str)]) -> Vec<(OsString, OsString)> {
+    list.iter()
+        .map(|(key, value)| (OsString::from(key), OsString::from(value)))
+        .collect()
+}
+
+#[test]
+fn defaults_match_previous_constants() {
+    let config = AppConfig::load_layers(&[], vars(&[])).unwrap();
+    assert_eq!(config, AppConfig::default());
+    assert_eq!(config.ui.tick_ms, 200);
+    assert_eq!(config.ui.splash_ms, 3000);
+    assert_eq!(config.reports.dir, "reports");
+    assert_eq!(config.engine.command_channel, 32);
+}
+
+#[test]
+fn later_layers_override_earlier_ones() {
+    let user = write_layer("user", "[ui]\ntick_ms = 100\nsplash_ms = 0\n[reports]\ndir = \"out\"\n");
+    let project = write_layer("project", "[ui]\ntick_ms = 50\n");
+    let missing = env::temp_dir().join("revolver_missing_config.toml");
+    let config = AppConfig::load_layers(
+        &[user.clone(), missing, project.clone()],
+        vars(&[("REVOLVER_REPORTS__DIR", "nightly"), ("HOME", "/ignored")]),
+    )
+    .unwrap();
+    assert_eq!(config.ui.tick_ms, 50);
+    assert_eq!(config.ui.splash_ms, 0);
+    assert_eq!(config.reports.dir, "nightly");
+    let _ = fs::remove_file(user);
+    let _ = fs::remove_file(project);
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    let err = AppConfig::load_layers(&[], vars(&[("REVOLVER_UI__TICK_MS", "0")])).unwrap_err();
+    assert!(err.contains("ui.tick_ms"), "{err}");
+
+    let unknown = write_layer("unknown", "[ui]\ntick = 10\n");
+    assert!(AppConfig::load_layers(std::slice::from_ref(&unknown), vars(&[])).is_err());
+    let _ = fs::remove_file(unknown);
+}
+
+#[test]
+fn environment_values_take_the_setting_type_and_unknown_names_are_ignored() {
+    let config = AppConfig::load_layers(
+        &[],
+        vars(&[
+            ("REVOLVER_LLM__MODEL", "4"),
+            ("REVOLVER_UI__TICK_MS", "100"),
+            ("REVOLVER_REPORTS__FORMATS", "json, html"),
+            ("REVOLVER_REPORTS__TEMPLATE", "report.md"),
+            ("REVOLVER_FOO__BAR", "1"),
+            ("REVOLVER_UI__TICK", "1"),
+            ("REVOLVER_TOKEN", "secret"),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(config.llm.model, "4");
+    assert_eq!(config.ui.tick_ms, 100);
+    assert_eq!(config.reports.formats, vec![ReportFormat::Json, ReportFormat::Html]);
+    assert_eq!(config.reports.template.as_deref(), Some("report.md"));
+    assert_eq!(
+        config.ignored_vars,
+        vec!["REVOLVER_FOO__BAR", "REVOLVER_TOKEN", "REVOLVER_UI__TICK"]
+    );
+
+    let err = AppConfig::load_layers(&[], vars(&[("REVOLVER_UI__TICK_MS", "fast")])).unwrap_err();
+    assert!(err.contains("invalid config"), "{err}");
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_environment_variables_are_ignored() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let mut env = vars(&[("REVOLVER_UI__TICK_MS", "100"), ("REVOLVER_LLM__MODEL", "gpt-4o")]);
+    env[1].1 = OsString::from_vec(b"gpt-\xff".to_vec());
+    env.push((OsString::from_vec(b"REVOLVER_\xffX".to_vec()), OsString::from("1")));
+    env.push((OsString::from_vec(b"OTHER_\xff".to_vec()), OsString::from("1")));
+    let config = AppConfig::load_layers(&[], env).unwrap();
+    assert_eq!(config.ui.tick_ms, 100);
+    assert_eq!(config.llm.model, AppConfig::default().llm.model);
+    assert_eq!(config.ignored_vars, vec!["REVOLVER_LLM__MODEL", "REVOLVER_\u{fffd}X"]);
+}
+
+#[test]
+fn spec_files_fall_back_to_configured_defaults() {
+    let config = AppConfig::load_layers(
+        &[],
+        vars(&[
+            ("REVOLVER_DEFAULTS__DATASET_FOLDER", "/srv/images"),
+            ("REVOLVER_LLM__MODEL", "gpt-4o"),
+        ]),
+    )
+    .unwrap();
+    let spec =
+        TaskSpec::parse_over("name = \"nightly\"", SpecFormat::Toml, &config.default_spec()).unwrap();
+    assert_eq!(spec.dataset_folder, "/srv/images");
+    assert_eq!(spec.llm.model, "gpt-4o");
+    assert_eq!(spec.llm.temperature, TaskSpec::default().llm.temperature);
+}
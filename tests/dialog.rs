@@ -0,0 +1,118 @@
+mod common;
+
+use crossterm::event::KeyCode;
+
+use revolver::app::{AppEvent, MainScreenEvent};
+use revolver::engine::scheduler::{LogLevel, TaskUpdate};
+use revolver::protocol::UiToEngine;
+use revolver::report::ReportFormat;
+use revolver::screens::dialog::{Dialog, DialogKind};
+use revolver::screens::{dispatch_paste, ScreenId};
+use revolver::task::TaskStatus;
+
+use common::{app_with, click, draw, press, snapshot};
+
+#[test]
+fn cancelling_a_task_asks_first_and_the_dialog_takes_the_keys() {
+    let mut app = app_with(TaskStatus::Running);
+    let (commands, _) = press(&mut app, &[KeyCode::Char('c')]);
+    assert!(commands.is_empty());
+    assert!(matches!(app.dialogs[0].kind, DialogKind::Confirm(_)));
+    let screen = draw(&app).join("\n");
+    assert!(screen.contains("Cancel task 1 \"task 1\"?"), "{screen}");
+    assert!(screen.contains("[ y Yes ]  [ n No ]"), "{screen}");
+
+    // 'n' answers the dialog instead of opening the task input.
+    let (commands, _) = press(&mut app, &[KeyCode::Char('n')]);
+    assert!(commands.is_empty() && app.dialogs.is_empty());
+    assert_eq!(app.screen, ScreenId::Main);
+
+    let (commands, _) = press(&mut app, &[KeyCode::Char('c'), KeyCode::Enter]);
+    assert!(matches!(commands.as_slice(), [UiToEngine::CancelTask { id: 1 }]));
+
+    press(&mut app, &[KeyCode::Char('c')]);
+    click(&mut app, "[ y Yes ]");
+    assert!(app.dialogs.is_empty());
+
+    let mut app = app_with(TaskStatus::Done);
+    press(&mut app, &[KeyCode::Char('c')]);
+    assert!(matches!(app.dialogs[0].kind, DialogKind::Message));
+    assert_eq!(app.dialogs[0].message, "Task 1 is not running.");
+    press(&mut app, &[KeyCode::Esc]);
+    assert!(app.dialogs.is_empty());
+}
+
+#[test]
+fn quitting_asks_only_while_tasks_run() {
+    let mut app = app_with(TaskStatus::Done);
+    assert!(press(&mut app, &[KeyCode::Char('q')]).1);
+
+    let mut app = app_with(TaskStatus::Pending);
+    app.apply_update(TaskUpdate::Upsert(snapshot(2, TaskStatus::Running)));
+    assert!(!press(&mut app, &[KeyCode::Char('q')]).1);
+    assert_eq!(app.dialogs[0].message, "2 tasks are still running. Quit and stop them?");
+    assert!(!press(&mut app, &[KeyCode::Esc]).1);
+    assert!(press(&mut app, &[KeyCode::Char('q'), KeyCode::Char('y')]).1);
+}
+
+#[test]
+fn export_prompt_runs_the_completed_command() {
+    let mut app = app_with(TaskStatus::Done);
+    press(&mut app, &[KeyCode::Char('e')]);
+    let Some(DialogKind::Prompt { input, .. }) = app.dialogs.last().map(|dialog| &dialog.kind)
+    else {
+        panic!("{:?}", app.dialogs);
+    };
+    assert_eq!(input.as_str(), "markdown");
+    let screen = draw(&app).join("\n");
+    assert!(screen.contains("> markdown"), "{screen}");
+
+    let mut keys = vec![KeyCode::Backspace; "markdown".len()];
+    keys.extend("JS".chars().map(KeyCode::Char));
+    press(&mut app, &keys);
+    dispatch_paste("ON", &mut app);
+    let (commands, _) = press(&mut app, &[KeyCode::Enter]);
+    assert!(matches!(
+        commands.as_slice(),
+        [UiToEngine::ExportReport { id: 1, format: ReportFormat::Json }]
+    ));
+
+    press(&mut app, &[KeyCode::Char('e'), KeyCode::Char('x'), KeyCode::Enter]);
+    assert!(matches!(app.dialogs[0].kind, DialogKind::Error), "{:?}", app.dialogs);
+
+    let mut app = app_with(TaskStatus::Running);
+    press(&mut app, &[KeyCode::Char('e')]);
+    assert_eq!(app.dialogs[0].message, "Task 1 has no results to export.");
+}
+
+#[test]
+fn engine_errors_open_one_dialog_per_task_over_any_screen() {
+    let mut app = app_with(TaskStatus::Running);
+    app.apply_update(TaskUpdate::Log {
+        id: 1,
+        level: LogLevel::Error,
+        message: "Report generation failed: disk full".to_string(),
+    });
+    app.apply_update(TaskUpdate::Upsert(snapshot(1, TaskStatus::Failed("disk full".into()))));
+    // Later updates of a failed task don't report it again.
+    app.apply_update(TaskUpdate::Upsert(snapshot(1, TaskStatus::Failed("disk full".into()))));
+    assert_eq!(app.dialogs.len(), 1);
+    assert_eq!(
+        app.dialogs[0].message,
+        "Report generation failed: disk full\nTask failed: disk full"
+    );
+
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenHypotheses));
+    app.open_dialog(Dialog::message("Note", "on top"));
+    let screen = draw(&app).join("\n");
+    assert!(screen.contains("Note (1 more)"), "{screen}");
+    assert!(!screen.contains("Task 1 error"), "{screen}");
+
+    click(&mut app, "[ Enter OK ]");
+    let screen = draw(&app).join("\n");
+    assert!(screen.contains("Task 1 error"), "{screen}");
+    assert!(screen.contains("Task failed: disk full"), "{screen}");
+    press(&mut app, &[KeyCode::Char('q')]);
+    assert!(app.dialogs.is_empty());
+    assert_eq!(app.screen, ScreenId::Hypotheses, "the key closed the dialog only");
+}
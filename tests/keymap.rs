@@ -0,0 +1,104 @@
+mod common;
+
+use std::env;
+use std::fs;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use revolver::app::{AppEvent, AppState};
+use revolver::config::AppConfig;
+use revolver::screens::keymap::KeyChord;
+use revolver::screens::{dispatch_key, ScreenId};
+
+use common::{apply, lines};
+
+#[test]
+fn chords_parse_and_display() {
+    let ctrl_s = KeyChord::parse("ctrl+s").unwrap();
+    assert_eq!(ctrl_s, KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert_eq!(ctrl_s.to_string(), "Ctrl+s");
+    assert_eq!(KeyChord::parse("F5").unwrap(), KeyChord::plain(KeyCode::F(5)));
+    assert_eq!(KeyChord::parse("Esc").unwrap().to_string(), "Esc");
+    assert_eq!(KeyChord::parse("pgdn").unwrap().to_string(), "PgDn");
+    assert_eq!(KeyChord::parse("shift+tab").unwrap(), KeyChord::plain(KeyCode::BackTab));
+    assert_eq!(KeyChord::parse("shift+a").unwrap(), KeyChord::plain(KeyCode::Char('A')));
+    assert_eq!(
+        KeyChord::parse("Alt++").unwrap(),
+        KeyChord::new(KeyCode::Char('+'), KeyModifiers::ALT)
+    );
+    assert_eq!(KeyChord::parse("+").unwrap(), KeyChord::plain(KeyCode::Char('+')));
+    assert_eq!(
+        KeyChord::parse("hyper+x"),
+        Err("unknown modifier 'hyper' in 'hyper+x'".to_string())
+    );
+    assert_eq!(KeyChord::parse("ctrl+"), Err("unknown key 'ctrl+'".to_string()));
+    assert!(KeyChord::parse("f25").is_err());
+
+    let colon = KeyEvent::new(KeyCode::Char(':'), KeyModifiers::SHIFT);
+    assert_eq!(KeyChord::from_event(&colon), KeyChord::plain(KeyCode::Char(':')));
+}
+
+fn config_with(name: &str, contents: &str) -> Result<AppConfig, String> {
+    let path = env::temp_dir().join(format!("revolver_keys_{}_{name}.toml", std::process::id()));
+    fs::write(&path, contents).unwrap();
+    let config = AppConfig::load_layers(std::slice::from_ref(&path), Vec::new());
+    let _ = fs::remove_file(path);
+    config
+}
+
+fn controls(app: &AppState) -> String {
+    lines(app, 160, 30).remove(28)
+}
+
+fn quits(app: &mut AppState, key: KeyEvent) -> bool {
+    dispatch_key(key, app).unwrap();
+    apply(app).1
+}
+
+#[test]
+fn config_overrides_rebind_keys_and_regenerate_help() {
+    let default_help = controls(&AppState::new());
+    assert!(default_help.contains("n new task"), "{default_help}");
+    assert!(default_help.contains("j/k move/scroll"), "{default_help}");
+    assert!(default_help.contains("i/: command"), "{default_help}");
+    assert!(default_help.contains("q quit"), "{default_help}");
+
+    let config = config_with(
+        "rebind",
+        "[keys.main]\nquit = [\"ctrl+q\"]\nopen_hypotheses = [\"n\"]\n",
+    )
+    .unwrap();
+    let mut app = AppState::with_config(config);
+    let help = controls(&app);
+    assert!(help.contains("Ctrl+q quit"), "{help}");
+    assert!(help.contains("n hypotheses"), "{help}");
+    assert!(!help.contains("new task"), "{help}");
+
+    assert!(!quits(&mut app, KeyEvent::from(KeyCode::Char('q'))));
+    assert!(!quits(&mut app, KeyEvent::from(KeyCode::Char('n'))));
+    assert_eq!(app.screen, ScreenId::Main);
+    assert!(quits(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)
+    ));
+
+    app.apply_event(AppEvent::Main(revolver::app::MainScreenEvent::OpenTaskInput));
+    let help = controls(&app);
+    assert!(help.contains("F1/F2 switch fragment"), "{help}");
+    assert!(help.contains("Ctrl+d/F5 delete/duplicate"), "{help}");
+}
+
+#[test]
+fn invalid_key_overrides_are_config_errors() {
+    let err = config_with("action", "[keys.main]\nlaunch = [\"l\"]\n").unwrap_err();
+    assert!(err.contains("keys.main: unknown action 'launch'"), "{err}");
+    let err = config_with("key", "[keys.task_input]\nsubmit = [\"hyper+enter\"]\n").unwrap_err();
+    assert!(err.contains("keys.task_input.submit: unknown modifier 'hyper'"), "{err}");
+    assert!(config_with("screen", "[keys.settings]\nquit = [\"q\"]\n").is_err());
+    let err = config_with("typed", "[keys.task_input]\nadd_item = [\"+\"]\n").unwrap_err();
+    assert!(
+        err.contains("keys.task_input.add_item: '+' types text in a field; add ctrl or alt"),
+        "{err}"
+    );
+    assert!(config_with("typed_ctrl", "[keys.task_input]\nadd_item = [\"ctrl++\"]\n").is_ok());
+}
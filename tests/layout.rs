@@ -0,0 +1,55 @@
+mod common;
+
+use revolver::app::{AppEvent, AppState, MainScreenEvent};
+use revolver::screens::ScreenId;
+use revolver::task::TaskStatus;
+
+use common::{app_with, lines, position};
+
+#[test]
+fn wide_screens_split_side_by_side_and_narrow_ones_stack() {
+    let app = AppState::new();
+    let wide = lines(&app, 120, 32);
+    let (tasks, detail) = (position(&wide, "Tasks [T]"), position(&wide, "Task Detail [D]"));
+    assert_eq!(tasks.1, detail.1);
+    assert!(tasks.0 < detail.0);
+
+    let narrow = lines(&app, 80, 32);
+    let tasks = position(&narrow, "Tasks [T]");
+    let detail = position(&narrow, "Task Detail [D]");
+    let logs = position(&narrow, "Logs [L]");
+    assert!(tasks.1 < detail.1 && detail.1 < logs.1);
+    assert_eq!((tasks.0, detail.0, logs.0), (1, 1, 1));
+}
+
+#[test]
+fn short_screens_collapse_the_header_and_controls_bar() {
+    let tall = lines(&app_with(TaskStatus::Pending), 120, 32);
+    assert_eq!(position(&tall, "Controls").1, 29);
+
+    for (open, controls) in [
+        (None, "n new task"),
+        (Some(MainScreenEvent::OpenTaskInput), "F1/F2 switch"),
+        (Some(MainScreenEvent::OpenHypotheses), "Esc/q back"),
+    ] {
+        let mut app = app_with(TaskStatus::Pending);
+        if let Some(event) = open {
+            app.apply_event(AppEvent::Main(event));
+            assert_ne!(app.screen, ScreenId::Main);
+        }
+        let short = lines(&app, 100, 24).join("\n");
+        assert!(!short.contains("Controls"), "{short}");
+        assert!(short.lines().next().unwrap().contains("Revolver"), "{short}");
+        assert!(short.lines().last().unwrap().contains(controls), "{short}");
+    }
+}
+
+#[test]
+fn tiny_terminals_show_a_size_warning() {
+    let app = AppState::new();
+    let screen = lines(&app, 50, 15).join("\n");
+    assert!(screen.contains("Terminal too small"), "{screen}");
+    assert!(screen.contains("50x15, need at least 60x20"), "{screen}");
+    assert!(!screen.contains("Tasks [T]"));
+}
+
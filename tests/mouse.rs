@@ -0,0 +1,96 @@
+mod common;
+
+use crossterm::event::{MouseButton, MouseEventKind};
+
+use revolver::app::{AppEvent, AppState, DraftField, MainScreenEvent, TaskInputEvent};
+use revolver::engine::scheduler::TaskUpdate;
+use revolver::screens::{FragmentId, ScreenId};
+use revolver::task::TaskStatus;
+
+use common::{click, find, hypothesis, mouse, snapshot};
+
+fn app_with_tasks() -> AppState {
+    let mut app = AppState::new();
+    for id in 1..=3 {
+        let mut snapshot = snapshot(id, TaskStatus::Pending);
+        snapshot.verified = (0..3).map(|n| hypothesis(n, 0.5)).collect();
+        app.apply_update(TaskUpdate::Upsert(snapshot));
+    }
+    app
+}
+
+#[test]
+fn clicks_focus_fragments_and_select_task_rows() {
+    let mut app = app_with_tasks();
+    click(&mut app, "Logs [L]");
+    assert_eq!(app.fragment, FragmentId::MainLogs);
+    click(&mut app, "task 3");
+    assert_eq!((app.fragment, app.selected), (FragmentId::MainTasks, 2));
+
+    let tasks = find(&app, "task 2");
+    mouse(&mut app, MouseEventKind::ScrollDown, tasks);
+    assert_eq!(app.selected, 2, "already on the last task");
+    mouse(&mut app, MouseEventKind::ScrollUp, tasks);
+    mouse(&mut app, MouseEventKind::ScrollUp, tasks);
+    assert_eq!(app.selected, 0);
+
+    click(&mut app, "Press ':'");
+    assert_eq!(app.fragment, FragmentId::MainInput);
+    app.apply_event(AppEvent::Main(MainScreenEvent::FocusTasks));
+
+    click(&mut app, "hypotheses");
+    assert_eq!(app.screen, ScreenId::Hypotheses);
+    click(&mut app, "idea 2");
+    assert_eq!(app.explorer.selected, 2);
+    click(&mut app, "Esc/q back");
+    assert_eq!(app.screen, ScreenId::Main);
+}
+
+#[test]
+fn help_buttons_run_their_own_key_and_the_label_runs_the_first() {
+    let mut app = app_with_tasks();
+    click(&mut app, "new task");
+    assert_eq!(app.screen, ScreenId::TaskInput);
+
+    let (x, y) = find(&app, "F1/F2 switch fragment");
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), (x + 3, y));
+    assert_eq!(app.fragment, FragmentId::TaskHypotheses);
+    click(&mut app, "F1/F2 switch fragment");
+    assert_eq!(app.fragment, FragmentId::TaskDescription);
+}
+
+#[test]
+fn task_input_boxes_and_lists_respond_to_the_mouse() {
+    let mut app = AppState::new();
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    app.add_heuristic("edges".to_string());
+    app.add_heuristic("blobs".to_string());
+    app.apply_event(AppEvent::TaskInput(TaskInputEvent::FocusField(DraftField::Name)));
+
+    click(&mut app, "Dataset Folder");
+    assert_eq!(app.draft.field, DraftField::DatasetFolder);
+    click(&mut app, "blobs");
+    assert_eq!(app.draft.field, DraftField::Heuristics);
+    assert_eq!(app.draft.selected_heuristic, 1);
+    let titles = find(&app, "edges");
+    mouse(&mut app, MouseEventKind::ScrollUp, titles);
+    assert_eq!(app.draft.selected_heuristic, 0);
+
+    click(&mut app, "Seed Hypotheses");
+    assert_eq!(app.fragment, FragmentId::TaskHypotheses);
+    click(&mut app, "Name");
+    assert_eq!(
+        (app.fragment, app.draft.field),
+        (FragmentId::TaskDescription, DraftField::Name)
+    );
+
+    click(&mut app, "Dataset Folder");
+    app.apply_event(AppEvent::TaskInput(TaskInputEvent::OpenPathPicker));
+    assert!(app.path_picker.is_some());
+    let (x, y) = find(&app, "F1/F2 switch fragment");
+    mouse(&mut app, MouseEventKind::Down(MouseButton::Left), (x + 3, y));
+    assert_eq!(app.fragment, FragmentId::TaskDescription);
+    mouse(&mut app, MouseEventKind::ScrollDown, (1, 1));
+    let picker = app.path_picker.as_ref().expect("clicks behind the picker are ignored");
+    assert_eq!(picker.selected, 1.min(picker.visible().len() - 1));
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::PathBuf;
+
+use revolver::app::{
+    AppEvent, AppState, DraftList, MainScreenEvent, PathPickerEvent, TaskInputEvent, TextEditEvent,
+};
+use revolver::path_picker::{fuzzy_match, EntryKind, PathPicker, PathTarget};
+use revolver::protocol::UiToEngine;
+use revolver::spec::SpecField;
+
+fn fixture(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("revolver_picker_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("cats")).unwrap();
+    fs::create_dir_all(dir.join("dogs")).unwrap();
+    fs::write(dir.join("cats").join("tabby.png"), b"png").unwrap();
+    fs::write(dir.join("notes.txt"), b"text").unwrap();
+    fs::write(dir.join("cover.jpg"), b"jpg").unwrap();
+    fs::canonicalize(dir).unwrap()
+}
+
+fn names(picker: &PathPicker) -> Vec<String> {
+    picker
+        .visible()
+        .into_iter()
+        .map(|entry| entry.name.clone())
+        .collect()
+}
+
+#[test]
+fn picker_lists_filters_and_completes() {
+    let dir = fixture("list");
+    let mut picker = PathPicker::open(PathTarget::Image, &dir.join("missing.png").to_string_lossy());
+    assert_eq!(picker.dir, dir);
+    assert_eq!(names(&picker), vec!["..", "cats", "dogs", "cover.jpg", "notes.txt"]);
+    let images = picker
+        .visible()
+        .into_iter()
+        .filter(|entry| entry.is_image)
+        .map(|entry| entry.name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(images, vec!["cover.jpg"]);
+
+    picker.push_filter('c');
+    picker.push_filter('j');
+    assert_eq!(names(&picker), vec!["cover.jpg"]);
+    picker.pop_filter();
+    picker.pop_filter();
+    picker.push_filter('c');
+    picker.complete();
+    assert_eq!(picker.filter, "c");
+    picker.push_filter('a');
+    picker.complete();
+    assert_eq!(picker.dir, dir.join("cats"));
+    assert_eq!(names(&picker), vec!["..", "tabby.png"]);
+
+    picker.select_next();
+    assert_eq!(
+        picker.confirm(),
+        Some(dir.join("cats").join("tabby.png").to_string_lossy().to_string())
+    );
+    picker.pop_filter();
+    assert_eq!(picker.dir, dir);
+
+    let mut folders = PathPicker::open(PathTarget::DatasetFolder, &dir.to_string_lossy());
+    assert_eq!(folders.selected_entry().map(|entry| entry.kind), Some(EntryKind::Current));
+    assert_eq!(folders.confirm(), Some(dir.to_string_lossy().to_string()));
+
+    assert!(fuzzy_match("new_image.png", "NIM"));
+    assert!(!fuzzy_match("new_image.png", "gx"));
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn missing_paths_block_submit_until_picked() {
+    let dir = fixture("submit");
+    let mut app = AppState::new();
+    let apply = |app: &mut AppState, events: Vec<TaskInputEvent>| {
+        events
+            .into_iter()
+            .filter_map(|event| app.apply_event(AppEvent::TaskInput(event)).cmd)
+            .collect::<Vec<_>>()
+    };
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    apply(
+        &mut app,
+        vec![
+            TaskInputEvent::Edit(TextEditEvent::InsertChar('p')),
+            TaskInputEvent::SwitchField,
+            TaskInputEvent::SwitchField,
+            TaskInputEvent::AddHeuristic,
+            TaskInputEvent::Edit(TextEditEvent::CursorRight),
+            TaskInputEvent::Edit(TextEditEvent::InsertChar('x')),
+        ],
+    );
+    assert_eq!(app.focused_list(), Some(DraftList::Images));
+    assert_eq!(app.missing_paths(), vec!["x".to_string()]);
+
+    app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorLeft)));
+    app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorLeft)));
+    assert!(apply(&mut app, vec![TaskInputEvent::Submit]).is_empty());
+    assert_eq!(app.draft.issues.len(), 1);
+    assert_eq!(
+        app.draft.issues[0].field,
+        SpecField::Image {
+            heuristic: 0,
+            image: 0
+        }
+    );
+
+    app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(TextEditEvent::CursorRight)));
+    apply(&mut app, vec![TaskInputEvent::OpenPathPicker]);
+    app.path_picker.as_mut().unwrap().change_dir(dir.clone());
+    let picked = "cov"
+        .chars()
+        .map(|ch| TaskInputEvent::PathPicker(PathPickerEvent::Filter(TextEditEvent::InsertChar(ch))))
+        .chain([TaskInputEvent::PathPicker(PathPickerEvent::Confirm)])
+        .collect();
+    apply(&mut app, picked);
+    assert!(app.path_picker.is_none());
+    let cover = dir.join("cover.jpg").to_string_lossy().to_string();
+    assert_eq!(app.draft.heuristics[0].images[0].as_str(), cover);
+    assert!(app.missing_paths().is_empty());
+    assert!(app.draft.issues.is_empty());
+
+    let commands = apply(
+        &mut app,
+        vec![TaskInputEvent::FocusHypotheses, TaskInputEvent::Submit],
+    );
+    match commands.as_slice() {
+        [UiToEngine::AddTaskFromSpec { spec }] => {
+            assert_eq!(spec.heuristics[0].images, vec![cover]);
+        }
+        other => panic!("unexpected commands {other:?}"),
+    }
+    let _ = fs::remove_dir_all(dir);
+}
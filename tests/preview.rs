@@ -0,0 +1,150 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+use revolver::app::{AppEvent, AppState, HypothesesEvent, MainScreenEvent};
+use revolver::engine::scheduler::TaskUpdate;
+use revolver::preview::{ImagePreviews, PreviewProtocol, Thumbnail};
+use revolver::task::{Hypothesis, ImageResult, TaskDefinition, TaskSnapshot};
+use revolver::text::TextBuffer;
+
+use common::screen;
+
+const RED: [u8; 3] = [255, 0, 0];
+const BLUE: [u8; 3] = [0, 0, 255];
+
+/// A 4×4 PNG, red on top and blue below.
+fn fixture(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("revolver_preview_{name}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("split.png");
+    let image = image::RgbImage::from_fn(4, 4, |_, y| image::Rgb(if y < 2 { RED } else { BLUE }));
+    image.save(&path).unwrap();
+    path
+}
+
+#[test]
+fn thumbnails_render_as_half_blocks_and_encode_for_graphics_protocols() {
+    let path = fixture("encode");
+    let thumbnail = Thumbnail::load(&path, 2, 2).unwrap();
+    assert_eq!((thumbnail.width, thumbnail.height), (2, 2));
+    assert_eq!(thumbnail.cells((1, 2)), (2, 1));
+
+    let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+    thumbnail.render_half_blocks(Rect::new(1, 0, 3, 2), &mut buf);
+    let cell = buf.get(1, 0);
+    assert_eq!(cell.symbol(), "▀");
+    assert_eq!((cell.fg, cell.bg), (Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255)));
+    assert_eq!(buf.get(0, 0).symbol(), " ");
+    assert_eq!(buf.get(1, 1).symbol(), " ");
+
+    let sixel = thumbnail.sixel();
+    assert!(sixel.starts_with("\x1bPq\"1;1;2;2"), "{sixel:?}");
+    assert!(sixel.contains("#180;2;100;0;0"), "{sixel:?}");
+    assert!(sixel.contains("#5;2;0;0;100"), "{sixel:?}");
+    assert!(sixel.ends_with("-\x1b\\"), "{sixel:?}");
+
+    let kitty = thumbnail.kitty(2, 1);
+    assert!(
+        kitty.starts_with("\x1b_Ga=T,f=24,s=2,v=2,c=2,r=1,C=1,q=2,m=0;/wAA/wAAAAD/AAD/"),
+        "{kitty:?}"
+    );
+
+    assert!(Thumbnail::load(&path.with_file_name("missing.png"), 2, 2).is_err());
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn protocol_detection_and_graphics_placements() {
+    assert_eq!(PreviewProtocol::detect("xterm-kitty", "", false), PreviewProtocol::Kitty);
+    assert_eq!(PreviewProtocol::detect("xterm-256color", "WezTerm", false), PreviewProtocol::Kitty);
+    assert_eq!(PreviewProtocol::detect("foot", "", false), PreviewProtocol::Sixel);
+    assert_eq!(PreviewProtocol::detect("xterm-256color", "", false), PreviewProtocol::HalfBlocks);
+    assert_eq!(PreviewProtocol::Sixel.resolve(), PreviewProtocol::Sixel);
+
+    let path = fixture("placements");
+    let path = path.to_string_lossy().to_string();
+    let mut previews = ImagePreviews::new(PreviewProtocol::Sixel);
+    previews.cell_size = (2, 4);
+    let area = Rect::new(0, 0, 10, 4);
+    let mut buf = Buffer::empty(area);
+
+    previews.render(&path, area, &mut buf).unwrap();
+    let graphics = previews.take_graphics().expect("first placement is written");
+    assert!(!graphics.redraw);
+    let mut out = Vec::new();
+    graphics.write(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("\x1b[1;2H\x1bPq\"1;1;16;16"), "{out:?}");
+
+    previews.render(&path, area, &mut buf).unwrap();
+    assert!(previews.take_graphics().is_none(), "unchanged frames write nothing");
+    let graphics = previews.take_graphics().expect("removing the image is a change");
+    assert!(graphics.redraw);
+
+    assert!(previews.render("missing.png", area, &mut buf).is_err());
+    let _ = fs::remove_dir_all(PathBuf::from(path).parent().unwrap());
+}
+
+fn has_half_blocks(buffer: &Buffer) -> bool {
+    buffer
+        .content()
+        .iter()
+        .any(|cell| cell.symbol() == "▀" && cell.fg == Color::Rgb(255, 0, 0))
+}
+
+#[test]
+fn screens_preview_heuristic_and_result_images() {
+    let path = fixture("screens");
+    let image = path.to_string_lossy().to_string();
+
+    let mut app = AppState::new();
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    assert!(!has_half_blocks(&screen(&app, 120, 30)));
+    app.add_heuristic("edges".to_string());
+    app.draft.heuristics[0].images.push(TextBuffer::new(image.as_str()));
+    assert!(has_half_blocks(&screen(&app, 120, 30)));
+
+    let mut snapshot = TaskSnapshot::from_definition(&TaskDefinition::mock(1, "preview".to_string()));
+    snapshot.verified = vec![Hypothesis {
+        id: 0,
+        iteration: 1,
+        description: "red on top".to_string(),
+        rationale: String::new(),
+        score: 0.5,
+        results: vec![
+            ImageResult {
+                image_id: 0,
+                image: "mock.png".to_string(),
+                path: None,
+                passed: false,
+            },
+            ImageResult {
+                image_id: 1,
+                image: "split.png".to_string(),
+                path: Some(image),
+                passed: true,
+            },
+        ],
+    }];
+    let mut app = AppState::new();
+    app.apply_update(TaskUpdate::Upsert(snapshot));
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenHypotheses));
+    let buffer = screen(&app, 120, 30);
+    assert!(!has_half_blocks(&buffer));
+    let text = buffer.content().iter().map(|cell| cell.symbol()).collect::<String>();
+    assert!(text.contains("No image file (mock dataset)."), "{text}");
+
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::NextImage));
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::NextImage));
+    assert_eq!(app.explorer.image, 1);
+    assert!(has_half_blocks(&screen(&app, 120, 30)));
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::PrevImage));
+    assert_eq!(app.explorer.image, 0);
+    let _ = fs::remove_dir_all(path.parent().unwrap());
+}
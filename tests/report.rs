@@ -0,0 +1,223 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::json;
+
+use revolver::report::html::{render_html_report, THUMBNAIL_SIZE};
+use revolver::report::json::{render_json_report, JsonReport, SCHEMA_VERSION};
+use revolver::report::template::render;
+use revolver::report::{render_markdown_report, TaskOutcome, DEFAULT_TEMPLATE};
+use revolver::spec::TaskSpec;
+use revolver::task::{IterationRecord, Prediction, TaskDefinition};
+
+use common::hypothesis;
+
+fn iteration_record(iteration: usize, best_score: f32, program_version: Option<usize>) -> IterationRecord {
+    IterationRecord {
+        iteration,
+        generated: 3,
+        verified: 1,
+        best_score,
+        last_score: best_score,
+        elapsed_ms: 40,
+        program_version,
+    }
+}
+
+#[test]
+fn template_renders_values_blocks_and_formats() {
+    let context = json!({
+        "name": "demo",
+        "score": 0.12345,
+        "labels": ["a", "b"],
+        "items": [{"id": 1, "score": 0.2}, {"id": 2, "score": 0.9}, {"id": 3, "score": 0.5}],
+        "empty": [],
+    });
+    let template = "\
+{{name}} {{score:.2}} [{{labels}}]
+{{#each items sort=-score limit=2}}
+  {{@number}}. #{{id}} {{score:.1}}
+{{/each}}
+{{#each empty}}
+never
+{{else}}
+none
+{{/each}}
+{{#if missing}}yes{{else}}no{{/if}}
+";
+    assert_eq!(
+        render(template, &context).unwrap(),
+        "demo 0.12 [a, b]\n  1. #2 0.9\n  2. #3 0.5\nnone\nno\n"
+    );
+}
+
+#[test]
+fn template_reports_syntax_errors() {
+    assert!(render("{{#each items}}open", &json!({})).is_err());
+    assert!(render("{{/if}}", &json!({})).is_err());
+    assert!(render("{{#each items limit=x}}{{/each}}", &json!({})).is_err());
+    assert!(render("{{name", &json!({})).is_err());
+}
+
+#[test]
+fn default_template_keeps_report_layout() {
+    let mut spec = TaskSpec::named("Edge sweep");
+    spec.labels = vec!["defect".to_string(), "clean".to_string()];
+    let definition = TaskDefinition::from_spec(4, &spec);
+    let outcome = TaskOutcome {
+        verified: (0..25).map(|id| hypothesis(id, 0.7)).collect(),
+        discarded: Vec::new(),
+        program: "fn solve() {}".to_string(),
+        best_score: 0.8125,
+        predictions: Vec::new(),
+        iterations: Vec::new(),
+        started_at: UNIX_EPOCH,
+        finished_at: UNIX_EPOCH,
+    };
+    let report = render_markdown_report(&definition, &outcome, DEFAULT_TEMPLATE).unwrap();
+    assert!(report.starts_with("# Task Report: Edge sweep\n\n## Task Definition\n- Task id: 4\n"));
+    assert!(report.contains("- Labels: defect, clean\n- Max iterations: 6\n"));
+    assert!(report.contains("### Dataset sample\n- 0: image_4_00.png\n"));
+    assert!(report.contains("- Best score: 0.812\n- Verified hypotheses: 25\n"));
+    assert_eq!(report.matches("(score 0.70)").count(), 20);
+    assert!(report.contains("## Discarded Hypotheses\n- _None_\n\n"));
+    assert!(report.ends_with("```rust\nfn solve() {}\n```\n"));
+}
+
+#[test]
+fn custom_template_selects_hypotheses() {
+    let definition = TaskDefinition::from_spec(1, &TaskSpec::named("custom"));
+    let outcome = TaskOutcome {
+        verified: vec![hypothesis(1, 0.61), hypothesis(2, 0.95), hypothesis(3, 0.72)],
+        discarded: vec![hypothesis(4, 0.1)],
+        program: String::new(),
+        best_score: 0.95,
+        predictions: Vec::new(),
+        iterations: Vec::new(),
+        started_at: UNIX_EPOCH,
+        finished_at: UNIX_EPOCH,
+    };
+    let template = "{{#each verified sort=-score limit=1}}top: {{description}}{{/each}}";
+    assert_eq!(
+        render_markdown_report(&definition, &outcome, template).unwrap(),
+        "top: idea 2"
+    );
+}
+
+#[test]
+fn html_report_is_self_contained() {
+    let dataset = env::temp_dir().join(format!("revolver_html_dataset_{}", std::process::id()));
+    fs::create_dir_all(dataset.join("defect")).unwrap();
+    fs::create_dir_all(dataset.join("clean")).unwrap();
+    image::RgbImage::from_pixel(1200, 600, image::Rgb([200, 40, 40]))
+        .save(dataset.join("defect").join("a.png"))
+        .unwrap();
+    fs::write(dataset.join("clean").join("b.png"), b"not a png").unwrap();
+    let mut spec = TaskSpec::named("<Edge> & blobs");
+    spec.dataset_folder = dataset.to_string_lossy().to_string();
+    let definition = TaskDefinition::from_spec(2, &spec);
+    assert_eq!(definition.label_set(), vec!["clean", "defect"]);
+
+    let predictions = definition
+        .dataset
+        .iter()
+        .map(|image| Prediction {
+            image_id: image.id,
+            image: image.name.clone(),
+            predicted: "defect".to_string(),
+            expected: image.label.clone(),
+        })
+        .collect();
+    let outcome = TaskOutcome {
+        verified: vec![hypothesis(1, 0.9)],
+        discarded: vec![hypothesis(2, 0.2)],
+        program: "fn solve() -> Vec<Label> {}".to_string(),
+        best_score: 0.9,
+        predictions,
+        iterations: vec![
+            iteration_record(1, 0.7, Some(1)),
+            iteration_record(2, 0.9, Some(2)),
+        ],
+        started_at: UNIX_EPOCH,
+        finished_at: UNIX_EPOCH,
+    };
+    assert_eq!(outcome.accuracy(), Some(0.5));
+
+    let html = render_html_report(&definition, &outcome);
+    let _ = fs::remove_dir_all(&dataset);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("Task 2 — &lt;Edge&gt; &amp; blobs"));
+    let prefix = "data:image/jpeg;base64,";
+    assert_eq!(html.matches(prefix).count(), 1);
+    let start = html.find(prefix).unwrap() + prefix.len();
+    let encoded = &html[start..start + html[start..].find('"').unwrap()];
+    let jpeg = BASE64.decode(encoded).unwrap();
+    let thumbnail = image::load_from_memory(&jpeg).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2));
+    assert!(html.contains("<div class=\"placeholder\">no preview</div>"));
+    assert_eq!(html.matches("class=\"card ok\"").count(), 1);
+    assert_eq!(html.matches("class=\"card bad\"").count(), 1);
+    assert!(html.contains("<svg"));
+    assert!(html.contains("<details open>\n<summary>Verified hypotheses (1)</summary>"));
+    assert!(html.contains("<details>\n<summary>Discarded hypotheses (1)</summary>"));
+    assert!(html.contains("fn solve() -&gt; Vec&lt;Label&gt; {}"));
+    assert!(!html.contains("<script"));
+}
+
+#[test]
+fn json_report_lists_everything_with_schema_version() {
+    let mut spec = TaskSpec::named("json");
+    spec.labels = vec!["defect".to_string(), "clean".to_string()];
+    let definition = TaskDefinition::from_spec(3, &spec);
+    let outcome = TaskOutcome {
+        verified: (0..25).map(|id| hypothesis(200 + id, 0.7)).collect(),
+        discarded: vec![hypothesis(100, 0.1)],
+        program: "fn solve() {}".to_string(),
+        best_score: 0.7,
+        predictions: Vec::new(),
+        iterations: Vec::new(),
+        started_at: UNIX_EPOCH + Duration::from_millis(1_000),
+        finished_at: UNIX_EPOCH + Duration::from_millis(3_500),
+    };
+    let rendered = render_json_report(&definition, &outcome).unwrap();
+    let report: JsonReport = serde_json::from_str(&rendered).unwrap();
+    assert_eq!(report.schema_version, SCHEMA_VERSION);
+    assert_eq!(report.task.dataset.len(), 16);
+    assert_eq!(report.task.budget.max_iters, 6);
+    assert_eq!(report.metrics.hypotheses_total, 26);
+    assert_eq!(report.hypotheses.len(), 26);
+    assert_eq!(report.hypotheses[0].id, 100);
+    assert!(!report.hypotheses[0].verified);
+    assert_eq!(report.hypotheses[0].iteration, 1);
+    assert_eq!(report.timings.duration_ms, 2_500);
+    assert_eq!(report.program, "fn solve() {}");
+}
+
+#[test]
+fn default_template_renders_iteration_table() {
+    let definition = TaskDefinition::from_spec(5, &TaskSpec::named("history"));
+    let outcome = TaskOutcome {
+        verified: Vec::new(),
+        discarded: Vec::new(),
+        program: String::new(),
+        best_score: 0.9,
+        predictions: Vec::new(),
+        iterations: vec![
+            iteration_record(0, 0.2, Some(0)),
+            iteration_record(1, 0.0, None),
+            iteration_record(2, 0.9, Some(2)),
+        ],
+        started_at: UNIX_EPOCH,
+        finished_at: UNIX_EPOCH,
+    };
+    let report = render_markdown_report(&definition, &outcome, DEFAULT_TEMPLATE).unwrap();
+    assert!(report.contains("- Discarded hypotheses: 0\n\n## Iterations\n"));
+    assert!(report.contains("| 0 | 3 | 1 | 0.200 | 0.200 | v0 | 40 ms |\n"), "{report}");
+    assert!(report.contains("| 1 | 3 | 1 | 0.000 | 0.000 | - | 40 ms |\n"));
+    assert!(report.contains("| 2 | 3 | 1 | 0.900 | 0.900 | v2 | 40 ms |\n\n## Verified"));
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::collapsible_if)]
+
 use tokio::sync::mpsc;
 use tokio::time::{timeout, Duration};
 
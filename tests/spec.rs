@@ -0,0 +1,244 @@
+use std::env;
+use std::fs;
+
+use revolver::app::{
+    AppEvent, AppState, MainScreenEvent, TaskDraft, TaskInputEvent, TextEditEvent,
+};
+use revolver::protocol::UiToEngine;
+use revolver::spec::{HeuristicSpec, SpecField, SpecFormat, TaskSpec};
+
+#[test]
+fn example_spec_parses() {
+    let spec = TaskSpec::load("specs/example.toml").expect("example spec should load");
+    assert_eq!(spec.name, "Edge and blob detection");
+    assert_eq!(spec.heuristics.len(), 2);
+    assert_eq!(spec.heuristics[1].images.len(), 2);
+    assert_eq!(spec.hypotheses.len(), 3);
+    assert_eq!(spec.budget.max_iters, 6);
+    assert_eq!(spec.validate(), Vec::new(), "the example opens without issues");
+}
+
+#[test]
+fn unknown_spec_fields_are_rejected() {
+    let err = TaskSpec::parse("[budget]\nmax_iter = 50\n", SpecFormat::Toml).unwrap_err();
+    assert!(err.contains("unknown field `max_iter`"), "{err}");
+    let err = TaskSpec::parse_over("nmae = \"typo\"", SpecFormat::Toml, &TaskSpec::default())
+        .unwrap_err();
+    assert!(err.contains("unknown field `nmae`"), "{err}");
+    let json = r#"{"heuristics": [{"title": "edges", "image": "a.png"}]}"#;
+    assert!(TaskSpec::parse(json, SpecFormat::Json).is_err());
+}
+
+#[test]
+fn spec_round_trips_through_toml_and_json() {
+    let spec = TaskSpec::load("specs/example.toml").unwrap();
+    for format in [SpecFormat::Toml, SpecFormat::Json] {
+        let rendered = spec.render(format).unwrap();
+        assert_eq!(TaskSpec::parse(&rendered, format).unwrap(), spec);
+    }
+}
+
+#[test]
+fn partial_spec_uses_defaults() {
+    let spec = TaskSpec::parse("name = \"tiny\"", SpecFormat::Toml).unwrap();
+    assert_eq!(spec.name, "tiny");
+    assert_eq!(spec.dataset_folder, TaskSpec::default().dataset_folder);
+    assert_eq!(spec.budget, TaskSpec::default().budget);
+}
+
+#[test]
+fn draft_conversion_preserves_spec() {
+    let spec = TaskSpec::load("specs/example.toml").unwrap();
+    assert_eq!(TaskDraft::from_spec(&spec).to_spec(), spec);
+}
+
+#[test]
+fn task_input_saves_and_opens_spec() {
+    let path = env::temp_dir().join(format!("revolver_spec_{}.json", std::process::id()));
+    let mut app = AppState::new();
+    let _ = app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    for ch in "saved".chars() {
+        let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(
+            TextEditEvent::InsertChar(ch),
+        )));
+    }
+    app.draft.spec_path = path.to_string_lossy().to_string();
+    let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::SaveSpec));
+    assert!(path.exists());
+
+    let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(
+        TextEditEvent::Backspace,
+    )));
+    let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::OpenSpec));
+    assert_eq!(app.draft.name, "saved");
+    assert_eq!(app.input.as_str(), "saved");
+
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    match outcome.cmd {
+        Some(UiToEngine::AddTaskFromSpec { spec }) => assert_eq!(spec.name, "saved"),
+        other => panic!("expected AddTaskFromSpec, got {other:?}"),
+    }
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn validate_reports_every_problem_in_a_spec() {
+    let dir = env::temp_dir().join(format!("revolver_validate_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("unlabelled")).unwrap();
+    fs::write(dir.join("unlabelled").join("a.png"), b"png").unwrap();
+    fs::write(dir.join("notes.txt"), b"text").unwrap();
+    assert!(TaskSpec::named("mock").validate().is_empty());
+
+    let mut spec = TaskSpec::named(" ");
+    spec.dataset_folder = dir.join("missing").to_string_lossy().to_string();
+    spec.labels = vec!["cat".to_string(), "cat".to_string()];
+    spec.heuristics = vec![HeuristicSpec {
+        title: String::new(),
+        images: vec![
+            dir.join("notes.txt").to_string_lossy().to_string(),
+            dir.join("gone.png").to_string_lossy().to_string(),
+        ],
+    }];
+    spec.budget.max_iters = 0;
+    spec.budget.verify_threshold = 1.5;
+    spec.llm.temperature = -1.0;
+    let fields = spec
+        .validate()
+        .into_iter()
+        .map(|issue| issue.field)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            SpecField::Name,
+            SpecField::DatasetFolder,
+            SpecField::Labels,
+            SpecField::Heuristic(0),
+            SpecField::Image {
+                heuristic: 0,
+                image: 0
+            },
+            SpecField::Image {
+                heuristic: 0,
+                image: 1
+            },
+            SpecField::Budget,
+            SpecField::Budget,
+            SpecField::Llm,
+        ]
+    );
+
+    let mut spec = TaskSpec::named("real");
+    spec.dataset_folder = dir.to_string_lossy().to_string();
+    assert!(spec.validate().is_empty(), "subfolders provide labels");
+    spec.dataset_folder = dir.join("unlabelled").to_string_lossy().to_string();
+    let issues = spec.validate();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].field, SpecField::Labels);
+    spec.labels = vec!["cat".to_string()];
+    assert!(spec.validate().is_empty());
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn submit_is_blocked_until_the_draft_validates() {
+    let mut app = AppState::new();
+    let _ = app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    assert!(outcome.cmd.is_none());
+    assert!(!outcome.quit);
+    assert_eq!(app.draft.issues.len(), 1);
+    assert_eq!(app.draft.issues[0].field, SpecField::Name);
+
+    let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(
+        TextEditEvent::InsertChar('x'),
+    )));
+    assert!(app.draft.issues.is_empty(), "issues clear once resolved");
+
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    assert!(matches!(
+        outcome.cmd,
+        Some(UiToEngine::AddTaskFromSpec { .. })
+    ));
+}
+
+#[test]
+fn issues_the_screen_cannot_fix_only_warn_before_submit() {
+    let dir = env::temp_dir().join(format!("revolver_flat_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("a.png"), b"png").unwrap();
+
+    let mut app = AppState::new();
+    let _ = app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    app.draft.dataset_folder = dir.to_string_lossy().to_string();
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    assert!(outcome.cmd.is_none());
+    let fields = app.draft.issues.iter().map(|issue| issue.field).collect::<Vec<_>>();
+    assert_eq!(fields, vec![SpecField::Name, SpecField::Labels]);
+    assert!(app.draft.blocks_submit());
+
+    let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(
+        TextEditEvent::InsertChar('x'),
+    )));
+    assert_eq!(app.draft.issues.len(), 1);
+    assert!(!app.draft.blocks_submit(), "labels can't be edited here");
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    match outcome.cmd {
+        Some(UiToEngine::AddTaskFromSpec { spec }) => {
+            assert_eq!(spec.dataset_folder, dir.to_string_lossy());
+            assert!(spec.labels.is_empty());
+        }
+        other => panic!("expected AddTaskFromSpec, got {other:?}"),
+    }
+
+    // A warning nobody has seen yet holds back the first Submit.
+    let _ = app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    app.draft.dataset_folder = dir.to_string_lossy().to_string();
+    for ch in "flat".chars() {
+        let _ = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Edit(
+            TextEditEvent::InsertChar(ch),
+        )));
+    }
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    assert!(outcome.cmd.is_none());
+    assert_eq!(app.draft.issues[0].field, SpecField::Labels);
+    let outcome = app.apply_event(AppEvent::TaskInput(TaskInputEvent::Submit));
+    assert!(matches!(outcome.cmd, Some(UiToEngine::AddTaskFromSpec { .. })));
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn spec_paths_are_relative_to_the_spec_file() {
+    let root = env::temp_dir().join(format!("revolver_relative_{}", std::process::id()));
+    let dir = root.join("specs");
+    fs::create_dir_all(dir.join("data").join("cat")).unwrap();
+    fs::create_dir_all(dir.join("img")).unwrap();
+    fs::write(dir.join("data").join("cat").join("a.png"), b"png").unwrap();
+    fs::write(dir.join("img").join("edge.png"), b"png").unwrap();
+    let source = "name = \"relative\"\ndataset_folder = \"data\"\n\n[report]\ndir = \"out\"\n\n\
+                  [[heuristics]]\ntitle = \"edges\"\nimages = [\"img/edge.png\"]\n";
+    fs::write(dir.join("task.toml"), source).unwrap();
+
+    let spec = TaskSpec::load(dir.join("task.toml")).unwrap();
+    assert_eq!(spec.dataset_folder, dir.join("data").to_string_lossy());
+    assert_eq!(spec.heuristics[0].images[0], dir.join("img/edge.png").to_string_lossy());
+    assert_eq!(spec.report.dir.as_deref(), Some(&*dir.join("out").to_string_lossy()));
+    assert!(spec.validate().is_empty(), "{:?}", spec.validate());
+
+    let base = TaskSpec {
+        dataset_folder: "shared".to_string(),
+        ..TaskSpec::default()
+    };
+    fs::write(dir.join("bare.toml"), "name = \"bare\"\n").unwrap();
+    let bare = TaskSpec::load_over(dir.join("bare.toml"), &base).unwrap();
+    assert_eq!(bare.dataset_folder, "shared", "defaults are not the file's paths");
+
+    // Saved next to its files, the spec stays relative and moves with them.
+    spec.save(root.join("copy.toml")).unwrap();
+    let saved = fs::read_to_string(root.join("copy.toml")).unwrap();
+    assert!(saved.contains("dataset_folder = \"specs/data\""), "{saved}");
+    assert_eq!(TaskSpec::load(root.join("copy.toml")).unwrap(), spec);
+    let _ = fs::remove_dir_all(root);
+}
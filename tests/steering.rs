@@ -0,0 +1,363 @@
+mod common;
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use revolver::config::AppConfig;
+use revolver::engine::scheduler::{run_scheduler_with_config, TaskUpdate};
+use revolver::engine::steering::{Steering, SteeringState};
+use revolver::gateway::Gateway;
+use revolver::protocol::{EngineToUi, UiToEngine};
+use revolver::spec::TaskSpec;
+use revolver::task::{Hypothesis, TaskPhase, TaskSnapshot, TaskStatus};
+
+use common::hypothesis;
+
+fn ids(list: &[Hypothesis]) -> Vec<usize> {
+    list.iter().map(|h| h.id).collect()
+}
+
+#[test]
+fn pins_and_bans_move_hypotheses_until_released() {
+    let mut steering = SteeringState::new(0.6);
+    let mut verified = vec![hypothesis(100, 0.8)];
+    let mut discarded = vec![hypothesis(101, 0.3)];
+
+    steering.apply(Steering::Pin(101), &mut verified, &mut discarded).unwrap();
+    steering.apply(Steering::Ban(100), &mut verified, &mut discarded).unwrap();
+    assert_eq!(ids(&verified), vec![101]);
+    assert_eq!(ids(&discarded), vec![100]);
+    assert!(steering.is_banned("idea 100"));
+
+    steering.apply(Steering::Release(101), &mut verified, &mut discarded).unwrap();
+    steering.apply(Steering::Release(100), &mut verified, &mut discarded).unwrap();
+    assert_eq!(ids(&verified), vec![100]);
+    assert_eq!(ids(&discarded), vec![101]);
+    assert!(!steering.is_banned("idea 100"));
+
+    let inject = Steering::Inject("try shadows".to_string());
+    steering.apply(inject, &mut verified, &mut discarded).unwrap();
+    assert_eq!(steering.take_injected(), vec!["try shadows".to_string()]);
+    assert!(steering.take_injected().is_empty());
+
+    for unknown in [Steering::Pin(7), Steering::Ban(7), Steering::Release(7)] {
+        let reason = steering.apply(unknown, &mut verified, &mut discarded).unwrap_err();
+        assert_eq!(reason, "has no hypothesis 7");
+    }
+    assert_eq!(
+        Steering::Pin(7).dropped_message(1, "has no hypothesis 7"),
+        "Pin of hypothesis 7 was dropped: task 1 has no hypothesis 7."
+    );
+    assert_eq!(ids(&verified), vec![100]);
+    assert_eq!(ids(&discarded), vec![101]);
+}
+
+#[tokio::test]
+async fn injected_hypotheses_are_evaluated_by_the_running_task() {
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 20;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    let mut spec = TaskSpec::named("steered");
+    spec.budget.max_iters = 3;
+    spec.budget.patience = 3;
+    spec.report.dir = Some(
+        std::env::temp_dir()
+            .join(format!("revolver_steering_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string(),
+    );
+    config.reports.dir = spec.report.dir.clone().unwrap();
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    gateway.send(UiToEngine::AddTaskFromSpec { spec: spec.clone() }).await;
+
+    let mut injected = false;
+    let mut last: Option<TaskSnapshot> = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        let TaskUpdate::Upsert(snapshot) = update else {
+            continue;
+        };
+        if !injected {
+            gateway
+                .send(UiToEngine::InjectHypothesis {
+                    task_id: snapshot.id,
+                    text: "count dark pixels near the rim".to_string(),
+                })
+                .await;
+            injected = true;
+        }
+        let done = matches!(snapshot.status, TaskStatus::Done | TaskStatus::Failed(_));
+        last = Some(snapshot);
+        if done {
+            break;
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    let _ = std::fs::remove_dir_all(spec.report.dir.unwrap());
+
+    let last = last.expect("no task updates");
+    assert!(matches!(last.status, TaskStatus::Done));
+    let found = last
+        .verified
+        .iter()
+        .chain(&last.discarded)
+        .find(|h| h.description == "count dark pixels near the rim")
+        .expect("injected hypothesis was not evaluated");
+    assert_eq!(found.rationale, "Injected by the user.");
+}
+
+#[tokio::test]
+async fn seed_hypotheses_are_evaluated_as_iteration_zero() {
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 0;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    let mut spec = TaskSpec::named("seeded");
+    spec.hypotheses = vec!["edge density".to_string(), "blob count".to_string()];
+    spec.budget.max_iters = 1;
+    spec.report.dir = Some(
+        std::env::temp_dir()
+            .join(format!("revolver_seeds_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string(),
+    );
+    config.reports.dir = spec.report.dir.clone().unwrap();
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    let spec_dir = spec.report.dir.clone().unwrap();
+    gateway.send(UiToEngine::AddTaskFromSpec { spec }).await;
+
+    let mut first_log = None;
+    let mut seeded: Option<TaskSnapshot> = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        match update {
+            TaskUpdate::Log { message, .. } if message.starts_with("Iteration") => {
+                first_log.get_or_insert(message);
+            }
+            TaskUpdate::Upsert(snapshot) if snapshot.iteration == 0 && !snapshot.history.is_empty() => {
+                seeded = Some(snapshot);
+                break;
+            }
+            _ => {}
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    let _ = std::fs::remove_dir_all(spec_dir);
+
+    assert_eq!(
+        first_log.as_deref(),
+        Some("Iteration 0: evaluating 2 seed hypotheses.")
+    );
+    let seeded = seeded.expect("no update after evaluating seeds");
+    assert_eq!(seeded.history[0].iteration, 0);
+    assert_eq!(seeded.history[0].generated, 2);
+    let mut seeds = seeded
+        .verified
+        .iter()
+        .chain(&seeded.discarded)
+        .map(|h| (h.iteration, h.description.as_str()))
+        .collect::<Vec<_>>();
+    seeds.sort();
+    assert_eq!(seeds, vec![(0, "blob count"), (0, "edge density")]);
+}
+
+#[tokio::test]
+async fn bans_recompute_the_best_result_and_late_steering_is_reported() {
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 100;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    let mut spec = TaskSpec::named("banned");
+    spec.hypotheses = vec!["edge density".to_string(), "blob count".to_string()];
+    spec.budget.max_iters = 1;
+    spec.budget.verify_threshold = 0.0;
+    spec.report.dir = Some(
+        std::env::temp_dir()
+            .join(format!("revolver_bans_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string(),
+    );
+    config.reports.dir = spec.report.dir.clone().unwrap();
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    gateway.send(UiToEngine::AddTaskFromSpec { spec: spec.clone() }).await;
+
+    let mut banned = None;
+    let mut last: Option<TaskSnapshot> = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        let TaskUpdate::Upsert(snapshot) = update else {
+            continue;
+        };
+        if banned.is_none()
+            && snapshot.iteration == 0
+            && let Some(best) = snapshot.verified.iter().max_by(|a, b| a.score.total_cmp(&b.score))
+        {
+            banned = Some(best.id);
+            let ban = UiToEngine::BanHypothesis {
+                task_id: snapshot.id,
+                hypothesis_id: best.id,
+            };
+            gateway.send(ban).await;
+        }
+        let done = matches!(snapshot.status, TaskStatus::Done | TaskStatus::Failed(_));
+        last = Some(snapshot);
+        if done {
+            break;
+        }
+    }
+    let last = last.expect("no task updates");
+    let banned = banned.expect("no seed hypothesis was verified");
+    assert!(matches!(last.status, TaskStatus::Done));
+    assert!(ids(&last.discarded).contains(&banned));
+    let remaining_best = last.verified.iter().map(|h| h.score).fold(0.0f32, f32::max);
+    assert_eq!(last.best_score, remaining_best, "the banned hypothesis still counts");
+
+    gateway
+        .send(UiToEngine::PinHypothesis {
+            task_id: last.id,
+            hypothesis_id: banned,
+        })
+        .await;
+    let dropped = loop {
+        match timeout(Duration::from_secs(5), gateway.recv()).await {
+            Ok(Some(EngineToUi::TaskUpdate(TaskUpdate::SteeringDropped {
+                hypothesis_id,
+                message,
+                ..
+            }))) => break (hypothesis_id, message),
+            Ok(Some(_)) => continue,
+            other => panic!("no dropped steering reported: {other:?}"),
+        }
+    };
+    gateway.send(UiToEngine::Shutdown).await;
+    let _ = std::fs::remove_dir_all(spec.report.dir.unwrap());
+
+    assert_eq!(dropped.0, Some(banned));
+    assert_eq!(
+        dropped.1,
+        format!("Pin of hypothesis {banned} was dropped: task 1 is no longer running.")
+    );
+}
+
+/// A spec reporting into its own temp dir and a config without engine sleeps.
+fn quick_task(name: &str) -> (TaskSpec, AppConfig) {
+    let mut config = AppConfig::default();
+    config.engine.generating_sleep_ms = 0;
+    config.engine.reducing_sleep_ms = 0;
+    config.engine.synthesizing_sleep_ms = 0;
+    config.engine.testing_sleep_ms = 0;
+    let mut spec = TaskSpec::named(name);
+    let dir = std::env::temp_dir().join(format!("revolver_{name}_{}", std::process::id()));
+    spec.report.dir = Some(dir.to_string_lossy().to_string());
+    config.reports.dir = spec.report.dir.clone().unwrap();
+    (spec, config)
+}
+
+#[tokio::test]
+async fn hypothesis_ids_stay_unique_past_a_hundred_per_iteration() {
+    let (mut spec, config) = quick_task("many_seeds");
+    spec.hypotheses = (0..150).map(|n| format!("seed {n}")).collect();
+    spec.budget.max_iters = 2;
+    spec.budget.patience = 2;
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    gateway.send(UiToEngine::AddTaskFromSpec { spec: spec.clone() }).await;
+
+    let mut last: Option<TaskSnapshot> = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        if let TaskUpdate::Upsert(snapshot) = update {
+            let done = matches!(snapshot.status, TaskStatus::Done | TaskStatus::Failed(_));
+            last = Some(snapshot);
+            if done {
+                break;
+            }
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    let _ = std::fs::remove_dir_all(spec.report.dir.unwrap());
+
+    let last = last.expect("no task updates");
+    assert!(matches!(last.status, TaskStatus::Done));
+    let mut all = ids(&last.verified);
+    all.extend(ids(&last.discarded));
+    assert!(last.history.len() == 3 && all.len() > 150, "{:?}", last.history);
+    all.sort();
+    all.dedup();
+    assert_eq!(all.len(), last.verified.len() + last.discarded.len());
+}
+
+#[tokio::test]
+async fn injections_after_the_last_generation_are_still_evaluated() {
+    let (mut spec, mut config) = quick_task("late_inject");
+    config.engine.reducing_sleep_ms = 200;
+    spec.budget.max_iters = 1;
+
+    let (cmd_tx, cmd_rx) = mpsc::channel(8);
+    let (update_tx, update_rx) = mpsc::channel(128);
+    tokio::spawn(run_scheduler_with_config(cmd_rx, update_tx, config));
+    let mut gateway = Gateway::new(cmd_tx, update_rx);
+    gateway.send(UiToEngine::AddTaskFromSpec { spec: spec.clone() }).await;
+
+    let mut injected = false;
+    let mut last: Option<TaskSnapshot> = None;
+    while let Ok(Some(EngineToUi::TaskUpdate(update))) =
+        timeout(Duration::from_secs(5), gateway.recv()).await
+    {
+        let TaskUpdate::Upsert(snapshot) = update else {
+            continue;
+        };
+        // The only iteration has already generated, so no later one can pick this up.
+        if !injected && matches!(snapshot.phase, TaskPhase::Reducing) {
+            gateway
+                .send(UiToEngine::InjectHypothesis {
+                    task_id: snapshot.id,
+                    text: "count dark pixels near the rim".to_string(),
+                })
+                .await;
+            injected = true;
+        }
+        let done = matches!(snapshot.status, TaskStatus::Done | TaskStatus::Failed(_));
+        last = Some(snapshot);
+        if done {
+            break;
+        }
+    }
+    gateway.send(UiToEngine::Shutdown).await;
+    let _ = std::fs::remove_dir_all(spec.report.dir.unwrap());
+
+    let last = last.expect("no task updates");
+    assert!(injected && matches!(last.status, TaskStatus::Done));
+    let found = last
+        .verified
+        .iter()
+        .chain(&last.discarded)
+        .find(|h| h.description == "count dark pixels near the rim")
+        .expect("late injection was lost");
+    assert_eq!(found.iteration, 1);
+}
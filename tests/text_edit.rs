@@ -0,0 +1,131 @@
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use revolver::app::{AppEvent, AppState, MainScreenEvent, TextEditEvent};
+use revolver::screens::{dispatch_key, dispatch_paste, task_input, FragmentId};
+use revolver::text::TextBuffer;
+use revolver::ui::truncate;
+
+use common::apply;
+
+fn key(app: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+    dispatch_key(KeyEvent::new(code, modifiers), app).unwrap();
+    apply(app);
+}
+
+fn typed(app: &mut AppState, text: &str) {
+    for ch in text.chars() {
+        key(app, KeyCode::Char(ch), KeyModifiers::NONE);
+    }
+}
+
+fn ctrl(app: &mut AppState, ch: char) {
+    key(app, KeyCode::Char(ch), KeyModifiers::CONTROL);
+}
+
+#[test]
+fn task_fields_support_line_and_word_editing() {
+    let mut app = AppState::new();
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    typed(&mut app, "edge probe v2");
+    assert_eq!(app.input.as_str(), "edge probe v2");
+
+    ctrl(&mut app, 'w');
+    assert_eq!(app.input.as_str(), "edge probe ");
+    key(&mut app, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(app.input.cursor(), 5);
+    key(&mut app, KeyCode::Delete, KeyModifiers::NONE);
+    assert_eq!(app.input.as_str(), "edge robe ");
+    ctrl(&mut app, 'k');
+    assert_eq!(app.input.as_str(), "edge ");
+    ctrl(&mut app, 'a');
+    assert_eq!(app.input.cursor(), 0);
+    key(&mut app, KeyCode::Char('f'), KeyModifiers::ALT);
+    assert_eq!(app.input.cursor(), 4);
+    ctrl(&mut app, 'u');
+    assert_eq!((app.input.as_str(), app.input.cursor()), (" ", 0));
+    key(&mut app, KeyCode::End, KeyModifiers::NONE);
+    typed(&mut app, "é!");
+    key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+    key(&mut app, KeyCode::Backspace, KeyModifiers::ALT);
+    assert_eq!(app.input.as_str(), " !");
+
+    ctrl(&mut app, 'x');
+    key(&mut app, KeyCode::Char('q'), KeyModifiers::ALT);
+    assert_eq!(app.input.as_str(), " !", "modified characters are never typed");
+}
+
+#[test]
+fn pastes_insert_text_on_one_line() {
+    let mut app = AppState::new();
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    key(&mut app, KeyCode::F(2), KeyModifiers::NONE);
+    assert_eq!(app.fragment, FragmentId::TaskHypotheses);
+    dispatch_paste("dark\nedges\r\n", &mut app);
+    apply(&mut app);
+    assert_eq!(app.draft.hypotheses.len(), 1);
+    let title = &app.draft.hypotheses[0].title;
+    assert_eq!((title.as_str(), title.cursor()), ("dark edges", "dark edges".len()));
+
+    let mut app = AppState::new();
+    dispatch_paste("ignored", &mut app);
+    assert!(app.pop_event().is_none());
+    typed(&mut app, ":");
+    dispatch_paste("retry 1", &mut app);
+    apply(&mut app);
+    ctrl(&mut app, 'w');
+    assert_eq!(app.command_line.text.as_str(), "retry ");
+    key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+    typed(&mut app, "q");
+    assert_eq!(app.command_line.text.as_str(), "retryq ");
+    assert_eq!(app.fragment, FragmentId::MainInput);
+}
+
+#[test]
+fn text_buffers_edit_whole_graphemes() {
+    let mut buffer = TextBuffer::new("e\u{301}🎉x");
+    buffer.apply(&TextEditEvent::CursorLeft);
+    buffer.apply(&TextEditEvent::CursorLeft);
+    assert_eq!(buffer.cursor(), "e\u{301}".len());
+    assert_eq!(buffer.cursor_column(), 1);
+    buffer.apply(&TextEditEvent::Delete);
+    assert_eq!(buffer.as_str(), "e\u{301}x");
+    buffer.apply(&TextEditEvent::Backspace);
+    assert_eq!((buffer.as_str(), buffer.cursor()), ("x", 0));
+    buffer.apply(&TextEditEvent::InsertChar('a'));
+    buffer.apply(&TextEditEvent::InsertChar('\u{308}'));
+    assert_eq!((buffer.as_str(), buffer.cursor()), ("a\u{308}x", 3));
+
+    let buffer = TextBuffer::with_cursor("日本", 4);
+    assert_eq!((buffer.cursor(), buffer.cursor_column()), (3, 2));
+
+    assert_eq!(truncate("日本語テキスト", 8), "日本...");
+    assert_eq!(truncate("naïve", 5), "naïve");
+    assert_eq!(truncate("abcdef", 3), "abc");
+    let buffer = TextBuffer::with_cursor("ab日本x", "ab日本".len());
+    assert_eq!(buffer.scrolled(4), ("本x", 2));
+}
+
+#[test]
+fn wide_characters_place_the_field_cursor_by_display_width() {
+    let mut app = AppState::new();
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    typed(&mut app, "café 日本");
+    key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+    assert_eq!(app.cursor_column(), 7);
+    key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+    assert_eq!(app.input.as_str(), "café 本");
+    assert_eq!(app.cursor_column(), 5);
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    terminal.draw(|frame| task_input::draw(frame, &app)).unwrap();
+    let (x, y) = terminal.get_cursor().unwrap();
+    let buffer = terminal.backend().buffer();
+    let name_x = (0..buffer.area.width)
+        .find(|&x| buffer.get(x, y).symbol() == "c")
+        .expect("name is drawn");
+    assert_eq!(x, name_x + 5);
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::env;
+use std::fs;
+
+use ratatui::style::{Color, Modifier};
+
+use revolver::app::{AppEvent, AppState, MainScreenEvent};
+use revolver::config::{AppConfig, ThemeConfig};
+use revolver::engine::scheduler::{LogLevel, TaskUpdate};
+use revolver::screens::theme::{Palette, Theme, ThemeName};
+use revolver::task::TaskStatus;
+
+use common::{app_with, screen, HEIGHT, WIDTH};
+
+fn app_with_theme(theme: Theme) -> AppState {
+    let mut app = app_with(TaskStatus::Pending);
+    app.theme = theme;
+    app.apply_update(TaskUpdate::Log {
+        id: 1,
+        level: LogLevel::Error,
+        message: "evaluation failed".to_string(),
+    });
+    app
+}
+
+#[test]
+fn config_picks_a_palette_and_overrides_roles() {
+    let path = env::temp_dir().join(format!("revolver_{}_theme.toml", std::process::id()));
+    fs::write(
+        &path,
+        "[theme]\nname = \"light\"\n[theme.colors]\nfocus = \"magenta\"\nerror = \"#ff8800\"\n",
+    )
+    .unwrap();
+    let config = AppConfig::load_layers(std::slice::from_ref(&path), Vec::new()).unwrap();
+    let _ = fs::remove_file(path);
+    assert_eq!(config.theme.name, ThemeName::Light);
+
+    let theme = Theme::build(&config.theme, false).unwrap();
+    assert!(theme.color);
+    assert_eq!(theme.palette.focus, Color::Magenta);
+    assert_eq!(theme.palette.error, Color::Rgb(255, 136, 0));
+    assert_eq!(theme.palette.title, Palette::builtin(ThemeName::Light).title);
+
+    let env = vec![("REVOLVER_THEME__NAME".into(), "high_contrast".into())];
+    let config = AppConfig::load_layers(&[], env).unwrap();
+    assert_eq!(config.theme.name, ThemeName::HighContrast);
+}
+
+#[test]
+fn unknown_roles_and_bad_colors_are_config_errors() {
+    let mut theme = ThemeConfig::default();
+    theme.colors.insert("border".to_string(), "red".to_string());
+    let config = AppConfig {
+        theme,
+        ..AppConfig::default()
+    };
+    let err = config.validate().unwrap_err();
+    assert!(err.contains("theme.colors.border: unknown role"), "{err}");
+
+    let mut theme = ThemeConfig::default();
+    theme.colors.insert("focus".to_string(), "#12".to_string());
+    let err = Theme::build(&theme, false).unwrap_err();
+    assert!(err.contains("invalid color '#12'"), "{err}");
+}
+
+#[test]
+fn palettes_color_the_screens() {
+    let mut theme = Theme::build(&ThemeConfig::default(), false).unwrap();
+    theme.palette.focus = Color::Magenta;
+    let buffer = screen(&app_with_theme(theme), WIDTH, HEIGHT);
+    // The tasks box has focus at startup.
+    assert_eq!(buffer.get(1, 3).symbol(), "T");
+    assert_eq!(buffer.get(1, 3).fg, Color::Magenta);
+    assert!(buffer.content.iter().any(|cell| cell.fg == Color::Red), "error log line");
+}
+
+#[test]
+fn no_color_draws_every_screen_without_colors() {
+    let config = ThemeConfig {
+        no_color: true,
+        ..ThemeConfig::default()
+    };
+    assert!(!Theme::build(&config, false).unwrap().color);
+    let mut app = app_with_theme(Theme::build(&ThemeConfig::default(), true).unwrap());
+
+    let mut screens = vec![screen(&app, WIDTH, HEIGHT)];
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenHypotheses));
+    screens.push(screen(&app, WIDTH, HEIGHT));
+    let mut app = app_with_theme(app.theme);
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    screens.push(screen(&app, WIDTH, HEIGHT));
+
+    for buffer in &screens {
+        let colored = buffer
+            .content
+            .iter()
+            .find(|cell| cell.fg != Color::Reset || cell.bg != Color::Reset);
+        assert!(colored.is_none(), "{colored:?}");
+    }
+    // Selection falls back to reversed text.
+    assert!(
+        screens[0]
+            .content
+            .iter()
+            .any(|cell| cell.modifier.contains(Modifier::REVERSED))
+    );
+}
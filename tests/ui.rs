@@ -0,0 +1,516 @@
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use revolver::app::{
+    AppEvent, AppState, DraftList, HeuristicDraft, HypothesesEvent, HypothesisMark,
+    MainScreenEvent, TaskFilter, TaskGrouping, TaskInputEvent, TaskSort, TextEditEvent,
+};
+use revolver::config::AppConfig;
+use revolver::engine::scheduler::{LogLevel, TaskUpdate};
+use revolver::protocol::UiToEngine;
+use revolver::report::ReportFormat;
+use revolver::screens::{main, task_input, FragmentId, ScreenId};
+use revolver::task::{
+    Hypothesis, ImageResult, IterationRecord, TaskDefinition, TaskSnapshot, TaskStatus,
+};
+
+use common::{apply, lines, press, render, rows};
+
+fn rendered(app: &AppState, width: u16, height: u16) -> String {
+    rows(&render(width, height, |frame| main::draw(frame, app))).join("\n")
+}
+
+fn app_with_history() -> AppState {
+    let mut snapshot = TaskSnapshot::from_definition(&TaskDefinition::mock(1, "chart".to_string()));
+    snapshot.history = (1..=3)
+        .map(|iteration| IterationRecord {
+            iteration,
+            generated: 3,
+            verified: 1,
+            best_score: 0.3 * iteration as f32,
+            last_score: 0.25 * iteration as f32,
+            elapsed_ms: 10,
+            program_version: Some(iteration),
+        })
+        .collect();
+    let mut app = AppState::new();
+    app.apply_update(TaskUpdate::Upsert(snapshot));
+    app
+}
+
+#[test]
+fn detail_draws_score_chart_with_threshold() {
+    let screen = rendered(&app_with_history(), 100, 32);
+    assert!(screen.contains("threshold 0.60"), "{screen}");
+    assert!(screen.contains("best"), "{screen}");
+    assert!(screen.chars().any(|ch| ('\u{2800}'..='\u{28FF}').contains(&ch)));
+}
+
+#[test]
+fn detail_falls_back_to_sparkline_when_short() {
+    let screen = rendered(&app_with_history(), 100, 16);
+    assert!(!screen.contains("threshold 0.60"), "{screen}");
+    assert!(screen.contains('█') || screen.contains('▇'), "{screen}");
+}
+
+fn log(id: usize, level: LogLevel, message: &str) -> TaskUpdate {
+    TaskUpdate::Log {
+        id,
+        level,
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn logs_are_kept_per_task_up_to_capacity() {
+    let mut config = AppConfig::default();
+    config.ui.log_capacity = 3;
+    let mut app = AppState::with_config(config);
+    for id in 1..=2 {
+        app.apply_update(TaskUpdate::Upsert(TaskSnapshot::from_definition(
+            &TaskDefinition::mock(id, format!("task {id}")),
+        )));
+    }
+    for i in 0..5 {
+        app.apply_update(log(1, LogLevel::Info, &format!("one {i}")));
+    }
+    app.apply_update(log(2, LogLevel::Error, "two failed"));
+
+    let messages = app.visible_logs().iter().map(|e| e.message.clone()).collect::<Vec<_>>();
+    assert_eq!(messages, vec!["one 2", "one 3", "one 4"]);
+    assert_eq!(app.last_log(2).map(|e| e.level), Some(LogLevel::Error));
+}
+
+#[test]
+fn log_pane_scrolls_and_searches() {
+    let mut app = app_with_history();
+    for i in 0..30 {
+        let level = if i % 10 == 9 { LogLevel::Warn } else { LogLevel::Info };
+        app.apply_update(log(1, level, &format!("line {i:02}")));
+    }
+    for event in [MainScreenEvent::FocusLogs, MainScreenEvent::ScrollLogsTop] {
+        app.apply_event(AppEvent::Main(event));
+    }
+    assert_eq!(app.log_view.scroll, 29);
+    let screen = rendered(&app, 100, 32);
+    assert!(screen.contains("INFO  line 00"), "{screen}");
+    assert!(!screen.contains("INFO  line 29"), "{screen}");
+
+    app.apply_event(AppEvent::Main(MainScreenEvent::StartLogSearch));
+    for ch in "LINE 1".chars() {
+        app.apply_event(AppEvent::Main(MainScreenEvent::LogSearch(TextEditEvent::InsertChar(ch))));
+    }
+    app.apply_event(AppEvent::Main(MainScreenEvent::EndLogSearch));
+    assert_eq!(app.visible_logs().len(), 10);
+    assert_eq!(app.log_view.scroll, 0);
+    let screen = rendered(&app, 100, 32);
+    assert!(screen.contains("/LINE 1 (10 matches)"), "{screen}");
+    assert!(screen.contains("WARN  line 19"), "{screen}");
+    assert!(!screen.contains("INFO  line 20"), "{screen}");
+
+    app.apply_event(AppEvent::Main(MainScreenEvent::ClearLogSearch));
+    assert_eq!(app.visible_logs().len(), 30);
+}
+
+fn explorer_hypothesis(id: usize, score: f32) -> Hypothesis {
+    Hypothesis {
+        id,
+        iteration: id / 100,
+        description: format!("idea {id}"),
+        rationale: format!("because {id}"),
+        score,
+        results: vec![ImageResult {
+            image_id: 0,
+            image: "image_1_00.png".to_string(),
+            path: None,
+            passed: score > 0.5,
+        }],
+    }
+}
+
+#[test]
+fn hypothesis_explorer_sorts_filters_and_marks() {
+    let mut snapshot = TaskSnapshot::from_definition(&TaskDefinition::mock(1, "explore".to_string()));
+    snapshot.verified = vec![explorer_hypothesis(100, 0.7), explorer_hypothesis(201, 0.9)];
+    snapshot.discarded = vec![explorer_hypothesis(101, 0.2)];
+    let mut app = AppState::new();
+    app.apply_update(TaskUpdate::Upsert(snapshot));
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenHypotheses));
+    assert_eq!(app.screen, ScreenId::Hypotheses);
+
+    let ids = |app: &AppState| app.explorer_rows().iter().map(|r| r.hypothesis.id).collect::<Vec<_>>();
+    assert_eq!(ids(&app), vec![201, 100, 101]);
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::CycleSort));
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::CycleSort));
+    assert_eq!(ids(&app), vec![100, 101, 201]);
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::CycleFilter));
+    assert_eq!(ids(&app), vec![100, 201]);
+
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::SelectNext));
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::TogglePin));
+    assert_eq!(app.hypothesis_mark(1, 201), Some(HypothesisMark::Pinned));
+    assert!(matches!(
+        result.cmd,
+        Some(UiToEngine::PinHypothesis { task_id: 1, hypothesis_id: 201 })
+    ));
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::ToggleReject));
+    assert_eq!(app.hypothesis_mark(1, 201), Some(HypothesisMark::Rejected));
+    assert!(matches!(result.cmd, Some(UiToEngine::BanHypothesis { .. })));
+
+    let screen = lines(&app, 120, 30).join("\n");
+    assert!(screen.contains("sort: iteration | filter: verified"), "{screen}");
+    assert!(screen.contains("because 201"), "{screen}");
+    assert!(screen.contains("Per-image results (1/1 passed)"), "{screen}");
+    assert!(screen.contains("rejected"), "{screen}");
+
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::ToggleReject));
+    assert_eq!(app.hypothesis_mark(1, 201), None);
+    assert!(matches!(result.cmd, Some(UiToEngine::ReleaseHypothesis { .. })));
+
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::TogglePin));
+    app.apply_update(TaskUpdate::SteeringDropped {
+        id: 1,
+        hypothesis_id: Some(201),
+        message: "Pin of hypothesis 201 was dropped: task 1 is no longer running.".to_string(),
+    });
+    assert_eq!(app.hypothesis_mark(1, 201), None, "a dropped pin clears its mark");
+    assert_eq!(app.dialogs[0].title, "Steering dropped");
+    app.dialogs.clear();
+
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::StartInject));
+    for ch in "new idea".chars() {
+        app.apply_event(AppEvent::Hypotheses(HypothesesEvent::InjectEdit(
+            TextEditEvent::InsertChar(ch),
+        )));
+    }
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::SubmitInject));
+    assert!(app.explorer.inject.is_none());
+    match result.cmd {
+        Some(UiToEngine::InjectHypothesis { task_id, text }) => {
+            assert_eq!((task_id, text.as_str()), (1, "new idea"));
+        }
+        other => panic!("unexpected command {other:?}"),
+    }
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::Close));
+    assert_eq!(app.screen, ScreenId::Main);
+}
+
+#[test]
+fn explorer_selection_follows_the_hypothesis_across_updates() {
+    let mut snapshot = TaskSnapshot::from_definition(&TaskDefinition::mock(1, "explore".to_string()));
+    snapshot.verified = vec![explorer_hypothesis(100, 0.7), explorer_hypothesis(201, 0.9)];
+    snapshot.discarded = vec![explorer_hypothesis(101, 0.2)];
+    let mut app = AppState::new();
+    app.apply_update(TaskUpdate::Upsert(snapshot.clone()));
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenHypotheses));
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::SelectNext));
+    assert_eq!(app.selected_explorer_row().unwrap().hypothesis.id, 100);
+
+    // A better hypothesis arrives above the selection.
+    snapshot.verified.push(explorer_hypothesis(302, 0.95));
+    app.apply_update(TaskUpdate::Upsert(snapshot.clone()));
+    assert_eq!(app.selected_explorer_row().unwrap().hypothesis.id, 100);
+
+    // The engine moves the banned hypothesis to the discarded list.
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::ToggleReject));
+    assert!(matches!(
+        result.cmd,
+        Some(UiToEngine::BanHypothesis { task_id: 1, hypothesis_id: 100 })
+    ));
+    let banned = snapshot.verified.remove(0);
+    snapshot.discarded.insert(0, banned);
+    app.apply_update(TaskUpdate::Upsert(snapshot.clone()));
+    let result = app.apply_event(AppEvent::Hypotheses(HypothesesEvent::ToggleReject));
+    assert!(matches!(
+        result.cmd,
+        Some(UiToEngine::ReleaseHypothesis { task_id: 1, hypothesis_id: 100 })
+    ));
+
+    app.apply_event(AppEvent::Hypotheses(HypothesesEvent::CycleFilter));
+    assert_eq!(app.selected_explorer_row().unwrap().hypothesis.id, 302);
+    snapshot.verified.clear();
+    app.apply_update(TaskUpdate::Upsert(snapshot));
+    assert!(app.explorer_rows().is_empty());
+    assert_eq!(app.explorer.selected, 0);
+}
+
+fn type_line(app: &mut AppState, line: &str) -> Vec<UiToEngine> {
+    let mut keys = line.chars().map(KeyCode::Char).collect::<Vec<_>>();
+    keys.push(KeyCode::Enter);
+    press(app, &keys).0
+}
+
+#[test]
+fn command_line_dispatches_commands_with_history_and_completion() {
+    let mut app = AppState::new();
+    for id in 1..=2 {
+        let mut snapshot =
+            TaskSnapshot::from_definition(&TaskDefinition::mock(id, format!("task {id}")));
+        snapshot.status = if id == 1 { TaskStatus::Done } else { TaskStatus::Running };
+        app.apply_update(TaskUpdate::Upsert(snapshot));
+    }
+
+    press(&mut app, &[KeyCode::Char(':')]);
+    assert_eq!(app.fragment, FragmentId::MainInput);
+    let commands = type_line(&mut app, ":cancel 2");
+    assert!(matches!(commands.as_slice(), [UiToEngine::CancelTask { id: 2 }]));
+    assert_eq!(
+        app.command_line.message,
+        Some((LogLevel::Info, "Cancelling task 2.".to_string()))
+    );
+
+    assert!(type_line(&mut app, "retry 2").is_empty());
+    assert_eq!(
+        app.command_line.message,
+        Some((LogLevel::Error, "Task 2 is still running.".to_string()))
+    );
+    let commands = type_line(&mut app, "export 1 json");
+    assert!(matches!(
+        commands.as_slice(),
+        [UiToEngine::ExportReport { id: 1, format: ReportFormat::Json }]
+    ));
+    assert!(type_line(&mut app, "bogus").is_empty());
+    assert_eq!(
+        app.command_line.message,
+        Some((LogLevel::Error, "Unknown command 'bogus'.".to_string()))
+    );
+
+    press(&mut app, &[KeyCode::Up, KeyCode::Up]);
+    assert_eq!(app.command_line.text.as_str(), "export 1 json");
+    press(&mut app, &[KeyCode::Down, KeyCode::Down]);
+    assert_eq!(app.command_line.text.as_str(), "");
+
+    press(&mut app, &[KeyCode::Char('r'), KeyCode::Char('e'), KeyCode::Tab]);
+    assert_eq!(app.command_line.text.as_str(), "retry ");
+    let (commands, _) = press(&mut app, &[KeyCode::Tab, KeyCode::Char('1'), KeyCode::Enter]);
+    assert!(matches!(commands.as_slice(), [UiToEngine::RetryTask { id: 1 }]));
+
+    type_line(&mut app, "filter running");
+    assert_eq!(app.task_filter, TaskFilter::Running);
+    let tasks = app.tasks_in_order();
+    assert_eq!(tasks.iter().map(|task| task.id).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(app.selected_task().map(|task| task.id), Some(2));
+    assert!(rendered(&app, 100, 30).contains("Tasks [T] (running)"));
+
+    press(&mut app, &[KeyCode::Esc]);
+    assert_eq!(app.fragment, FragmentId::MainTasks);
+    assert!(press(&mut app, &[KeyCode::Char('c')]).0.is_empty(), "asks first");
+    let (commands, _) = press(&mut app, &[KeyCode::Char('y')]);
+    assert!(matches!(commands.as_slice(), [UiToEngine::CancelTask { id: 2 }]));
+}
+
+fn ids(app: &AppState) -> Vec<usize> {
+    app.tasks_in_order().iter().map(|task| task.id).collect()
+}
+
+#[test]
+fn task_list_filters_sorts_groups_and_searches_by_name() {
+    let mut app = AppState::new();
+    let tasks = [
+        ("edges", TaskStatus::Done, 0.9, 1.0),
+        ("blobs", TaskStatus::Running, 0.4, 0.5),
+        ("edge fusion", TaskStatus::Running, 0.7, 0.2),
+        ("contrast", TaskStatus::Pending, 0.0, 0.0),
+    ];
+    for (index, (name, status, score, progress)) in tasks.into_iter().enumerate() {
+        let mut snapshot =
+            TaskSnapshot::from_definition(&TaskDefinition::mock(index + 1, name.to_string()));
+        snapshot.status = status;
+        snapshot.best_score = score;
+        snapshot.progress = progress;
+        app.apply_update(TaskUpdate::Upsert(snapshot));
+    }
+    press(&mut app, &[KeyCode::Down]);
+    assert_eq!(app.selected_task().map(|task| task.id), Some(2));
+
+    press(&mut app, &[KeyCode::Char('s'), KeyCode::Char('s')]);
+    assert_eq!(app.task_sort, TaskSort::Score);
+    assert_eq!(ids(&app), vec![1, 3, 2, 4]);
+    assert_eq!(app.selected_task().map(|task| task.id), Some(2), "selection follows the task");
+
+    // A better score moves the task, and the selection with it.
+    let mut blobs = app.task(2).unwrap().clone();
+    blobs.best_score = 0.95;
+    app.apply_update(TaskUpdate::Upsert(blobs));
+    assert_eq!(ids(&app), vec![2, 1, 3, 4]);
+    assert_eq!(app.selected, 0);
+
+    press(&mut app, &[KeyCode::Char('g')]);
+    assert_eq!(app.task_grouping, TaskGrouping::Status);
+    assert_eq!(ids(&app), vec![2, 3, 4, 1]);
+    let screen = rendered(&app, 100, 30);
+    assert!(screen.contains("Tasks [T] (by score, grouped)"), "{screen}");
+    for group in ["running", "pending", "done"] {
+        // Group rows carry the status in the Name column.
+        assert!(screen.contains(&format!("┆      {group} ")), "{screen}");
+    }
+
+    press(&mut app, &[KeyCode::Char('f')]);
+    press(&mut app, &"EDGE".chars().map(KeyCode::Char).collect::<Vec<_>>());
+    assert_eq!(ids(&app), vec![3, 1]);
+    assert!(rendered(&app, 140, 30).contains("/EDGE_ (2 matches)"));
+    press(&mut app, &[KeyCode::Enter, KeyCode::Tab, KeyCode::Tab]);
+    assert_eq!(app.task_filter, TaskFilter::Running);
+    assert_eq!(ids(&app), vec![3]);
+
+    press(&mut app, &[KeyCode::Char('f'), KeyCode::Esc]);
+    assert_eq!(app.task_search.query, "");
+    assert_eq!(ids(&app), vec![2, 3]);
+    type_line(&mut app, ":sort newest");
+    type_line(&mut app, "group none");
+    press(&mut app, &[KeyCode::Esc]);
+    assert_eq!(ids(&app), vec![3, 2]);
+}
+
+#[test]
+fn seed_hypotheses_are_editable_and_submitted() {
+    let mut app = AppState::new();
+    press(&mut app, &[KeyCode::Char('n')]);
+    let send = |app: &mut AppState, key: KeyEvent| {
+        task_input::handle_key(key, app).unwrap();
+        apply(app);
+    };
+    let input = |app: &mut AppState, keys: &[KeyCode]| {
+        for key in keys {
+            send(app, KeyEvent::from(*key));
+        }
+    };
+    input(&mut app, &"probe".chars().map(KeyCode::Char).collect::<Vec<_>>());
+
+    let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+    input(&mut app, &[KeyCode::F(2)]);
+    send(&mut app, ctrl_n);
+    assert_eq!(app.fragment, FragmentId::TaskHypotheses);
+    input(&mut app, &"Edge + blob fusion".chars().map(KeyCode::Char).collect::<Vec<_>>());
+    assert_eq!(app.draft.hypotheses.len(), 1, "'+' is typed, not bound");
+    assert_eq!(app.draft.hypotheses[0].title.as_str(), "Edge + blob fusion");
+    for _ in 0.."Edge + blob fusion".len() {
+        input(&mut app, &[KeyCode::Backspace]);
+    }
+    input(&mut app, &"edges".chars().map(KeyCode::Char).collect::<Vec<_>>());
+    send(&mut app, ctrl_n);
+    input(&mut app, &"blobs".chars().map(KeyCode::Char).collect::<Vec<_>>());
+    input(&mut app, &[KeyCode::Left, KeyCode::Backspace, KeyCode::Char('e')]);
+    send(&mut app, ctrl_n);
+    input(&mut app, &[KeyCode::Char('x')]);
+    let titles = |app: &AppState| {
+        app.draft
+            .hypotheses
+            .iter()
+            .map(|h| String::from(h.title.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(titles(&app), vec!["edges", "bloes", "x"]);
+
+    let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+    task_input::handle_key(ctrl_d, &mut app).unwrap();
+    input(&mut app, &[KeyCode::PageUp]);
+    assert_eq!(titles(&app), vec!["bloes", "edges"]);
+    assert_eq!(app.draft.selected_hypothesis, 0);
+    input(&mut app, &[KeyCode::PageUp, KeyCode::Down]);
+    send(&mut app, ctrl_n);
+    assert_eq!(titles(&app), vec!["bloes", "edges", ""]);
+
+    render(120, 30, |frame| task_input::draw(frame, &app));
+
+    input(&mut app, &[KeyCode::F(1)]);
+    assert_eq!(app.input.as_str(), "probe");
+    let result = {
+        task_input::handle_key(KeyEvent::from(KeyCode::Enter), &mut app).unwrap();
+        let event = app.pop_event().unwrap();
+        app.apply_event(event)
+    };
+    match result.cmd {
+        Some(UiToEngine::AddTaskFromSpec { spec }) => {
+            assert_eq!(spec.name, "probe");
+            assert_eq!(spec.hypotheses, vec!["bloes", "edges"]);
+        }
+        other => panic!("unexpected command {other:?}"),
+    }
+}
+
+fn image_paths(heuristic: &HeuristicDraft) -> Vec<String> {
+    heuristic.images.iter().map(|image| image.as_str().to_string()).collect()
+}
+
+#[test]
+fn heuristics_and_images_can_be_deleted_duplicated_and_moved() {
+    let mut app = AppState::new();
+    let events = [
+        TaskInputEvent::SwitchField,
+        TaskInputEvent::SwitchField,
+        TaskInputEvent::AddHeuristic,
+        TaskInputEvent::AddHeuristic,
+    ];
+    app.apply_event(AppEvent::Main(MainScreenEvent::OpenTaskInput));
+    for event in events {
+        app.apply_event(AppEvent::TaskInput(event));
+    }
+    assert_eq!(app.focused_list(), Some(DraftList::Heuristics));
+    let titles = |app: &AppState| {
+        app.draft
+            .heuristics
+            .iter()
+            .map(|h| (String::from(h.title.clone()), image_paths(h)))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        titles(&app),
+        vec![
+            ("Heuristic 1".to_string(), Vec::new()),
+            ("Heuristic 2".to_string(), Vec::new()),
+        ]
+    );
+
+    let images = |app: &AppState| image_paths(&app.draft.heuristics[app.draft.selected_heuristic]);
+    let apply = |app: &mut AppState, events: &[TaskInputEvent]| {
+        for event in events {
+            app.apply_event(AppEvent::TaskInput(event.clone()));
+        }
+    };
+    apply(
+        &mut app,
+        &[
+            TaskInputEvent::Edit(TextEditEvent::CursorRight),
+            TaskInputEvent::Edit(TextEditEvent::InsertChar('a')),
+            TaskInputEvent::AddImage,
+            TaskInputEvent::Edit(TextEditEvent::InsertChar('b')),
+            TaskInputEvent::DuplicateItem,
+        ],
+    );
+    assert_eq!(app.focused_list(), Some(DraftList::Images));
+    assert_eq!(images(&app), vec!["a", "b", "b"]);
+    apply(&mut app, &[TaskInputEvent::MoveItemUp, TaskInputEvent::MoveItemUp]);
+    assert_eq!(app.draft.selected_image, 0);
+    apply(&mut app, &[TaskInputEvent::Edit(TextEditEvent::InsertChar('c'))]);
+    assert_eq!(images(&app), vec!["bc", "a", "b"]);
+    apply(&mut app, &[TaskInputEvent::DeleteItem, TaskInputEvent::DeleteItem]);
+    assert_eq!(images(&app), vec!["b"]);
+
+    apply(
+        &mut app,
+        &[
+            TaskInputEvent::Edit(TextEditEvent::CursorLeft),
+            TaskInputEvent::Edit(TextEditEvent::CursorLeft),
+            TaskInputEvent::DuplicateItem,
+        ],
+    );
+    assert_eq!(app.focused_list(), Some(DraftList::Heuristics));
+    assert_eq!(app.draft.selected_heuristic, 2);
+    apply(&mut app, &[TaskInputEvent::MoveItemDown, TaskInputEvent::MoveItemUp]);
+    assert_eq!(
+        titles(&app),
+        vec![
+            ("Heuristic 1".to_string(), Vec::new()),
+            ("Heuristic 2".to_string(), vec!["b".to_string()]),
+            ("Heuristic 2".to_string(), vec!["b".to_string()]),
+        ]
+    );
+    apply(&mut app, &[TaskInputEvent::MoveItemUp, TaskInputEvent::MoveItemUp]);
+    assert_eq!(app.draft.selected_heuristic, 0);
+    assert_eq!(images(&app), vec!["b"]);
+    apply(&mut app, &[TaskInputEvent::DeleteItem]);
+    assert_eq!(app.draft.heuristics.len(), 2);
+    assert_eq!(app.draft.heuristics[0].title.as_str(), "Heuristic 1");
+}